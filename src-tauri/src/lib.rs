@@ -17,15 +17,54 @@ pub fn run() {
             modules::openclaw_installer::install_openclaw,
             modules::openclaw_installer::check_openclaw_update,
             modules::openclaw_installer::update_openclaw,
+            modules::openclaw_installer::verify_openclaw_install,
+            modules::plugin_manager::list_plugins,
+            modules::plugin_manager::install_plugin,
+            modules::plugin_manager::update_plugin,
+            modules::plugin_manager::remove_plugin,
             modules::llm_config::list_providers,
+            modules::llm_config::get_provider_catalog_status,
+            modules::llm_config::refresh_provider_catalog,
+            modules::llm_config::discover_models,
+            modules::llm_config::save_custom_provider,
+            modules::llm_config::remove_custom_provider,
             modules::llm_config::get_llm_config_state,
             modules::llm_config::save_llm_config,
-            modules::llm_config::load_api_keys,
+            modules::llm_config::check_provider_endpoint,
+            modules::llm_config::list_llm_profiles,
+            modules::llm_config::save_llm_profile,
+            modules::llm_config::activate_llm_profile,
+            modules::llm_config::delete_llm_profile,
+            modules::llm_config::list_api_keys,
+            modules::llm_config::select_api_key,
+            modules::llm_config::rotate_api_key,
+            modules::llm_config::delete_api_key,
+            modules::llm_config::detect_existing_credentials,
+            modules::llm_config::import_detected_credentials,
+            modules::llm_config::validate_api_key,
+            modules::secret_store::get_secret_store_status,
+            modules::secret_store::unlock_secret_store,
+            modules::secret_store::lock_secret_store,
+            modules::secret_store::set_secret_store_passphrase,
             modules::service_manager::start_gateway,
             modules::service_manager::stop_gateway,
+            modules::service_manager::restart_gateway,
             modules::service_manager::get_gateway_status,
             modules::service_manager::health_check,
             modules::browser_launcher::open_webchat,
+            modules::openclaw_config::get_openclaw_config,
+            modules::openclaw_config::apply_config_patch,
+            modules::openclaw_config::list_config_versions,
+            modules::openclaw_config::diff_config_versions,
+            modules::openclaw_config::restore_config_version,
+            modules::ollama::get_ollama_status,
+            modules::ollama::list_ollama_models,
+            modules::ollama::pull_ollama_model,
+            modules::ollama::delete_ollama_model,
+            modules::setup_bundle::export_setup,
+            modules::setup_bundle::import_setup,
+            modules::config_watcher::get_config_watch_status,
+            modules::config_watcher::set_restart_gateway_on_config_change,
         ])
         .setup(|app| {
             if cfg!(debug_assertions) {
@@ -35,6 +74,14 @@ pub fn run() {
                         .build(),
                 )?;
             }
+            match modules::single_instance::init(app.handle()) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(0),
+                Err(err) => log::warn!("single-instance check unavailable: {err}"),
+            }
+            if let Err(err) = modules::config_watcher::init(app.handle()) {
+                log::warn!("config watcher unavailable: {err}");
+            }
             Ok(())
         })
         .run(tauri::generate_context!())
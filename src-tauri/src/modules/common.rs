@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Manager};
 
 pub const MIN_NODE_MAJOR: u64 = 22;
@@ -32,6 +35,7 @@ pub fn get_install_path_override(app: &AppHandle) -> Result<Option<PathBuf>, Str
 pub fn set_install_path_override(app: &AppHandle, path: String) -> Result<PathBuf, String> {
     let normalized = normalize_user_path(path)?;
     validate_writable_dir(&normalized)?;
+    let _lock = InstallLock::acquire(&app_data_dir(app)?, "set_install_path")?;
 
     let mut settings = read_settings(app)?;
     settings.install_path = Some(normalized.to_string_lossy().to_string());
@@ -41,6 +45,7 @@ pub fn set_install_path_override(app: &AppHandle, path: String) -> Result<PathBu
 }
 
 pub fn reset_install_path_override(app: &AppHandle) -> Result<PathBuf, String> {
+    let _lock = InstallLock::acquire(&app_data_dir(app)?, "reset_install_path")?;
     let mut settings = read_settings(app)?;
     settings.install_path = None;
     write_settings(app, &settings)?;
@@ -50,6 +55,106 @@ pub fn reset_install_path_override(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(default_dir)
 }
 
+/// Name of the secret-storage backend chosen on first use, if any.
+pub fn get_secret_backend(app: &AppHandle) -> Result<Option<String>, String> {
+    Ok(read_settings(app)?.secret_backend)
+}
+
+pub fn set_secret_backend(app: &AppHandle, backend: &str) -> Result<(), String> {
+    let mut settings = read_settings(app)?;
+    settings.secret_backend = Some(backend.to_string());
+    write_settings(app, &settings)
+}
+
+pub fn get_catalog_url(app: &AppHandle) -> Result<Option<String>, String> {
+    Ok(read_settings(app)?.catalog_url)
+}
+
+pub fn set_catalog_url(app: &AppHandle, url: &str) -> Result<(), String> {
+    let mut settings = read_settings(app)?;
+    settings.catalog_url = Some(url.to_string());
+    write_settings(app, &settings)
+}
+
+pub fn get_custom_providers(app: &AppHandle) -> Result<Vec<CustomProvider>, String> {
+    Ok(read_settings(app)?.custom_providers)
+}
+
+pub fn set_custom_providers(app: &AppHandle, providers: Vec<CustomProvider>) -> Result<(), String> {
+    let mut settings = read_settings(app)?;
+    settings.custom_providers = providers;
+    write_settings(app, &settings)
+}
+
+pub fn get_llm_profiles(app: &AppHandle) -> Result<Vec<LlmProfile>, String> {
+    Ok(read_settings(app)?.llm_profiles)
+}
+
+pub fn set_llm_profiles(app: &AppHandle, profiles: Vec<LlmProfile>) -> Result<(), String> {
+    let mut settings = read_settings(app)?;
+    settings.llm_profiles = profiles;
+    write_settings(app, &settings)
+}
+
+pub fn get_provider_base_urls(app: &AppHandle) -> Result<BTreeMap<String, String>, String> {
+    Ok(read_settings(app)?.provider_base_urls)
+}
+
+pub fn set_provider_base_urls(
+    app: &AppHandle,
+    base_urls: BTreeMap<String, String>,
+) -> Result<(), String> {
+    let mut settings = read_settings(app)?;
+    settings.provider_base_urls = base_urls;
+    write_settings(app, &settings)
+}
+
+pub fn get_restart_gateway_on_config_change(app: &AppHandle) -> Result<bool, String> {
+    Ok(read_settings(app)?.restart_gateway_on_config_change)
+}
+
+pub fn set_restart_gateway_on_config_change(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = read_settings(app)?;
+    settings.restart_gateway_on_config_change = enabled;
+    write_settings(app, &settings)
+}
+
+/// The settings that mean the same thing on another machine. The install
+/// path and secret backend stay behind.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PortableSettings {
+    pub catalog_url: Option<String>,
+    #[serde(default)]
+    pub custom_providers: Vec<CustomProvider>,
+    #[serde(default)]
+    pub llm_profiles: Vec<LlmProfile>,
+    #[serde(default)]
+    pub provider_base_urls: BTreeMap<String, String>,
+    #[serde(default)]
+    pub restart_gateway_on_config_change: bool,
+}
+
+pub fn get_portable_settings(app: &AppHandle) -> Result<PortableSettings, String> {
+    let settings = read_settings(app)?;
+    Ok(PortableSettings {
+        catalog_url: settings.catalog_url,
+        custom_providers: settings.custom_providers,
+        llm_profiles: settings.llm_profiles,
+        provider_base_urls: settings.provider_base_urls,
+        restart_gateway_on_config_change: settings.restart_gateway_on_config_change,
+    })
+}
+
+pub fn set_portable_settings(app: &AppHandle, portable: &PortableSettings) -> Result<(), String> {
+    let mut settings = read_settings(app)?;
+    settings.catalog_url = portable.catalog_url.clone();
+    settings.custom_providers = portable.custom_providers.clone();
+    settings.llm_profiles = portable.llm_profiles.clone();
+    settings.provider_base_urls = portable.provider_base_urls.clone();
+    settings.restart_gateway_on_config_change = portable.restart_gateway_on_config_change;
+    write_settings(app, &settings)
+}
+
 pub fn node_root_dir(app_data_dir: &Path) -> PathBuf {
     app_data_dir.join("node")
 }
@@ -92,19 +197,136 @@ pub fn openclaw_binary_path(app_data_dir: &Path) -> PathBuf {
     }
 }
 
+pub fn global_node_modules_dir(app_data_dir: &Path) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        openclaw_global_dir(app_data_dir).join("node_modules")
+    } else {
+        openclaw_global_dir(app_data_dir)
+            .join("lib")
+            .join("node_modules")
+    }
+}
+
+pub fn openclaw_package_dir(app_data_dir: &Path) -> PathBuf {
+    global_node_modules_dir(app_data_dir).join("openclaw")
+}
+
+pub fn plugins_manifest_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("plugins.json")
+}
+
+pub fn install_lock_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("install.lock")
+}
+
+/// Legacy plaintext key file, only read to migrate it into the secret store.
 pub fn keys_file_path(app_data_dir: &Path) -> PathBuf {
     app_data_dir.join("keys.json")
 }
 
-pub fn openclaw_config_path() -> Result<PathBuf, String> {
-    let home = if cfg!(target_os = "windows") {
+pub fn encrypted_keys_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("keys.enc")
+}
+
+/// User-maintained provider catalog layered over the bundled one.
+pub fn user_catalog_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("providers.json")
+}
+
+pub fn remote_catalog_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("providers.remote.json")
+}
+
+pub fn config_history_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("config-history")
+}
+
+pub fn model_cache_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("model-cache.json")
+}
+
+pub fn changelog_cache_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("changelogs")
+}
+
+pub fn home_dir() -> Result<PathBuf, String> {
+    if cfg!(target_os = "windows") {
         std::env::var("USERPROFILE").ok()
     } else {
         std::env::var("HOME").ok()
     }
-    .ok_or_else(|| "failed to resolve home directory from environment".to_string())?;
+    .map(PathBuf::from)
+    .ok_or_else(|| "failed to resolve home directory from environment".to_string())
+}
+
+pub fn openclaw_config_path() -> Result<PathBuf, String> {
+    Ok(home_dir()?.join(".openclaw").join("openclaw.json"))
+}
+
+/// Writes `contents` to a temp file next to `path` and renames it into place,
+/// so readers never observe a partially written file.
+pub fn write_file_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    write_atomic(path, contents, false)
+}
 
-    Ok(PathBuf::from(home).join(".openclaw").join("openclaw.json"))
+/// Like [`write_file_atomic`], but the file is readable and writable only by
+/// its owner on Unix from the moment it is created.
+pub fn write_private_file_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    write_atomic(path, contents, true)
+}
+
+/// Writes to a uniquely named temp file next to the target and renames it
+/// into place. A symlinked target is followed so the link survives, and an
+/// existing file's permissions carry over unless the file is private.
+fn write_atomic(path: &Path, contents: &[u8], private: bool) -> Result<(), String> {
+    static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let path = &std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let parent = path
+        .parent()
+        .ok_or_else(|| format!("{} has no parent directory", path.display()))?;
+    std::fs::create_dir_all(parent)
+        .map_err(|e| format!("failed to create dir {}: {e}", parent.display()))?;
+
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_path = parent.join(format!(
+        ".{file_name}.tmp-{}-{}",
+        std::process::id(),
+        TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let existing_permissions = std::fs::metadata(path)
+        .ok()
+        .filter(|_| !private)
+        .map(|meta| meta.permissions());
+
+    // A leftover temp file would keep its old permissions.
+    let _ = std::fs::remove_file(&tmp_path);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let result = options
+        .open(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            if let Some(permissions) = existing_permissions {
+                file.set_permissions(permissions)?;
+            }
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&tmp_path, path));
+    if let Err(err) = result {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(format!("failed to write {}: {err}", path.display()));
+    }
+    Ok(())
 }
 
 pub fn normalize_version(value: &str) -> String {
@@ -132,6 +354,320 @@ pub fn prepend_path_env(base_env: &HashMap<String, String>, node_path_prefix: &P
     })
 }
 
+/// Runs a command to completion, killing it if it has not exited within
+/// `timeout`. Stdout and stderr are captured the same way `Command::output`
+/// would capture them.
+pub fn output_with_timeout(command: &mut Command, timeout: Duration) -> Result<Output, String> {
+    run_with_timeout(command, None, timeout)
+}
+
+/// Like [`output_with_timeout`], but writes `input` to the process's stdin
+/// and then closes it. The timeout also covers a process that never reads
+/// its input, e.g. one waiting on an unlock prompt.
+pub fn output_with_input_and_timeout(
+    command: &mut Command,
+    input: &[u8],
+    timeout: Duration,
+) -> Result<Output, String> {
+    run_with_timeout(command, Some(input.to_vec()), timeout)
+}
+
+fn run_with_timeout(
+    command: &mut Command,
+    input: Option<Vec<u8>>,
+    timeout: Duration,
+) -> Result<Output, String> {
+    let mut child = command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to spawn process: {e}"))?;
+
+    if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+        // Written from a thread so a child that never reads cannot block
+        // us past the timeout; dropping the pipe closes its stdin.
+        thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let out_handle = thread::spawn(move || read_all(stdout));
+    let err_handle = thread::spawn(move || read_all(stderr));
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => {
+                if start.elapsed() >= timeout {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!(
+                        "process did not exit within {} seconds",
+                        timeout.as_secs()
+                    ));
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+            Err(e) => return Err(format!("failed to wait for process: {e}")),
+        }
+    };
+
+    Ok(Output {
+        status,
+        stdout: out_handle.join().unwrap_or_default(),
+        stderr: err_handle.join().unwrap_or_default(),
+    })
+}
+
+/// Puts `command` in a process group of its own on Unix, so
+/// [`stop_process_tree`] can signal everything it starts.
+pub fn own_process_group(command: &mut Command) -> &mut Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    command
+}
+
+/// Asks `child` and everything it started to exit, then kills whatever is
+/// left after `grace`. On Unix the child's process group (see
+/// [`own_process_group`]) gets SIGTERM and then SIGKILL; on Windows
+/// `taskkill /T` walks the tree, so node behind a `.cmd` shim is not
+/// orphaned.
+pub fn stop_process_tree(child: &mut Child, grace: Duration) {
+    let pid = child.id().to_string();
+    #[cfg(unix)]
+    let signal = |name: &str| {
+        let _ = Command::new("kill")
+            .args([name, "--", &format!("-{pid}")])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    };
+    #[cfg(windows)]
+    let signal = |name: &str| {
+        let mut command = Command::new("taskkill");
+        command.args(["/PID", &pid, "/T"]);
+        if name == "-KILL" {
+            command.arg("/F");
+        }
+        let _ = command.stdout(Stdio::null()).stderr(Stdio::null()).status();
+    };
+    #[cfg(not(any(unix, windows)))]
+    let signal = |_: &str| {};
+
+    signal("-TERM");
+    let start = Instant::now();
+    while start.elapsed() < grace && matches!(child.try_wait(), Ok(None)) {
+        thread::sleep(Duration::from_millis(100));
+    }
+    // Descendants may outlive the child itself.
+    signal("-KILL");
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Creates a fresh directory under `<app_data>/tmp` that no other call
+/// shares, for work files that must not collide between concurrent
+/// commands.
+pub fn create_scratch_dir(app_data_dir: &Path, prefix: &str) -> Result<PathBuf, String> {
+    static SCRATCH_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    let dir = app_data_dir.join("tmp").join(format!(
+        "{prefix}-{}-{nanos}-{}",
+        std::process::id(),
+        SCRATCH_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+    Ok(dir)
+}
+
+fn read_all<R: Read>(reader: Option<R>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    if let Some(mut reader) = reader {
+        let _ = reader.read_to_end(&mut buf);
+    }
+    buf
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Performs an HTTP request through curl. `Err` means the request never
+/// produced an HTTP response (DNS, connect, TLS or timeout failures); any
+/// HTTP status is returned as `Ok`.
+pub fn http_request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+    timeout: Duration,
+) -> Result<HttpResponse, String> {
+    let config = curl_config(method, url, headers, body);
+    let connect_timeout = timeout.as_secs().clamp(1, 10);
+    let mut child = Command::new("curl")
+        .args(["-sS", "--config", "-", "-w", "\n%{http_code}"])
+        .arg("--connect-timeout")
+        .arg(connect_timeout.to_string())
+        .arg("--max-time")
+        .arg(timeout.as_secs().max(1).to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run curl: {e}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(config.as_bytes())
+            .map_err(|e| format!("failed to pass request to curl: {e}"))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("curl process wait failed: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if stderr.is_empty() {
+            format!("request to {url} failed with curl status {}", output.status)
+        } else {
+            stderr
+        });
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or_else(|| format!("curl returned no status for {url}"))?;
+    let status = status
+        .trim()
+        .parse::<u16>()
+        .map_err(|e| format!("curl returned an invalid status for {url}: {e}"))?;
+    if status == 0 {
+        return Err(format!("no HTTP response from {url}"));
+    }
+
+    Ok(HttpResponse {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// Like [`http_request`], but hands each line of the response body to
+/// `on_line` as it arrives, for streamed (NDJSON) responses. Returns the
+/// HTTP status.
+pub fn http_stream_lines(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+    timeout: Duration,
+    mut on_line: impl FnMut(&str),
+) -> Result<u16, String> {
+    let config = curl_config(method, url, headers, body);
+    let connect_timeout = timeout.as_secs().clamp(1, 10);
+    let mut child = Command::new("curl")
+        .args(["-sS", "-N", "--config", "-", "-w", "\n%{http_code}"])
+        .arg("--connect-timeout")
+        .arg(connect_timeout.to_string())
+        .arg("--max-time")
+        .arg(timeout.as_secs().max(1).to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run curl: {e}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(config.as_bytes())
+            .map_err(|e| format!("failed to pass request to curl: {e}"))?;
+    }
+
+    // The status written by `-w` is the last line, so every line is passed
+    // on one line late.
+    let mut pending: Option<String> = None;
+    if let Some(stdout) = child.stdout.take() {
+        use std::io::BufRead;
+        for line in std::io::BufReader::new(stdout)
+            .lines()
+            .map_while(Result::ok)
+        {
+            if let Some(previous) = pending.replace(line) {
+                on_line(&previous);
+            }
+        }
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("curl process wait failed: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if stderr.is_empty() {
+            format!("request to {url} failed with curl status {}", output.status)
+        } else {
+            stderr
+        });
+    }
+
+    let status = pending
+        .ok_or_else(|| format!("curl returned no status for {url}"))?
+        .trim()
+        .parse::<u16>()
+        .map_err(|e| format!("curl returned an invalid status for {url}: {e}"))?;
+    if status == 0 {
+        return Err(format!("no HTTP response from {url}"));
+    }
+    Ok(status)
+}
+
+/// Headers and body are handed to curl on stdin as a config file so secrets
+/// never appear in the process list.
+fn curl_config(method: &str, url: &str, headers: &[(&str, &str)], body: Option<&str>) -> String {
+    let mut config = format!(
+        "url = \"{}\"\nrequest = \"{}\"\n",
+        curl_config_escape(url),
+        curl_config_escape(method)
+    );
+    for (name, value) in headers {
+        config.push_str(&format!(
+            "header = \"{}: {}\"\n",
+            curl_config_escape(name),
+            curl_config_escape(value)
+        ));
+    }
+    if let Some(body) = body {
+        config.push_str(&format!("data-binary = \"{}\"\n", curl_config_escape(body)));
+    }
+    config
+}
+
+fn curl_config_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+}
+
 pub fn iso_utc_now() -> String {
     #[cfg(target_os = "windows")]
     {
@@ -174,12 +710,284 @@ pub fn iso_utc_now() -> String {
     format!("{unix_secs}")
 }
 
+const UNREADABLE_LOCK_GRACE: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InstallLockOwner {
+    pid: u32,
+    operation: String,
+    acquired_at: String,
+    /// Start time of `pid` as the OS reports it, so a lock is not kept
+    /// alive by an unrelated process that was later given the same pid.
+    #[serde(default)]
+    process_started: Option<String>,
+}
+
+/// File-based lock in `app_data_dir` that serializes node installs, OpenClaw
+/// installs and install path changes across every running app instance.
+/// The lock file is removed when the guard is dropped; a lock left behind by
+/// a process that no longer exists is reclaimed on the next acquire by
+/// moving it aside and checking it is still the one found stale.
+#[derive(Debug)]
+pub struct InstallLock {
+    path: PathBuf,
+}
+
+impl InstallLock {
+    pub fn acquire(app_data_dir: &Path, operation: &str) -> Result<Self, String> {
+        Self::acquire_file(install_lock_path(app_data_dir), operation)
+    }
+
+    /// Takes the same kind of lock at `path`, for other short critical
+    /// sections shared between app instances.
+    pub fn acquire_file(path: PathBuf, operation: &str) -> Result<Self, String> {
+        let owner = InstallLockOwner {
+            pid: std::process::id(),
+            operation: operation.to_string(),
+            acquired_at: iso_utc_now(),
+            process_started: process_started(std::process::id()),
+        };
+        let serialized = serde_json::to_string(&owner)
+            .map_err(|e| format!("failed to serialize install lock: {e}"))?;
+
+        for _ in 0..3 {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    if let Err(err) = file.write_all(serialized.as_bytes()) {
+                        let _ = std::fs::remove_file(&path);
+                        return Err(format!(
+                            "failed to write install lock {}: {err}",
+                            path.display()
+                        ));
+                    }
+                    return Ok(Self { path });
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let raw = std::fs::read_to_string(&path).unwrap_or_default();
+                    if let Some(message) = live_lock_holder(&path, &raw) {
+                        return Err(message);
+                    }
+                    reclaim_stale_lock(&path, &raw)?;
+                }
+                Err(err) => {
+                    return Err(format!(
+                        "failed to create install lock {}: {err}",
+                        path.display()
+                    ))
+                }
+            }
+        }
+
+        Err(format!("failed to acquire install lock {}", path.display()))
+    }
+}
+
+impl Drop for InstallLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Returns an error message if the lock file, read as `raw`, belongs to a
+/// live process, or `None` if the lock is stale and may be removed.
+fn live_lock_holder(path: &Path, raw: &str) -> Option<String> {
+    match serde_json::from_str::<InstallLockOwner>(raw) {
+        Ok(owner) if owner_is_running(&owner) => Some(format!(
+            "another install operation is already in progress ({} in process {} since {})",
+            owner.operation, owner.pid, owner.acquired_at
+        )),
+        Ok(_) => None,
+        Err(_) => {
+            // A lock that cannot be parsed may still be mid-write by its owner.
+            let age = std::fs::metadata(path)
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .unwrap_or(UNREADABLE_LOCK_GRACE);
+            if age < UNREADABLE_LOCK_GRACE {
+                Some("another install operation is already in progress".to_string())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Moves the stale lock at `path` aside under a unique name, then removes
+/// it only if it still reads as `seen`. If another instance replaced the
+/// stale lock with its own in the meantime, that lock is put back.
+fn reclaim_stale_lock(path: &Path, seen: &str) -> Result<(), String> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+    let aside = path.with_extension(format!("lock.stale-{}-{nanos}", std::process::id()));
+    match std::fs::rename(path, &aside) {
+        Ok(()) => {}
+        // Someone else already reclaimed or released it.
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => {
+            return Err(format!(
+                "failed to move stale install lock {}: {err}",
+                path.display()
+            ))
+        }
+    }
+
+    let moved = std::fs::read_to_string(&aside).unwrap_or_default();
+    if moved != seen {
+        // A hard link never replaces an existing file, so a lock taken
+        // after ours was moved is not clobbered either.
+        let _ = std::fs::hard_link(&aside, path);
+        let _ = std::fs::remove_file(&aside);
+        return Err("another install operation is already in progress".to_string());
+    }
+    std::fs::remove_file(&aside).map_err(|e| {
+        format!(
+            "failed to remove stale install lock {}: {e}",
+            aside.display()
+        )
+    })
+}
+
+fn owner_is_running(owner: &InstallLockOwner) -> bool {
+    if !process_is_alive(owner.pid) {
+        return false;
+    }
+    // Unknown start times are treated as a match to stay on the safe side.
+    match (&owner.process_started, process_started(owner.pid)) {
+        (Some(recorded), Some(current)) => recorded == &current,
+        _ => true,
+    }
+}
+
+/// An identifier of when `pid` started, stable for the life of the
+/// process: the start tick from `/proc` on Linux, `ps -o lstart` on other
+/// Unixes and the start file time on Windows.
+fn process_started(pid: u32) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        // Fields after the parenthesised command start at field 3 (state);
+        // starttime is field 22.
+        let (_, rest) = stat.rsplit_once(')')?;
+        rest.split_whitespace().nth(19).map(str::to_string)
+    }
+
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        let output = Command::new("ps")
+            .args(["-o", "lstart=", "-p", &pid.to_string()])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        let started = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !started.is_empty()).then_some(started)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let output = Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                &format!("(Get-Process -Id {pid}).StartTime.ToFileTimeUtc()"),
+            ])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        let started = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !started.is_empty()).then_some(started)
+    }
+
+    #[cfg(not(any(unix, target_os = "windows")))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+pub fn process_is_alive(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return true;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let output = Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {pid}"), "/NH", "/FO", "CSV"])
+            .output();
+        return match output {
+            Ok(output) if output.status.success() => {
+                String::from_utf8_lossy(&output.stdout).contains(&format!("\"{pid}\""))
+            }
+            _ => false,
+        };
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Command::new("kill")
+            .arg("-0")
+            .arg(pid.to_string())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+}
+
+/// A user-defined OpenAI-compatible endpoint (LM Studio, vLLM, LiteLLM, a
+/// company proxy, ...). `base_url` is the API root without the `/v1` suffix
+/// and `models` holds bare model ids as the endpoint names them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomProvider {
+    pub id: String,
+    pub display_name: String,
+    pub base_url: String,
+    #[serde(default)]
+    pub env_var: Option<String>,
+    pub models: Vec<String>,
+}
+
+/// A named model setup ("local", "opus-work") that can be switched to with
+/// `activate_llm_profile`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmProfile {
+    pub name: String,
+    pub provider: String,
+    pub model: String,
+    #[serde(default)]
+    pub fallbacks: Vec<String>,
+    /// Label of the stored provider key to use; `None` keeps the provider's
+    /// current key.
+    #[serde(default)]
+    pub key_label: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct Settings {
     install_path: Option<String>,
+    secret_backend: Option<String>,
+    catalog_url: Option<String>,
+    #[serde(default)]
+    custom_providers: Vec<CustomProvider>,
+    #[serde(default)]
+    llm_profiles: Vec<LlmProfile>,
+    /// Endpoints replacing a built-in provider's, by provider id.
+    #[serde(default)]
+    provider_base_urls: BTreeMap<String, String>,
+    #[serde(default)]
+    restart_gateway_on_config_change: bool,
 }
 
-fn settings_file_path(app: &AppHandle) -> Result<PathBuf, String> {
+pub fn settings_file_path(app: &AppHandle) -> Result<PathBuf, String> {
     let config_dir = app
         .path()
         .app_config_dir()
@@ -205,6 +1013,14 @@ fn read_settings(app: &AppHandle) -> Result<Settings, String> {
         .map_err(|e| format!("failed to parse settings {}: {e}", file_path.display()))
 }
 
+/// Parses settings text as the app would read it, returning the raw JSON so
+/// edits can be diffed field by field.
+pub fn parse_settings(raw: &str) -> Result<serde_json::Value, String> {
+    let value = serde_json::from_str::<serde_json::Value>(raw).map_err(|e| e.to_string())?;
+    serde_json::from_value::<Settings>(value.clone()).map_err(|e| e.to_string())?;
+    Ok(value)
+}
+
 fn write_settings(app: &AppHandle, settings: &Settings) -> Result<(), String> {
     let file_path = settings_file_path(app)?;
     let serialized = serde_json::to_string_pretty(settings)
@@ -243,6 +1059,59 @@ fn validate_writable_dir(path: &Path) -> Result<(), String> {
     })
 }
 
+/// Minimal HTTP/1.1 stand-in server for tests. Answers one connection per
+/// canned `(status, body)` response, in order, and forwards every raw request
+/// it received through the returned channel.
+#[cfg(test)]
+pub(crate) fn serve_http(
+    responses: Vec<(u16, String)>,
+) -> (String, std::sync::mpsc::Receiver<String>) {
+    use std::io::BufRead;
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind stand-in server");
+    let base_url = format!("http://{}", listener.local_addr().expect("local addr"));
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    thread::spawn(move || {
+        for (status, body) in responses {
+            let Ok((stream, _)) = listener.accept() else {
+                return;
+            };
+            let mut reader = std::io::BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0usize;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut payload = vec![0u8; content_length];
+            let _ = reader.read_exact(&mut payload);
+            request.push_str(&String::from_utf8_lossy(&payload));
+            let _ = tx.send(request);
+
+            let response = format!(
+                "HTTP/1.1 {status} Stand-in\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = reader.get_mut().write_all(response.as_bytes());
+        }
+    });
+
+    (base_url, rx)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,4 +1125,224 @@ mod tests {
     fn normalize_user_path_rejects_empty_input() {
         assert!(normalize_user_path(" ".to_string()).is_err());
     }
+
+    #[test]
+    fn install_lock_is_exclusive_and_released_on_drop() {
+        let dir = std::env::temp_dir().join(format!("openclawini-lock-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create lock dir");
+
+        let lock = InstallLock::acquire(&dir, "install_node").expect("first acquire");
+        let err = InstallLock::acquire(&dir, "install_openclaw").expect_err("second acquire");
+        assert!(err.contains("install_node"));
+
+        drop(lock);
+        assert!(!install_lock_path(&dir).exists());
+        let relock = InstallLock::acquire(&dir, "install_openclaw").expect("acquire after drop");
+        drop(relock);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn install_lock_reclaims_lock_of_dead_process() {
+        let dir =
+            std::env::temp_dir().join(format!("openclawini-stale-lock-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create lock dir");
+        let stale = InstallLockOwner {
+            pid: i32::MAX as u32,
+            operation: "install_openclaw".to_string(),
+            acquired_at: "2026-01-01T00:00:00Z".to_string(),
+            process_started: None,
+        };
+        std::fs::write(
+            install_lock_path(&dir),
+            serde_json::to_string(&stale).expect("serialize"),
+        )
+        .expect("write stale lock");
+
+        let lock = InstallLock::acquire(&dir, "install_node");
+        assert!(lock.is_ok());
+        drop(lock);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn install_lock_of_a_recycled_pid_is_stale() {
+        let dir =
+            std::env::temp_dir().join(format!("openclawini-reused-pid-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create lock dir");
+        // Our own pid is alive, but not the process that took this lock.
+        let recycled = InstallLockOwner {
+            pid: std::process::id(),
+            operation: "install_openclaw".to_string(),
+            acquired_at: "2026-01-01T00:00:00Z".to_string(),
+            process_started: Some("0".to_string()),
+        };
+        let raw = serde_json::to_string(&recycled).expect("serialize");
+        assert!(live_lock_holder(&install_lock_path(&dir), &raw).is_none());
+
+        std::fs::write(install_lock_path(&dir), &raw).expect("write lock");
+        let lock = InstallLock::acquire(&dir, "install_node").expect("reclaims lock");
+        assert!(live_lock_holder(
+            &install_lock_path(&dir),
+            &std::fs::read_to_string(install_lock_path(&dir)).expect("read lock")
+        )
+        .is_some());
+        drop(lock);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reclaim_puts_back_a_lock_taken_in_the_meantime() {
+        let dir =
+            std::env::temp_dir().join(format!("openclawini-lock-race-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create lock dir");
+        let path = install_lock_path(&dir);
+        std::fs::write(&path, "{\"pid\":1,\"fresh\":true}").expect("write lock");
+
+        let err = reclaim_stale_lock(&path, "{\"pid\":1}").expect_err("lock was replaced");
+        assert!(err.contains("in progress"));
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("lock restored"),
+            "{\"pid\":1,\"fresh\":true}"
+        );
+        assert_eq!(std::fs::read_dir(&dir).expect("list").count(), 1);
+
+        reclaim_stale_lock(&path, "{\"pid\":1,\"fresh\":true}").expect("reclaim");
+        assert!(!path.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn atomic_write_follows_symlinks_and_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("openclawini-atomic-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let target = dir.join("openclaw.json");
+        std::fs::write(&target, "{}").expect("write target");
+        std::fs::set_permissions(&target, std::fs::Permissions::from_mode(0o640))
+            .expect("set mode");
+        let link = dir.join("linked.json");
+        std::os::unix::fs::symlink(&target, &link).expect("symlink");
+
+        write_file_atomic(&link, b"{\"a\":1}").expect("write through link");
+
+        assert!(std::fs::symlink_metadata(&link)
+            .expect("link metadata")
+            .file_type()
+            .is_symlink());
+        assert_eq!(
+            std::fs::read_to_string(&target).expect("read target"),
+            "{\"a\":1}"
+        );
+        let mode = std::fs::metadata(&target)
+            .expect("metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o640);
+        assert_eq!(std::fs::read_dir(&dir).expect("list").count(), 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn http_request_returns_status_body_and_sends_headers() {
+        let (base_url, requests) = serve_http(vec![(401, "{\"error\":\"nope\"}".to_string())]);
+
+        let response = http_request(
+            "POST",
+            &format!("{base_url}/v1/check"),
+            &[("x-api-key", "sk-\"quoted\"")],
+            Some("{\"a\":1}"),
+            Duration::from_secs(5),
+        )
+        .expect("stand-in answers");
+
+        assert_eq!(response.status, 401);
+        assert_eq!(response.body, "{\"error\":\"nope\"}");
+        let request = requests.recv().expect("request captured");
+        assert!(request.starts_with("POST /v1/check HTTP/1.1"));
+        assert!(request.contains("x-api-key: sk-\"quoted\""));
+        assert!(request.ends_with("{\"a\":1}"));
+    }
+
+    #[test]
+    fn streamed_lines_arrive_without_the_status() {
+        let (base_url, _requests) = serve_http(vec![(
+            200,
+            "{\"status\":\"a\"}\n{\"status\":\"b\"}\n".to_string(),
+        )]);
+        let mut lines = Vec::new();
+
+        let status = http_stream_lines(
+            "POST",
+            &format!("{base_url}/api/pull"),
+            &[],
+            Some("{}"),
+            Duration::from_secs(5),
+            |line| lines.push(line.to_string()),
+        )
+        .expect("stand-in answers");
+
+        assert_eq!(status, 200);
+        assert_eq!(lines, vec!["{\"status\":\"a\"}", "{\"status\":\"b\"}", ""]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn output_with_timeout_kills_slow_processes() {
+        let mut fast = Command::new("sh");
+        fast.args(["-c", "echo ready"]);
+        let output = output_with_timeout(&mut fast, Duration::from_secs(5)).expect("fast exits");
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ready");
+
+        let mut slow = Command::new("sleep");
+        slow.arg("5");
+        assert!(output_with_timeout(&mut slow, Duration::from_millis(200)).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn input_is_fed_and_the_timeout_still_applies() {
+        let mut echo = Command::new("cat");
+        let output = output_with_input_and_timeout(&mut echo, b"secret", Duration::from_secs(5))
+            .expect("cat exits");
+        assert_eq!(output.stdout, b"secret");
+
+        let mut stuck = Command::new("sleep");
+        stuck.arg("5");
+        assert!(
+            output_with_input_and_timeout(&mut stuck, b"secret", Duration::from_millis(200))
+                .is_err()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stopping_a_process_tree_terminates_before_killing() {
+        let dir = std::env::temp_dir().join(format!("openclawini-tree-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let marker = dir.join("terminated");
+        let mut command = Command::new("sh");
+        command.args([
+            "-c",
+            &format!(
+                "trap 'echo term > {}; exit 0' TERM; while :; do sleep 0.1; done",
+                marker.display()
+            ),
+        ]);
+        let mut child = own_process_group(&mut command).spawn().expect("spawn");
+        thread::sleep(Duration::from_millis(300));
+
+        stop_process_tree(&mut child, Duration::from_secs(5));
+
+        assert!(child.try_wait().expect("wait").is_some());
+        assert_eq!(
+            std::fs::read_to_string(&marker).expect("trap ran").trim(),
+            "term"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
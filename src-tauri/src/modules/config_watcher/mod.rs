@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::modules::openclaw_config::{self, ConfigChange};
+use crate::modules::{common, service_manager};
+
+pub const FILE_OPENCLAW: &str = "openclaw";
+pub const FILE_SETTINGS: &str = "settings";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Payload of `config:changed`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChangedPayload {
+    /// `openclaw` or `settings`.
+    pub file: String,
+    pub path: String,
+    /// `false` when the new text does not parse; `changes` is then empty and
+    /// the last valid version stays the base for the next diff.
+    pub valid: bool,
+    pub error: Option<String>,
+    pub changes: Vec<ConfigChange>,
+    /// The gateway was restarted to pick up the change.
+    pub gateway_restarted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchedFileStatus {
+    pub file: String,
+    pub path: String,
+    pub exists: bool,
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigWatchStatus {
+    pub files: Vec<WatchedFileStatus>,
+    /// Restart a running gateway after valid edits of `openclaw.json`.
+    pub restart_gateway_on_change: bool,
+}
+
+/// One watched file. The file is polled rather than watched natively so
+/// editors that save by rename, and files that do not exist yet, behave the
+/// same on every platform.
+struct Watched {
+    file: &'static str,
+    path: PathBuf,
+    parse: fn(&str) -> Result<Value, String>,
+    /// Text last reported; `None` while the file is missing.
+    seen: Option<String>,
+    /// Text seen once and not yet reported. A change is only reported when
+    /// two polls agree, so half-written saves are not flagged as invalid.
+    pending: Option<Option<String>>,
+    /// Last version that parsed; a missing file counts as `{}`.
+    last_valid: Value,
+    error: Option<String>,
+}
+
+impl Watched {
+    fn new(file: &'static str, path: PathBuf, parse: fn(&str) -> Result<Value, String>) -> Self {
+        let mut watched = Self {
+            file,
+            path,
+            parse,
+            seen: None,
+            pending: None,
+            last_valid: Value::Object(Default::default()),
+            error: None,
+        };
+        watched.seen = watched.read();
+        if let Some(text) = watched.seen.as_deref() {
+            match parse(text) {
+                Ok(value) => watched.last_valid = value,
+                Err(err) => watched.error = Some(err),
+            }
+        }
+        watched
+    }
+
+    fn read(&self) -> Option<String> {
+        std::fs::read_to_string(&self.path).ok()
+    }
+
+    fn poll(&mut self) -> Option<ConfigChangedPayload> {
+        let current = self.read();
+        if current == self.seen {
+            self.pending = None;
+            return None;
+        }
+        if self.pending.as_ref() != Some(&current) {
+            self.pending = Some(current);
+            return None;
+        }
+        self.pending = None;
+        self.seen = current;
+
+        let parsed = match self.seen.as_deref() {
+            Some(text) => (self.parse)(text),
+            None => Ok(Value::Object(Default::default())),
+        };
+        let changes = match parsed {
+            Ok(value) => {
+                self.error = None;
+                let changes = openclaw_config::diff(&self.last_valid, &value);
+                self.last_valid = value;
+                changes
+            }
+            Err(err) => {
+                self.error = Some(err);
+                Vec::new()
+            }
+        };
+        Some(ConfigChangedPayload {
+            file: self.file.to_string(),
+            path: self.path.display().to_string(),
+            valid: self.error.is_none(),
+            error: self.error.clone(),
+            changes,
+            gateway_restarted: false,
+        })
+    }
+
+    fn status(&self) -> WatchedFileStatus {
+        WatchedFileStatus {
+            file: self.file.to_string(),
+            path: self.path.display().to_string(),
+            exists: self.seen.is_some(),
+            valid: self.error.is_none(),
+            error: self.error.clone(),
+        }
+    }
+}
+
+fn watch_status() -> &'static Mutex<Vec<WatchedFileStatus>> {
+    static STATUS: OnceLock<Mutex<Vec<WatchedFileStatus>>> = OnceLock::new();
+    STATUS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn parse_openclaw_config(text: &str) -> Result<Value, String> {
+    let value = openclaw_config::json5::parse(text)?;
+    if !value.is_object() {
+        return Err("top level must be an object".to_string());
+    }
+    Ok(value)
+}
+
+/// Starts watching `~/.openclaw/openclaw.json` and the app `settings.json`,
+/// emitting `config:changed` whenever either changes on disk, including
+/// writes made by the app itself. Only outside edits restart the gateway.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let mut watched = vec![
+        Watched::new(
+            FILE_OPENCLAW,
+            common::openclaw_config_path()?,
+            parse_openclaw_config,
+        ),
+        Watched::new(
+            FILE_SETTINGS,
+            common::settings_file_path(app)?,
+            common::parse_settings,
+        ),
+    ];
+    set_status(&watched);
+
+    let app = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+        for file in &mut watched {
+            if let Some(payload) = file.poll() {
+                let own_write = file
+                    .seen
+                    .as_deref()
+                    .is_some_and(openclaw_config::is_own_write);
+                let payload = maybe_restart_gateway(&app, payload, own_write);
+                if let Err(err) = app.emit("config:changed", payload) {
+                    log::warn!("failed to emit config change: {err}");
+                }
+            }
+        }
+        set_status(&watched);
+    });
+    Ok(())
+}
+
+fn set_status(watched: &[Watched]) {
+    if let Ok(mut status) = watch_status().lock() {
+        *status = watched.iter().map(Watched::status).collect();
+    }
+}
+
+/// Whether `payload` is a valid outside edit of `openclaw.json`. Writes the
+/// app made itself leave restarting to the command that made them.
+fn restart_wanted(payload: &ConfigChangedPayload, own_write: bool) -> bool {
+    payload.file == FILE_OPENCLAW && payload.valid && !payload.changes.is_empty() && !own_write
+}
+
+fn maybe_restart_gateway(
+    app: &AppHandle,
+    mut payload: ConfigChangedPayload,
+    own_write: bool,
+) -> ConfigChangedPayload {
+    if !restart_wanted(&payload, own_write) {
+        return payload;
+    }
+    let enabled = common::get_restart_gateway_on_config_change(app).unwrap_or_else(|err| {
+        log::warn!("failed to read config watch settings: {err}");
+        false
+    });
+    if enabled && service_manager::is_gateway_running() {
+        match service_manager::restart_gateway(app.clone()) {
+            Ok(()) => payload.gateway_restarted = true,
+            Err(err) => log::warn!("failed to restart gateway after config change: {err}"),
+        }
+    }
+    payload
+}
+
+/// Reports whether the watched files currently parse, so the UI can show an
+/// invalid edit made before it subscribed to `config:changed`.
+#[tauri::command]
+pub fn get_config_watch_status(app: AppHandle) -> Result<ConfigWatchStatus, String> {
+    let files = watch_status()
+        .lock()
+        .map_err(|_| "config watch state lock poisoned".to_string())?
+        .clone();
+    Ok(ConfigWatchStatus {
+        files,
+        restart_gateway_on_change: common::get_restart_gateway_on_config_change(&app)?,
+    })
+}
+
+#[tauri::command]
+pub fn set_restart_gateway_on_config_change(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<ConfigWatchStatus, String> {
+    common::set_restart_gateway_on_config_change(&app, enabled)?;
+    get_config_watch_status(app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn edits_are_reported_once_settled() {
+        let path =
+            std::env::temp_dir().join(format!("openclawini-watch-{}.json", std::process::id()));
+        std::fs::write(&path, "{ agent: { model: 'ollama/llama3.2' } }").expect("write config");
+        let mut watched = Watched::new(FILE_OPENCLAW, path.clone(), parse_openclaw_config);
+        assert_eq!(watched.poll(), None);
+
+        std::fs::write(&path, "{ agent: { model: 'openai/gpt-4o' } }").expect("edit config");
+        assert_eq!(
+            watched.poll(),
+            None,
+            "first sighting waits for the file to settle"
+        );
+        let change = watched.poll().expect("settled edit");
+        assert!(change.valid);
+        assert!(restart_wanted(&change, false));
+        assert!(!restart_wanted(&change, true), "the app's own write");
+        assert_eq!(change.changes[0].path, "/agent/model");
+        assert_eq!(change.changes[0].after, Some(json!("openai/gpt-4o")));
+
+        std::fs::write(&path, "{ agent: { model: ").expect("break config");
+        watched.poll();
+        let broken = watched.poll().expect("invalid edit");
+        assert!(!broken.valid);
+        assert!(broken.error.is_some());
+        assert!(!watched.status().valid);
+
+        std::fs::write(
+            &path,
+            "{ agent: { model: 'openai/gpt-4o' }, gateway: { port: 1 } }",
+        )
+        .expect("fix config");
+        watched.poll();
+        let fixed = watched.poll().expect("fixed edit");
+        assert!(fixed.valid);
+        assert_eq!(
+            fixed
+                .changes
+                .iter()
+                .map(|c| c.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["/gateway"],
+            "diffed against the last valid version"
+        );
+
+        std::fs::remove_file(&path).expect("remove config");
+        watched.poll();
+        let removed = watched.poll().expect("removal");
+        assert_eq!(removed.changes.len(), 2);
+        assert!(!watched.status().exists);
+    }
+}
@@ -0,0 +1,559 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Manager};
+
+use super::credentials::{self, CredentialField};
+use super::endpoints;
+use super::parameters::{self, ModelParameter};
+use super::{ModelInfo, ProviderInfo};
+use crate::modules::common;
+
+pub const SCHEMA_VERSION: u32 = 1;
+const BUNDLED_RESOURCE: &str = "resources/providers.json";
+const BUNDLED_CATALOG: &str = include_str!("../../../resources/providers.json");
+const REFRESH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A provider catalog file. The bundled catalog, the cached remote catalog
+/// and the user override in `app_data_dir` all share this shape.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogFile {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub catalog_version: Option<String>,
+    pub providers: Vec<CatalogProvider>,
+}
+
+/// Provider entry. Fields other than `id` may be omitted when the entry only
+/// adds models to, or adjusts, a provider defined by an earlier source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogProvider {
+    pub id: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub requires_api_key: Option<bool>,
+    /// Shorthand for `credentials` holding a single API key field.
+    #[serde(default)]
+    pub env_var: Option<String>,
+    #[serde(default)]
+    pub credentials: Option<Vec<CredentialField>>,
+    /// API root without the version suffix, e.g. `https://api.groq.com/openai`.
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Generation parameters the provider's models accept.
+    #[serde(default)]
+    pub parameters: Option<Vec<ModelParameter>>,
+    #[serde(default)]
+    pub models: Vec<CatalogModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogModel {
+    pub id: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub is_default: bool,
+    /// Replaces the provider's parameters for this model.
+    #[serde(default)]
+    pub parameters: Option<Vec<ModelParameter>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogSource {
+    pub name: String,
+    pub location: String,
+    pub catalog_version: Option<String>,
+    pub loaded: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderCatalogStatus {
+    pub remote_url: Option<String>,
+    pub sources: Vec<CatalogSource>,
+}
+
+pub struct Catalog {
+    pub providers: Vec<ProviderInfo>,
+    pub sources: Vec<CatalogSource>,
+}
+
+/// Builds the provider list from the bundled catalog, then the cached
+/// remote catalog, then the user override. A source that fails to parse or
+/// validate is skipped as a whole and reported in `sources`.
+pub fn load(app: &AppHandle) -> Catalog {
+    let mut catalog = Catalog {
+        providers: Vec::new(),
+        sources: Vec::new(),
+    };
+
+    let (location, bundled) = match app
+        .path()
+        .resolve(BUNDLED_RESOURCE, BaseDirectory::Resource)
+    {
+        Ok(path) if path.exists() => (
+            path.display().to_string(),
+            std::fs::read_to_string(&path).unwrap_or_else(|_| BUNDLED_CATALOG.to_string()),
+        ),
+        _ => ("built-in".to_string(), BUNDLED_CATALOG.to_string()),
+    };
+    apply_source(&mut catalog, "bundled", location, Ok(bundled));
+
+    match common::app_data_dir(app) {
+        Ok(app_data_dir) => {
+            for (name, path) in [
+                ("remote", common::remote_catalog_path(&app_data_dir)),
+                ("user", common::user_catalog_path(&app_data_dir)),
+            ] {
+                if path.exists() {
+                    let text = std::fs::read_to_string(&path)
+                        .map_err(|e| format!("failed to read {}: {e}", path.display()));
+                    apply_source(&mut catalog, name, path.display().to_string(), text);
+                }
+            }
+        }
+        Err(err) => catalog.sources.push(CatalogSource {
+            name: "user".to_string(),
+            location: String::new(),
+            catalog_version: None,
+            loaded: false,
+            error: Some(err),
+        }),
+    }
+
+    catalog
+}
+
+fn apply_source(catalog: &mut Catalog, name: &str, location: String, text: Result<String, String>) {
+    let parsed = text.and_then(|text| parse(&text));
+    let catalog_version = parsed
+        .as_ref()
+        .ok()
+        .and_then(|file| file.catalog_version.clone());
+    let result = parsed.and_then(|file| merge(&catalog.providers, &file));
+
+    let error = match result {
+        Ok(providers) => {
+            catalog.providers = providers;
+            None
+        }
+        Err(err) => {
+            log::warn!("skipping {name} provider catalog {location}: {err}");
+            Some(err)
+        }
+    };
+    catalog.sources.push(CatalogSource {
+        name: name.to_string(),
+        location,
+        catalog_version,
+        loaded: error.is_none(),
+        error,
+    });
+}
+
+/// Downloads a catalog from `url`, checks it merges cleanly over the bundled
+/// catalog and caches it in `app_data_dir` for [`load`] to pick up.
+pub fn refresh(app_data_dir: &Path, url: &str) -> Result<(), String> {
+    let response = common::http_request("GET", url, &[], None, REFRESH_TIMEOUT)?;
+    if !(200..300).contains(&response.status) {
+        return Err(format!(
+            "catalog download from {url} failed with HTTP {}",
+            response.status
+        ));
+    }
+
+    let file = parse(&response.body).map_err(|e| format!("catalog from {url} is invalid: {e}"))?;
+    let bundled = parse(BUNDLED_CATALOG)
+        .and_then(|bundled| merge(&[], &bundled))
+        .map_err(|e| format!("built-in catalog is invalid: {e}"))?;
+    merge(&bundled, &file).map_err(|e| format!("catalog from {url} is invalid: {e}"))?;
+
+    common::write_file_atomic(
+        &common::remote_catalog_path(app_data_dir),
+        response.body.as_bytes(),
+    )
+}
+
+pub fn parse(text: &str) -> Result<CatalogFile, String> {
+    let file: CatalogFile = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    if file.schema_version != SCHEMA_VERSION {
+        return Err(format!(
+            "unsupported schema_version {} (expected {SCHEMA_VERSION})",
+            file.schema_version
+        ));
+    }
+    Ok(file)
+}
+
+/// Layers `file` over `base`. Provider fields present in `file` replace the
+/// earlier values, models are merged by id with the later entry winning, and
+/// a model marked default in `file` takes the default from earlier ones.
+pub fn merge(base: &[ProviderInfo], file: &CatalogFile) -> Result<Vec<ProviderInfo>, String> {
+    let mut providers = base.to_vec();
+    let mut seen = HashSet::new();
+
+    for (index, entry) in file.providers.iter().enumerate() {
+        let at = format!("providers[{index}]");
+        if !is_slug(&entry.id) {
+            return Err(format!(
+                "{at}.id: {:?} must be lowercase letters, digits, '-' or '_'",
+                entry.id
+            ));
+        }
+        if !seen.insert(entry.id.as_str()) {
+            return Err(format!("{at}.id: duplicate provider {}", entry.id));
+        }
+        if let Some(env_var) = entry.env_var.as_deref() {
+            if !credentials::is_env_var_name(env_var) {
+                return Err(format!(
+                    "{at}.env_var: {env_var:?} is not a valid environment variable name"
+                ));
+            }
+        }
+        if let Some(fields) = entry.credentials.as_deref() {
+            credentials::validate_fields(fields).map_err(|e| format!("{at}.{e}"))?;
+            if let Some(env_var) = entry.env_var.as_deref() {
+                if !fields.iter().any(|field| field.env_var == env_var) {
+                    return Err(format!(
+                        "{at}.env_var: {env_var} is not one of the provider's credentials"
+                    ));
+                }
+            }
+        }
+
+        if let Some(specs) = entry.parameters.as_deref() {
+            parameters::validate_specs(specs).map_err(|e| format!("{at}.{e}"))?;
+        }
+        let base_url = entry
+            .base_url
+            .as_deref()
+            .map(endpoints::normalize)
+            .transpose()
+            .map_err(|e| format!("{at}.base_url: {e}"))?;
+
+        let position = providers.iter().position(|p| p.id == entry.id);
+        let provider = match position {
+            Some(position) => &mut providers[position],
+            None => {
+                let display_name = entry
+                    .display_name
+                    .clone()
+                    .ok_or_else(|| format!("{at}.display_name: required for a new provider"))?;
+                let requires_api_key = entry
+                    .requires_api_key
+                    .ok_or_else(|| format!("{at}.requires_api_key: required for a new provider"))?;
+                providers.push(ProviderInfo {
+                    id: entry.id.clone(),
+                    display_name,
+                    requires_api_key,
+                    env_var: None,
+                    credentials: Vec::new(),
+                    models: Vec::new(),
+                    base_url: None,
+                    custom: false,
+                    base_url_override: None,
+                    parameters: Vec::new(),
+                });
+                providers.last_mut().expect("provider just pushed")
+            }
+        };
+
+        if let Some(display_name) = &entry.display_name {
+            provider.display_name = display_name.clone();
+        }
+        if let Some(requires_api_key) = entry.requires_api_key {
+            provider.requires_api_key = requires_api_key;
+        }
+        if let Some(specs) = &entry.parameters {
+            provider.parameters = specs.clone();
+        }
+        if base_url.is_some() {
+            provider.base_url = base_url;
+        }
+        match (&entry.credentials, &entry.env_var) {
+            (Some(fields), env_var) => {
+                provider.credentials = fields.clone();
+                provider.env_var = env_var.clone().or_else(|| primary_secret(fields));
+            }
+            (None, Some(env_var)) => {
+                provider.env_var = Some(env_var.clone());
+                if !provider
+                    .credentials
+                    .iter()
+                    .any(|field| &field.env_var == env_var)
+                {
+                    provider.credentials = vec![credentials::api_key_field(env_var)];
+                }
+            }
+            (None, None) => {}
+        }
+        if provider.requires_api_key && provider.credentials.is_empty() {
+            return Err(format!(
+                "{at}.env_var: env_var or credentials required when requires_api_key is true"
+            ));
+        }
+
+        merge_models(provider, &entry.models, &at)?;
+    }
+
+    Ok(providers)
+}
+
+fn merge_models(
+    provider: &mut ProviderInfo,
+    models: &[CatalogModel],
+    at: &str,
+) -> Result<(), String> {
+    let prefix = format!("{}/", provider.id);
+    let mut seen = HashSet::new();
+    let mut defaults = 0;
+
+    for (index, model) in models.iter().enumerate() {
+        let at = format!("{at}.models[{index}]");
+        if model.id.len() <= prefix.len() || !model.id.starts_with(&prefix) {
+            return Err(format!(
+                "{at}.id: {:?} must start with {prefix:?}",
+                model.id
+            ));
+        }
+        if model.display_name.trim().is_empty() {
+            return Err(format!("{at}.display_name: must not be empty"));
+        }
+        if !seen.insert(model.id.as_str()) {
+            return Err(format!("{at}.id: duplicate model {}", model.id));
+        }
+        if let Some(specs) = model.parameters.as_deref() {
+            parameters::validate_specs(specs).map_err(|e| format!("{at}.{e}"))?;
+        }
+        if model.is_default {
+            defaults += 1;
+        }
+    }
+    if defaults > 1 {
+        return Err(format!("{at}.models: more than one model is_default"));
+    }
+
+    for model in models {
+        if model.is_default {
+            for existing in &mut provider.models {
+                existing.is_default = false;
+            }
+        }
+        let merged = ModelInfo {
+            id: model.id.clone(),
+            display_name: model.display_name.clone(),
+            is_default: model.is_default,
+            discovered: false,
+            parameters: model.parameters.clone(),
+        };
+        match provider.models.iter_mut().find(|m| m.id == model.id) {
+            Some(existing) => {
+                // Keep an earlier default unless this file picks a new one.
+                let is_default = merged.is_default || (existing.is_default && defaults == 0);
+                *existing = ModelInfo {
+                    is_default,
+                    ..merged
+                };
+            }
+            None => provider.models.push(merged),
+        }
+    }
+
+    if !provider.models.iter().any(|m| m.is_default) {
+        if let Some(first) = provider.models.first_mut() {
+            first.is_default = true;
+        }
+    }
+    Ok(())
+}
+
+fn is_slug(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// The field `env_var` points at when a catalog only lists `credentials`.
+fn primary_secret(fields: &[CredentialField]) -> Option<String> {
+    fields
+        .iter()
+        .find(|field| {
+            field.kind == credentials::KIND_SECRET && field.required && field.group.is_none()
+        })
+        .map(|field| field.env_var.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundled() -> Vec<ProviderInfo> {
+        merge(&[], &parse(BUNDLED_CATALOG).expect("bundled parses")).expect("bundled is valid")
+    }
+
+    #[test]
+    fn bundled_catalog_is_valid() {
+        let providers = bundled();
+        let ids = providers.iter().map(|p| p.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                "anthropic",
+                "openai",
+                "ollama",
+                "google",
+                "openrouter",
+                "amazon-bedrock",
+                "azure-openai"
+            ]
+        );
+        for provider in &providers {
+            assert_eq!(
+                provider.models.iter().filter(|m| m.is_default).count(),
+                1,
+                "{} needs exactly one default model",
+                provider.id
+            );
+            assert_eq!(
+                provider.requires_api_key,
+                !provider.credentials.is_empty(),
+                "{} credentials",
+                provider.id
+            );
+        }
+
+        let azure = providers
+            .iter()
+            .find(|p| p.id == "azure-openai")
+            .expect("azure");
+        assert_eq!(azure.env_var.as_deref(), Some("AZURE_OPENAI_API_KEY"));
+        let bedrock = providers
+            .iter()
+            .find(|p| p.id == "amazon-bedrock")
+            .expect("bedrock");
+        assert_eq!(bedrock.env_var, None);
+        assert_eq!(bedrock.credentials.len(), 5);
+    }
+
+    #[test]
+    fn override_adds_models_and_providers() {
+        let file = parse(
+            r#"{
+                "schema_version": 1,
+                "providers": [
+                    { "id": "openai", "models": [
+                        { "id": "openai/gpt-4.1", "display_name": "GPT-4.1", "is_default": true }
+                    ] },
+                    { "id": "groq", "display_name": "Groq", "requires_api_key": true,
+                      "env_var": "GROQ_API_KEY", "base_url": "https://api.groq.com/openai/",
+                      "models": [
+                        { "id": "groq/llama-3.3-70b", "display_name": "Llama 3.3 70B" }
+                    ] }
+                ]
+            }"#,
+        )
+        .expect("override parses");
+
+        let providers = merge(&bundled(), &file).expect("override merges");
+        let openai = providers.iter().find(|p| p.id == "openai").expect("openai");
+        assert_eq!(openai.models.len(), 3);
+        let defaults = openai
+            .models
+            .iter()
+            .filter(|m| m.is_default)
+            .map(|m| m.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(defaults, vec!["openai/gpt-4.1"]);
+
+        let groq = providers.last().expect("groq appended");
+        assert_eq!(groq.id, "groq");
+        assert!(groq.models[0].is_default);
+        assert_eq!(
+            groq.base_url.as_deref(),
+            Some("https://api.groq.com/openai")
+        );
+    }
+
+    #[test]
+    fn invalid_catalogs_are_rejected_with_a_path() {
+        let cases = [
+            (
+                r#"{"schema_version": 2, "providers": []}"#,
+                "unsupported schema_version",
+            ),
+            (
+                r#"{"schema_version": 1, "providers": [{"id": "openai", "models": [{"id": "gpt-5", "display_name": "GPT-5"}]}]}"#,
+                "providers[0].models[0].id",
+            ),
+            (
+                r#"{"schema_version": 1, "providers": [{"id": "acme", "display_name": "Acme", "requires_api_key": true}]}"#,
+                "providers[0].env_var",
+            ),
+            (
+                r#"{"schema_version": 1, "providers": [{"id": "acme", "requires_api_key": false}]}"#,
+                "providers[0].display_name",
+            ),
+            (
+                r#"{"schema_version": 1, "providers": [{"id": "openai", "modles": []}]}"#,
+                "unknown field `modles`",
+            ),
+            (
+                r#"{"schema_version": 1, "providers": [{"id": "acme", "display_name": "Acme", "requires_api_key": true, "credentials": [{"env_var": "ACME_KEY", "label": "Key", "kind": "pin"}]}]}"#,
+                "providers[0].credentials[0].kind",
+            ),
+            (
+                r#"{"schema_version": 1, "providers": [{"id": "openai", "parameters": [{"name": "temperature", "min": 2, "max": 1}]}]}"#,
+                "providers[0].parameters[0].min",
+            ),
+            (
+                r#"{"schema_version": 1, "providers": [{"id": "openai", "models": [{"id": "openai/o3", "display_name": "o3", "parameters": [{"name": "top_p"}]}]}]}"#,
+                "providers[0].models[0].parameters[0].name",
+            ),
+            (
+                r#"{"schema_version": 1, "providers": [{"id": "openai", "base_url": "api.openai.com"}]}"#,
+                "providers[0].base_url",
+            ),
+        ];
+
+        for (text, expected) in cases {
+            let err = parse(text)
+                .and_then(|file| merge(&bundled(), &file))
+                .expect_err(text);
+            assert!(err.contains(expected), "{err} should mention {expected}");
+        }
+    }
+
+    #[test]
+    fn refresh_caches_only_valid_catalogs() {
+        let dir = std::env::temp_dir().join(format!("openclawini-catalog-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create app data dir");
+        let (base_url, _requests) = common::serve_http(vec![
+            (
+                200,
+                r#"{"schema_version": 1, "providers": [{"id": "Bad"}]}"#.to_string(),
+            ),
+            (
+                200,
+                r#"{"schema_version": 1, "catalog_version": "2026.11.0", "providers": []}"#
+                    .to_string(),
+            ),
+        ]);
+        let url = format!("{base_url}/providers.json");
+        let cached = common::remote_catalog_path(&dir);
+
+        assert!(refresh(&dir, &url).is_err());
+        assert!(!cached.exists());
+
+        refresh(&dir, &url).expect("valid catalog is cached");
+        let file = parse(&std::fs::read_to_string(&cached).expect("read cache")).expect("parse");
+        assert_eq!(file.catalog_version.as_deref(), Some("2026.11.0"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
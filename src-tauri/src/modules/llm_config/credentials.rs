@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const KIND_SECRET: &str = "secret";
+pub const KIND_TEXT: &str = "text";
+pub const KIND_URL: &str = "url";
+const KINDS: &[&str] = &[KIND_SECRET, KIND_TEXT, KIND_URL];
+
+/// One value a provider needs at gateway start, passed to OpenClaw as the
+/// environment variable `env_var`.
+///
+/// Ungrouped `required` fields must always be set. Fields sharing a `group`
+/// are alternatives: at least one group must have all of its fields set
+/// (Bedrock takes either an access key pair or a named profile).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CredentialField {
+    pub env_var: String,
+    pub label: String,
+    #[serde(default = "default_kind")]
+    pub kind: String,
+    #[serde(default = "default_required")]
+    pub required: bool,
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+fn default_kind() -> String {
+    KIND_SECRET.to_string()
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// The single API key shape most providers use.
+pub fn api_key_field(env_var: &str) -> CredentialField {
+    CredentialField {
+        env_var: env_var.to_string(),
+        label: "API key".to_string(),
+        kind: KIND_SECRET.to_string(),
+        required: true,
+        group: None,
+        default: None,
+    }
+}
+
+/// Checks a field list as declared in a catalog.
+pub fn validate_fields(fields: &[CredentialField]) -> Result<(), String> {
+    for (index, field) in fields.iter().enumerate() {
+        let at = format!("credentials[{index}]");
+        if !is_env_var_name(&field.env_var) {
+            return Err(format!(
+                "{at}.env_var: {:?} is not a valid environment variable name",
+                field.env_var
+            ));
+        }
+        if fields[..index]
+            .iter()
+            .any(|other| other.env_var == field.env_var)
+        {
+            return Err(format!("{at}.env_var: duplicate {}", field.env_var));
+        }
+        if field.label.trim().is_empty() {
+            return Err(format!("{at}.label: must not be empty"));
+        }
+        if !KINDS.contains(&field.kind.as_str()) {
+            return Err(format!(
+                "{at}.kind: {:?} is not one of {}",
+                field.kind,
+                KINDS.join(", ")
+            ));
+        }
+        if let Some(default) = field.default.as_deref() {
+            validate_value(field, default).map_err(|e| format!("{at}.default: {e}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks the format of one entered value.
+pub fn validate_value(field: &CredentialField, value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(());
+    }
+    if value.chars().any(char::is_control) {
+        return Err(format!("{} contains control characters", field.label));
+    }
+    if field.kind == KIND_URL && !value.starts_with("https://") && !value.starts_with("http://") {
+        return Err(format!("{} must be an http(s) URL", field.label));
+    }
+    Ok(())
+}
+
+/// Whether `values` (env var → value) satisfy the provider's fields. Defaults
+/// count as set.
+pub fn is_complete(fields: &[CredentialField], values: &HashMap<String, String>) -> bool {
+    missing(fields, values).is_empty()
+}
+
+/// Labels of the fields still needed, with alternatives joined by " or ".
+pub fn missing(fields: &[CredentialField], values: &HashMap<String, String>) -> Vec<String> {
+    let is_set = |field: &CredentialField| {
+        field.default.is_some()
+            || values
+                .get(&field.env_var)
+                .map(|value| !value.trim().is_empty())
+                .unwrap_or(false)
+    };
+
+    let mut missing = fields
+        .iter()
+        .filter(|field| field.group.is_none() && field.required && !is_set(field))
+        .map(|field| field.label.clone())
+        .collect::<Vec<_>>();
+
+    let mut groups: BTreeMap<&str, Vec<&CredentialField>> = BTreeMap::new();
+    for field in fields {
+        if let Some(group) = field.group.as_deref() {
+            groups.entry(group).or_default().push(field);
+        }
+    }
+    let any_group_complete = groups
+        .values()
+        .any(|members| members.iter().all(|field| is_set(field)));
+    if !groups.is_empty() && !any_group_complete {
+        missing.push(
+            groups
+                .values()
+                .map(|members| {
+                    members
+                        .iter()
+                        .map(|field| field.label.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" + ")
+                })
+                .collect::<Vec<_>>()
+                .join(" or "),
+        );
+    }
+    missing
+}
+
+pub fn is_env_var_name(value: &str) -> bool {
+    let mut chars = value.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_uppercase() || c == '_')
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::llm_config::test_values;
+
+    fn field(env_var: &str, group: Option<&str>) -> CredentialField {
+        CredentialField {
+            group: group.map(str::to_string),
+            label: env_var.to_string(),
+            ..api_key_field(env_var)
+        }
+    }
+
+    #[test]
+    fn grouped_fields_are_alternatives() {
+        let fields = vec![
+            CredentialField {
+                kind: KIND_TEXT.to_string(),
+                ..field("AWS_REGION", None)
+            },
+            field("AWS_ACCESS_KEY_ID", Some("access_keys")),
+            field("AWS_SECRET_ACCESS_KEY", Some("access_keys")),
+            field("AWS_PROFILE", Some("profile")),
+        ];
+
+        assert_eq!(
+            missing(&fields, &test_values(&[("AWS_ACCESS_KEY_ID", "AKIA")])),
+            vec![
+                "AWS_REGION",
+                "AWS_ACCESS_KEY_ID + AWS_SECRET_ACCESS_KEY or AWS_PROFILE"
+            ]
+        );
+        assert!(is_complete(
+            &fields,
+            &test_values(&[("AWS_REGION", "eu-west-1"), ("AWS_PROFILE", "work")])
+        ));
+        assert!(is_complete(
+            &fields,
+            &test_values(&[
+                ("AWS_REGION", "eu-west-1"),
+                ("AWS_ACCESS_KEY_ID", "AKIA"),
+                ("AWS_SECRET_ACCESS_KEY", "secret"),
+            ])
+        ));
+    }
+
+    #[test]
+    fn field_declarations_and_values_are_checked() {
+        let url = CredentialField {
+            kind: KIND_URL.to_string(),
+            ..field("AZURE_OPENAI_ENDPOINT", None)
+        };
+        assert!(validate_value(&url, "https://acme.openai.azure.com").is_ok());
+        assert!(validate_value(&url, "acme.openai.azure.com").is_err());
+
+        assert!(validate_fields(&[url.clone(), url.clone()]).is_err());
+        assert!(validate_fields(&[CredentialField {
+            kind: "number".to_string(),
+            ..url
+        }])
+        .is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use serde_json::{json, Value};
+
+use super::{credentials, discovery, ModelInfo, ProviderInfo};
+use crate::modules::common::CustomProvider;
+
+/// OpenClaw's adapter name for endpoints speaking the OpenAI chat API.
+const OPENCLAW_API: &str = "openai-completions";
+
+/// Checks the definition and normalises it: trims fields, strips a trailing
+/// `/` or `/v1` from the base URL and drops duplicate models.
+pub fn normalize(provider: CustomProvider) -> Result<CustomProvider, String> {
+    let id = provider.id.trim().to_string();
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "provider id {id:?} must be lowercase letters, digits, '-' or '_'"
+        ));
+    }
+
+    let display_name = provider.display_name.trim().to_string();
+    if display_name.is_empty() {
+        return Err("display name cannot be empty".to_string());
+    }
+
+    let mut base_url = provider.base_url.trim().trim_end_matches('/').to_string();
+    if let Some(root) = base_url.strip_suffix("/v1") {
+        base_url = root.to_string();
+    }
+    if !(base_url.starts_with("http://") || base_url.starts_with("https://")) {
+        return Err(format!(
+            "base URL {base_url:?} must start with http:// or https://"
+        ));
+    }
+
+    let env_var = provider
+        .env_var
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    if let Some(name) = env_var.as_deref() {
+        if !credentials::is_env_var_name(name) {
+            return Err(format!("{name:?} is not a valid environment variable name"));
+        }
+    }
+
+    let mut models = Vec::new();
+    for model in provider.models {
+        let model = model.trim().to_string();
+        if !model.is_empty() && !models.contains(&model) {
+            models.push(model);
+        }
+    }
+    if models.is_empty() {
+        return Err("at least one model is required".to_string());
+    }
+
+    Ok(CustomProvider {
+        id,
+        display_name,
+        base_url,
+        env_var,
+        models,
+    })
+}
+
+pub fn provider_info(provider: &CustomProvider) -> ProviderInfo {
+    ProviderInfo {
+        id: provider.id.clone(),
+        display_name: provider.display_name.clone(),
+        requires_api_key: provider.env_var.is_some(),
+        env_var: provider.env_var.clone(),
+        credentials: provider
+            .env_var
+            .as_deref()
+            .map(credentials::api_key_field)
+            .into_iter()
+            .collect(),
+        models: provider
+            .models
+            .iter()
+            .enumerate()
+            .map(|(index, model)| ModelInfo {
+                id: format!("{}/{model}", provider.id),
+                display_name: model.clone(),
+                is_default: index == 0,
+                discovered: false,
+                parameters: None,
+            })
+            .collect(),
+        base_url: Some(provider.base_url.clone()),
+        custom: true,
+        base_url_override: None,
+        parameters: Vec::new(),
+    }
+}
+
+/// Calls `GET {base_url}/v1/models` and makes sure every configured model is
+/// served by the endpoint.
+pub fn check_endpoint(provider: &CustomProvider, api_key: Option<&str>) -> Result<(), String> {
+    let served =
+        discovery::fetch_models(&provider.id, api_key, &provider.base_url).map_err(|e| {
+            format!(
+                "{} did not answer as an OpenAI-compatible API: {e}",
+                provider.base_url
+            )
+        })?;
+    let missing = provider
+        .models
+        .iter()
+        .filter(|model| {
+            let id = format!("{}/{model}", provider.id);
+            !served.iter().any(|served| served.id == id)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} does not serve: {}",
+            provider.base_url,
+            missing.join(", ")
+        ))
+    }
+}
+
+/// The `models.providers.<id>` entry OpenClaw reads for a custom endpoint.
+/// The key is referenced through its env var, which the gateway receives
+/// from the secret store at start-up, so it never lands in `openclaw.json`.
+pub fn openclaw_entry(provider: &CustomProvider) -> Value {
+    let mut entry = json!({
+        "baseUrl": format!("{}/v1", provider.base_url),
+        "api": OPENCLAW_API,
+        "models": provider
+            .models
+            .iter()
+            .map(|model| json!({ "id": model, "name": model }))
+            .collect::<Vec<_>>(),
+    });
+    if let Some(env_var) = provider.env_var.as_deref() {
+        entry["apiKey"] = json!(format!("${{{env_var}}}"));
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::common;
+
+    fn proxy() -> CustomProvider {
+        CustomProvider {
+            id: " corp-proxy ".to_string(),
+            display_name: "Corp proxy".to_string(),
+            base_url: "https://llm.corp.example/v1/".to_string(),
+            env_var: Some("CORP_LLM_KEY".to_string()),
+            models: vec![
+                "gpt-4o".to_string(),
+                "llama-3.1-70b".to_string(),
+                "gpt-4o".to_string(),
+            ],
+        }
+    }
+
+    #[test]
+    fn definitions_are_normalized_and_validated() {
+        let provider = normalize(proxy()).expect("valid provider");
+        assert_eq!(provider.id, "corp-proxy");
+        assert_eq!(provider.base_url, "https://llm.corp.example");
+        assert_eq!(provider.models, vec!["gpt-4o", "llama-3.1-70b"]);
+
+        let info = provider_info(&provider);
+        assert!(info.custom && info.requires_api_key);
+        assert_eq!(info.models[0].id, "corp-proxy/gpt-4o");
+        assert!(info.models[0].is_default);
+
+        let bad_url = CustomProvider {
+            base_url: "llm.corp.example".to_string(),
+            ..proxy()
+        };
+        assert!(normalize(bad_url).is_err());
+        let bad_env = CustomProvider {
+            env_var: Some("corp-key".to_string()),
+            ..proxy()
+        };
+        assert!(normalize(bad_env).is_err());
+    }
+
+    #[test]
+    fn openclaw_entry_references_the_key_by_env_var() {
+        let entry = openclaw_entry(&normalize(proxy()).expect("valid provider"));
+        assert_eq!(
+            entry,
+            json!({
+                "baseUrl": "https://llm.corp.example/v1",
+                "api": "openai-completions",
+                "models": [
+                    { "id": "gpt-4o", "name": "gpt-4o" },
+                    { "id": "llama-3.1-70b", "name": "llama-3.1-70b" }
+                ],
+                "apiKey": "${CORP_LLM_KEY}"
+            })
+        );
+    }
+
+    #[test]
+    fn endpoint_check_requires_configured_models() {
+        let (base_url, requests) = common::serve_http(vec![
+            (
+                200,
+                r#"{"data":[{"id":"gpt-4o"},{"id":"llama-3.1-70b"}]}"#.to_string(),
+            ),
+            (200, r#"{"data":[{"id":"gpt-4o"}]}"#.to_string()),
+        ]);
+        let provider = normalize(CustomProvider {
+            base_url: format!("{base_url}/v1"),
+            ..proxy()
+        })
+        .expect("valid provider");
+
+        check_endpoint(&provider, Some("corp-key")).expect("all models served");
+        let request = requests.recv().expect("request captured");
+        assert!(request.starts_with("GET /v1/models"));
+        assert!(request.contains("authorization: Bearer corp-key"));
+
+        let err = check_endpoint(&provider, Some("corp-key")).expect_err("model missing");
+        assert!(err.ends_with("does not serve: llama-3.1-70b"));
+    }
+}
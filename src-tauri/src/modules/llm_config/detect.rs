@@ -0,0 +1,348 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::ProviderInfo;
+use crate::modules::{openclaw_config::json5, secret_store};
+
+pub const SOURCE_ENVIRONMENT: &str = "environment";
+pub const SOURCE_DOTENV: &str = "dotenv";
+pub const SOURCE_OPENCLAW: &str = "openclaw";
+
+/// A credential found outside the key store, as the UI sees it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DetectedCredential {
+    /// Stable across scans; pass it to `import_detected_credentials`.
+    pub id: String,
+    /// One of `environment`, `dotenv` or `openclaw`.
+    pub source: String,
+    /// Where the value was found: a file path, or "process environment".
+    pub location: String,
+    pub env_var: String,
+    /// The provider declaring `env_var`, if any.
+    pub provider: Option<String>,
+    pub preview: String,
+    /// The same value is already in the key store.
+    pub already_stored: bool,
+}
+
+/// A detected value, kept in the backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Found {
+    pub source: &'static str,
+    pub location: String,
+    pub env_var: String,
+    pub value: String,
+}
+
+impl Found {
+    pub fn id(&self) -> String {
+        format!("{}:{}:{}", self.source, self.location, self.env_var)
+    }
+}
+
+/// Where to look. Kept separate from the real environment so scans can be
+/// pointed at a temp directory.
+pub struct Places {
+    pub env: HashMap<String, String>,
+    pub dotenv_files: Vec<PathBuf>,
+    /// `~/.openclaw`.
+    pub openclaw_dir: PathBuf,
+}
+
+impl Places {
+    /// The process environment, `.env` files in the working directory, the
+    /// home directory and `~/.openclaw`, and OpenClaw's own config.
+    pub fn current(home: &Path) -> Self {
+        let openclaw_dir = home.join(".openclaw");
+        let mut dotenv_files = Vec::new();
+        if let Ok(cwd) = std::env::current_dir() {
+            dotenv_files.push(cwd.join(".env"));
+            dotenv_files.push(cwd.join(".env.local"));
+        }
+        dotenv_files.push(home.join(".env"));
+        dotenv_files.push(openclaw_dir.join(".env"));
+        dotenv_files.dedup();
+
+        Self {
+            env: std::env::vars().collect(),
+            dotenv_files,
+            openclaw_dir,
+        }
+    }
+}
+
+/// Every non-blank value of a credential field some provider declares.
+/// OAuth tokens in OpenClaw's auth profiles are skipped: OpenClaw refreshes
+/// them itself and a copy would go stale.
+pub fn scan(places: &Places, providers: &[ProviderInfo]) -> Vec<Found> {
+    let is_known = |env_var: &str| {
+        providers.iter().any(|provider| {
+            provider
+                .credentials
+                .iter()
+                .any(|field| field.env_var == env_var)
+        })
+    };
+    let mut found = Vec::new();
+    let mut push = |source: &'static str, location: &str, env_var: &str, value: &str| {
+        let value = value.trim();
+        if is_known(env_var) && !value.is_empty() {
+            found.push(Found {
+                source,
+                location: location.to_string(),
+                env_var: env_var.to_string(),
+                value: value.to_string(),
+            });
+        }
+    };
+
+    let mut env = places.env.iter().collect::<Vec<_>>();
+    env.sort();
+    for (name, value) in env {
+        push(SOURCE_ENVIRONMENT, "process environment", name, value);
+    }
+
+    for path in &places.dotenv_files {
+        let Ok(raw) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let location = path.display().to_string();
+        for (name, value) in parse_dotenv(&raw) {
+            push(SOURCE_DOTENV, &location, &name, &value);
+        }
+    }
+
+    let config_path = places.openclaw_dir.join("openclaw.json");
+    if let Some(config) = read_json5(&config_path) {
+        let location = config_path.display().to_string();
+        for (name, value) in config_env(&config) {
+            push(SOURCE_OPENCLAW, &location, &name, &value);
+        }
+    }
+
+    for path in auth_profile_files(&places.openclaw_dir) {
+        let Some(store) = read_json5(&path) else {
+            continue;
+        };
+        let location = path.display().to_string();
+        for (profile_id, provider_id, key) in api_key_profiles(&store) {
+            let env_var = providers
+                .iter()
+                .find(|provider| provider.id == provider_id)
+                .and_then(|provider| provider.env_var.as_deref());
+            if let Some(env_var) = env_var {
+                push(
+                    SOURCE_OPENCLAW,
+                    &format!("{location}#{profile_id}"),
+                    env_var,
+                    &key,
+                );
+            }
+        }
+    }
+
+    found
+}
+
+pub fn describe(
+    found: &Found,
+    providers: &[ProviderInfo],
+    ring: &secret_store::KeyRing,
+) -> DetectedCredential {
+    DetectedCredential {
+        id: found.id(),
+        source: found.source.to_string(),
+        location: found.location.clone(),
+        env_var: found.env_var.clone(),
+        provider: providers
+            .iter()
+            .find(|provider| {
+                provider
+                    .credentials
+                    .iter()
+                    .any(|field| field.env_var == found.env_var)
+            })
+            .map(|provider| provider.id.clone()),
+        preview: secret_store::mask(&found.value),
+        already_stored: ring.contains_value(&found.env_var, &found.value),
+    }
+}
+
+/// `KEY=value` lines, with optional `export`, quotes and trailing comments.
+fn parse_dotenv(raw: &str) -> Vec<(String, String)> {
+    raw.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, value) = line.split_once('=')?;
+            let name = name.trim();
+            if name.starts_with('#') {
+                return None;
+            }
+            let value = value.trim();
+            let value = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let rest = &value[1..];
+                    rest.find(quote).map_or(rest, |end| &rest[..end])
+                }
+                _ => value.split(" #").next().unwrap_or_default().trim(),
+            };
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn read_json5(path: &Path) -> Option<Value> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    match json5::parse(&raw) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            log::warn!(
+                "skipping {} while detecting credentials: {err}",
+                path.display()
+            );
+            None
+        }
+    }
+}
+
+/// String entries of the config's `env` block, including `env.vars`.
+fn config_env(config: &Value) -> Vec<(String, String)> {
+    let Some(env) = config.get("env").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let nested = env.get("vars").and_then(Value::as_object);
+    env.iter()
+        .chain(nested.into_iter().flatten())
+        .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_string())))
+        .collect()
+}
+
+/// `~/.openclaw/agents/<agent>/agent/auth-profiles.json` for every agent.
+fn auth_profile_files(openclaw_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(openclaw_dir.join("agents")) else {
+        return Vec::new();
+    };
+    let mut files = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path().join("agent").join("auth-profiles.json"))
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+/// `(profile id, provider, key)` for each API key or static token profile.
+fn api_key_profiles(store: &Value) -> Vec<(String, String, String)> {
+    let Some(profiles) = store.get("profiles").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    profiles
+        .iter()
+        .filter_map(|(id, profile)| {
+            let secret = match profile.get("type").and_then(Value::as_str)? {
+                "api_key" => profile.get("key"),
+                "token" => profile.get("token"),
+                _ => None,
+            }?;
+            Some((
+                id.clone(),
+                profile.get("provider").and_then(Value::as_str)?.to_string(),
+                secret.as_str()?.to_string(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(id: &str, env_var: &str) -> ProviderInfo {
+        ProviderInfo::for_test(id, Some(env_var), &[])
+    }
+
+    #[test]
+    fn dotenv_lines_are_parsed_loosely() {
+        let parsed = parse_dotenv(
+            "# keys\nexport ANTHROPIC_API_KEY=\"sk-ant-1 #x\"\nOPENAI_API_KEY=sk-2 # work\nBROKEN\n",
+        );
+        assert_eq!(
+            parsed,
+            vec![
+                ("ANTHROPIC_API_KEY".to_string(), "sk-ant-1 #x".to_string()),
+                ("OPENAI_API_KEY".to_string(), "sk-2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn known_credentials_are_found_in_every_place() {
+        let dir = std::env::temp_dir().join(format!("openclawini-detect-{}", std::process::id()));
+        let openclaw_dir = dir.join(".openclaw");
+        let agent_dir = openclaw_dir.join("agents").join("main").join("agent");
+        std::fs::create_dir_all(&agent_dir).expect("create dirs");
+        std::fs::write(dir.join(".env"), "OPENAI_API_KEY=sk-openai-dotenv-0001\n")
+            .expect("write dotenv");
+        std::fs::write(
+            openclaw_dir.join("openclaw.json"),
+            "{ env: { vars: { OPENAI_API_KEY: 'sk-openai-config-0002' } } }",
+        )
+        .expect("write config");
+        std::fs::write(
+            agent_dir.join("auth-profiles.json"),
+            r#"{"profiles":{
+                "anthropic:default":{"type":"api_key","provider":"anthropic","key":"sk-ant-profile-0003"},
+                "anthropic:oauth":{"type":"oauth","provider":"anthropic","access":"at"}}}"#,
+        )
+        .expect("write auth profiles");
+
+        let providers = vec![
+            provider("anthropic", "ANTHROPIC_API_KEY"),
+            provider("openai", "OPENAI_API_KEY"),
+        ];
+        let places = Places {
+            env: HashMap::from([
+                (
+                    "ANTHROPIC_API_KEY".to_string(),
+                    "sk-ant-env-00004".to_string(),
+                ),
+                ("PATH".to_string(), "/usr/bin".to_string()),
+            ]),
+            dotenv_files: vec![dir.join(".env"), dir.join("missing.env")],
+            openclaw_dir,
+        };
+        let found = scan(&places, &providers);
+        let sources = found
+            .iter()
+            .map(|item| (item.source, item.env_var.as_str(), item.value.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sources,
+            vec![
+                (SOURCE_ENVIRONMENT, "ANTHROPIC_API_KEY", "sk-ant-env-00004"),
+                (SOURCE_DOTENV, "OPENAI_API_KEY", "sk-openai-dotenv-0001"),
+                (SOURCE_OPENCLAW, "OPENAI_API_KEY", "sk-openai-config-0002"),
+                (SOURCE_OPENCLAW, "ANTHROPIC_API_KEY", "sk-ant-profile-0003"),
+            ]
+        );
+        assert!(found[3].location.ends_with("#anthropic:default"));
+
+        let mut ring = secret_store::KeyRing::default();
+        ring.put(
+            "OPENAI_API_KEY",
+            "default",
+            "sk-openai-dotenv-0001".to_string(),
+            "t1",
+        );
+        let described = describe(&found[1], &providers, &ring);
+        assert!(described.already_stored);
+        assert_eq!(described.provider.as_deref(), Some("openai"));
+        assert_eq!(described.preview, "sk-o…0001");
+        assert!(!describe(&found[0], &providers, &ring).already_stored);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
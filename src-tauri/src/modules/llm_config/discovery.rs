@@ -0,0 +1,359 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{ModelInfo, ProviderInfo};
+use crate::modules::common;
+
+pub const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(15);
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Ids in an OpenAI-style model list that are not chat models.
+const NON_CHAT_MARKERS: &[&str] = &[
+    "embedding",
+    "whisper",
+    "tts",
+    "dall-e",
+    "moderation",
+    "transcribe",
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ModelCache {
+    providers: BTreeMap<String, CachedModels>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedModels {
+    fetched_at: u64,
+    models: Vec<ModelInfo>,
+}
+
+/// Asks the provider which models it serves. Returned ids carry the
+/// `provider/` prefix and every entry is marked as discovered.
+pub fn fetch_models(
+    provider: &str,
+    api_key: Option<&str>,
+    base_url: &str,
+) -> Result<Vec<ModelInfo>, String> {
+    let base_url = base_url.trim_end_matches('/');
+    let listed = match provider {
+        "ollama" => {
+            let body = get(&format!("{base_url}/api/tags"), &[])?;
+            entries(&body, "models", "name", None)
+                .into_iter()
+                .map(|(name, _)| {
+                    let name = name
+                        .strip_suffix(":latest")
+                        .map(str::to_string)
+                        .unwrap_or(name);
+                    (name.clone(), name)
+                })
+                .collect()
+        }
+        "anthropic" => {
+            let api_key = api_key.ok_or_else(|| "no API key stored for anthropic".to_string())?;
+            let body = get(
+                &format!("{base_url}/v1/models?limit=1000"),
+                &[
+                    ("x-api-key", api_key),
+                    ("anthropic-version", ANTHROPIC_VERSION),
+                ],
+            )?;
+            entries(&body, "data", "id", Some("display_name"))
+        }
+        "google" => {
+            let api_key = api_key.ok_or_else(|| "no API key stored for google".to_string())?;
+            let body = get(
+                &format!("{base_url}/v1beta/models?pageSize=1000"),
+                &[("x-goog-api-key", api_key)],
+            )?;
+            entries(&body, "models", "name", Some("displayName"))
+                .into_iter()
+                .map(|(name, display_name)| {
+                    let id = name
+                        .strip_prefix("models/")
+                        .map(str::to_string)
+                        .unwrap_or(name);
+                    (id, display_name)
+                })
+                .filter(|(id, _)| !NON_CHAT_MARKERS.iter().any(|marker| id.contains(marker)))
+                .collect()
+        }
+        "amazon-bedrock" | "azure-openai" => {
+            return Err(format!(
+                "{provider} does not offer model listing with the stored credentials"
+            ))
+        }
+        _ => {
+            let authorization = api_key.map(|key| format!("Bearer {key}"));
+            let headers = authorization
+                .as_deref()
+                .map(|value| vec![("authorization", value)])
+                .unwrap_or_default();
+            let body = get(&format!("{base_url}/v1/models"), &headers)?;
+            entries(&body, "data", "id", None)
+                .into_iter()
+                .filter(|(id, _)| !NON_CHAT_MARKERS.iter().any(|marker| id.contains(marker)))
+                .collect()
+        }
+    };
+
+    let mut models = listed
+        .into_iter()
+        .map(|(id, display_name)| ModelInfo {
+            id: format!("{provider}/{id}"),
+            display_name,
+            is_default: false,
+            discovered: true,
+            parameters: None,
+        })
+        .collect::<Vec<_>>();
+    models.sort_by(|a, b| a.id.cmp(&b.id));
+    models.dedup_by(|a, b| a.id == b.id);
+    Ok(models)
+}
+
+fn get(url: &str, headers: &[(&str, &str)]) -> Result<String, String> {
+    let response = common::http_request("GET", url, headers, None, DISCOVERY_TIMEOUT)?;
+    if (200..300).contains(&response.status) {
+        Ok(response.body)
+    } else {
+        Err(format!(
+            "model list request to {url} failed with HTTP {}: {}",
+            response.status,
+            response.body.trim().chars().take(200).collect::<String>()
+        ))
+    }
+}
+
+/// `(id, display name)` pairs from `{ list_key: [{ id_key, name_key? }] }`.
+fn entries(
+    body: &str,
+    list_key: &str,
+    id_key: &str,
+    name_key: Option<&str>,
+) -> Vec<(String, String)> {
+    let parsed = serde_json::from_str::<Value>(body).unwrap_or(Value::Null);
+    parsed
+        .get(list_key)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let id = item.get(id_key)?.as_str()?.to_string();
+                    let name = name_key
+                        .and_then(|key| item.get(key))
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| id.clone());
+                    Some((id, name))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Cached models for `provider` if they were fetched within the TTL, so a
+/// listing need not be fetched again.
+pub fn cached_models(app_data_dir: &Path, provider: &str) -> Option<Vec<ModelInfo>> {
+    let cache = read_cache(app_data_dir);
+    let entry = cache.providers.get(provider)?;
+    (now_secs().saturating_sub(entry.fetched_at) < CACHE_TTL.as_secs())
+        .then(|| entry.models.clone())
+}
+
+/// The last models fetched for `provider`, however old. A model picked
+/// from an earlier listing stays selectable until a new listing drops it.
+pub fn known_models(app_data_dir: &Path, provider: &str) -> Option<Vec<ModelInfo>> {
+    read_cache(app_data_dir)
+        .providers
+        .remove(provider)
+        .map(|entry| entry.models)
+}
+
+pub fn store_models(
+    app_data_dir: &Path,
+    provider: &str,
+    models: &[ModelInfo],
+) -> Result<(), String> {
+    let mut cache = read_cache(app_data_dir);
+    cache.providers.insert(
+        provider.to_string(),
+        CachedModels {
+            fetched_at: now_secs(),
+            models: models.to_vec(),
+        },
+    );
+    write_cache(app_data_dir, &cache)
+}
+
+/// Drops what was listed for `provider`, for when it moves to another
+/// endpoint that may serve different models.
+pub fn forget_models(app_data_dir: &Path, provider: &str) -> Result<(), String> {
+    let mut cache = read_cache(app_data_dir);
+    if cache.providers.remove(provider).is_none() {
+        return Ok(());
+    }
+    write_cache(app_data_dir, &cache)
+}
+
+fn write_cache(app_data_dir: &Path, cache: &ModelCache) -> Result<(), String> {
+    let serialized = serde_json::to_string_pretty(cache)
+        .map_err(|e| format!("failed to serialize model cache: {e}"))?;
+    common::write_file_atomic(
+        &common::model_cache_path(app_data_dir),
+        serialized.as_bytes(),
+    )
+}
+
+/// Appends the last discovered models that the catalog does not already
+/// list, whether or not they are due for a refetch.
+pub fn merge_cached(app_data_dir: &Path, providers: &mut [ProviderInfo]) {
+    for provider in providers.iter_mut() {
+        if let Some(models) = known_models(app_data_dir, &provider.id) {
+            merge_discovered(provider, models);
+        }
+    }
+}
+
+pub fn merge_discovered(provider: &mut ProviderInfo, models: Vec<ModelInfo>) {
+    for model in models {
+        if !provider.models.iter().any(|known| known.id == model.id) {
+            provider.models.push(model);
+        }
+    }
+}
+
+fn read_cache(app_data_dir: &Path) -> ModelCache {
+    std::fs::read_to_string(common::model_cache_path(app_data_dir))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_model_lists_are_normalized() {
+        let (base_url, requests) = common::serve_http(vec![
+            (
+                200,
+                r#"{"models":[{"name":"llama3.2:latest"},{"name":"qwen2.5:7b"}]}"#.to_string(),
+            ),
+            (
+                200,
+                r#"{"data":[{"id":"gpt-4o"},{"id":"text-embedding-3-small"},{"id":"whisper-1"}]}"#
+                    .to_string(),
+            ),
+            (
+                200,
+                r#"{"data":[{"id":"claude-opus-4-6","display_name":"Claude Opus 4.6"}],"has_more":false}"#
+                    .to_string(),
+            ),
+        ]);
+
+        let ollama = fetch_models("ollama", None, &base_url).expect("ollama models");
+        let ids = ollama.iter().map(|m| m.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["ollama/llama3.2", "ollama/qwen2.5:7b"]);
+        assert!(ollama.iter().all(|m| m.discovered && !m.is_default));
+        assert!(requests
+            .recv()
+            .expect("request")
+            .starts_with("GET /api/tags"));
+
+        let openai = fetch_models("openai", Some("sk-1"), &base_url).expect("openai models");
+        assert_eq!(openai.len(), 1);
+        assert_eq!(openai[0].id, "openai/gpt-4o");
+        assert!(requests
+            .recv()
+            .expect("request")
+            .contains("authorization: Bearer sk-1"));
+
+        let anthropic =
+            fetch_models("anthropic", Some("sk-ant"), &base_url).expect("anthropic models");
+        assert_eq!(anthropic[0].display_name, "Claude Opus 4.6");
+        let request = requests.recv().expect("request");
+        assert!(request.starts_with("GET /v1/models?limit=1000"));
+        assert!(request.contains("x-api-key: sk-ant"));
+    }
+
+    #[test]
+    fn gemini_model_names_lose_their_prefix() {
+        let (base_url, requests) = common::serve_http(vec![(
+            200,
+            r#"{"models":[{"name":"models/gemini-2.5-flash","displayName":"Gemini 2.5 Flash"},{"name":"models/text-embedding-004"}]}"#
+                .to_string(),
+        )]);
+
+        let google = fetch_models("google", Some("AIza-1"), &base_url).expect("gemini models");
+        assert_eq!(google.len(), 1);
+        assert_eq!(google[0].id, "google/gemini-2.5-flash");
+        assert_eq!(google[0].display_name, "Gemini 2.5 Flash");
+        let request = requests.recv().expect("request");
+        assert!(request.starts_with("GET /v1beta/models"));
+        assert!(request.contains("x-goog-api-key: AIza-1"));
+    }
+
+    #[test]
+    fn expired_cache_refetches_but_still_merges() {
+        let dir = std::env::temp_dir().join(format!("openclawini-models-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let discovered = |id: &str| ModelInfo {
+            id: id.to_string(),
+            display_name: id.to_string(),
+            is_default: false,
+            discovered: true,
+            parameters: None,
+        };
+
+        store_models(
+            &dir,
+            "ollama",
+            &[discovered("ollama/mistral"), discovered("ollama/phi4")],
+        )
+        .expect("store");
+        let mut provider = ProviderInfo::for_test("ollama", None, &["ollama/mistral"]);
+        merge_cached(&dir, std::slice::from_mut(&mut provider));
+        let ids = provider
+            .models
+            .iter()
+            .map(|m| (m.id.as_str(), m.discovered))
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![("ollama/mistral", false), ("ollama/phi4", true)]);
+
+        let mut cache = read_cache(&dir);
+        cache.providers.get_mut("ollama").expect("entry").fetched_at -= CACHE_TTL.as_secs();
+        std::fs::write(
+            common::model_cache_path(&dir),
+            serde_json::to_string(&cache).expect("serialize"),
+        )
+        .expect("write cache");
+        assert!(cached_models(&dir, "ollama").is_none());
+
+        provider.models.truncate(1);
+        merge_cached(&dir, std::slice::from_mut(&mut provider));
+        assert_eq!(
+            provider.models.last().map(|m| m.id.as_str()),
+            Some("ollama/phi4"),
+            "expired models stay selectable"
+        );
+
+        forget_models(&dir, "ollama").expect("forget");
+        assert!(known_models(&dir, "ollama").is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,207 @@
+//! Base URL overrides for built-in providers, such as an Ollama running on
+//! another machine, and the reachability check run before one is saved.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::modules::common;
+use crate::modules::openclaw_config::json5::Document;
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointCheck {
+    pub provider: String,
+    pub base_url: String,
+    pub reachable: bool,
+    pub detail: String,
+    pub http_status: Option<u16>,
+}
+
+/// Trims `url` and drops trailing slashes. Only http(s) URLs with a host
+/// are accepted.
+pub fn normalize(url: &str) -> Result<String, String> {
+    let url = url.trim().trim_end_matches('/').to_string();
+    let rest = url
+        .strip_prefix("http://")
+        .or_else(|| url.strip_prefix("https://"))
+        .ok_or_else(|| format!("base URL {url:?} must start with http:// or https://"))?;
+    if rest.is_empty() || rest.starts_with('/') || rest.chars().any(char::is_whitespace) {
+        return Err(format!("base URL {url:?} has no valid host"));
+    }
+    Ok(url)
+}
+
+/// The `baseUrl` OpenClaw expects for `provider` served at `base_url`.
+/// Anthropic's client adds the version itself and Gemini lives under
+/// `/v1beta`; every other provider speaks the OpenAI API under `/v1`, as
+/// custom providers do.
+pub fn openclaw_base_url(provider: &str, base_url: &str) -> String {
+    match provider {
+        "anthropic" => base_url.to_string(),
+        "google" => format!("{base_url}/v1beta"),
+        _ => format!("{base_url}/v1"),
+    }
+}
+
+/// Points `models.providers.<provider>` at `base_url`, or removes the
+/// override when it is `None`. A new entry gets `models: []` and the config
+/// `models.mode: "merge"` so OpenClaw keeps its built-in model list.
+pub fn write_override(
+    document: &mut Document,
+    provider: &str,
+    base_url: Option<&str>,
+) -> Result<(), String> {
+    let Some(base_url) = base_url else {
+        document.remove(&["models", "providers", provider, "baseUrl"])?;
+        let leftover = document.value()["models"]["providers"][provider].clone();
+        if leftover == json!({}) || leftover == json!({ "models": [] }) {
+            document.remove(&["models", "providers", provider])?;
+        }
+        return Ok(());
+    };
+
+    document.set(
+        &["models", "providers", provider, "baseUrl"],
+        &json!(openclaw_base_url(provider, base_url)),
+    )?;
+    if document.value()["models"]["providers"][provider]
+        .get("models")
+        .is_none()
+    {
+        document.set(&["models", "providers", provider, "models"], &json!([]))?;
+    }
+    if document.value().pointer("/models/mode").is_none() {
+        document.set(&["models", "mode"], &json!("merge"))?;
+    }
+    Ok(())
+}
+
+/// Sends one unauthenticated request to the listing endpoint `provider`
+/// serves under `base_url`. Any HTTP answer counts as reachable, since a
+/// missing key still gets a 401, except that Ollama must report a version.
+pub fn check(provider: &str, base_url: &str) -> EndpointCheck {
+    let path = match provider {
+        "ollama" => "/api/version",
+        "google" => "/v1beta/models",
+        _ => "/v1/models",
+    };
+    let result = |reachable: bool, detail: String, http_status: Option<u16>| EndpointCheck {
+        provider: provider.to_string(),
+        base_url: base_url.to_string(),
+        reachable,
+        detail,
+        http_status,
+    };
+
+    let response = match common::http_request(
+        "GET",
+        &format!("{base_url}{path}"),
+        &[],
+        None,
+        CHECK_TIMEOUT,
+    ) {
+        Ok(response) => response,
+        Err(err) => return result(false, err, None),
+    };
+    let status = Some(response.status);
+    if provider != "ollama" {
+        return result(
+            true,
+            format!("{base_url} answered with HTTP {}", response.status),
+            status,
+        );
+    }
+
+    let version = serde_json::from_str::<Value>(&response.body)
+        .ok()
+        .and_then(|body| body.get("version")?.as_str().map(str::to_string));
+    match version {
+        Some(version) if (200..300).contains(&response.status) => {
+            result(true, format!("Ollama {version} at {base_url}"), status)
+        }
+        _ => result(
+            false,
+            format!(
+                "{base_url} answered with HTTP {} but is not an Ollama server",
+                response.status
+            ),
+            status,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_urls_are_normalized() {
+        assert_eq!(
+            normalize(" http://10.0.0.5:11434/ ").expect("valid"),
+            "http://10.0.0.5:11434"
+        );
+        assert_eq!(
+            normalize("https://llm.corp.example/openai").expect("valid"),
+            "https://llm.corp.example/openai"
+        );
+        assert!(normalize("10.0.0.5:11434").is_err());
+        assert!(normalize("http://").is_err());
+        assert!(normalize("http://gpu box:11434").is_err());
+    }
+
+    #[test]
+    fn overrides_are_written_with_the_api_prefix() {
+        let mut document = Document::parse("{}").expect("parses");
+        write_override(&mut document, "ollama", Some("http://10.0.0.5:11434")).expect("set");
+        write_override(&mut document, "anthropic", Some("https://proxy.example")).expect("set");
+        let config = document.value();
+        assert_eq!(
+            config["models"]["providers"]["ollama"],
+            json!({ "baseUrl": "http://10.0.0.5:11434/v1", "models": [] })
+        );
+        assert_eq!(
+            config["models"]["providers"]["anthropic"]["baseUrl"],
+            "https://proxy.example"
+        );
+        assert_eq!(config["models"]["mode"], "merge");
+        assert_eq!(
+            openclaw_base_url("google", "https://gemini.example"),
+            "https://gemini.example/v1beta"
+        );
+
+        write_override(&mut document, "ollama", None).expect("reset");
+        assert!(document.value()["models"]["providers"]
+            .get("ollama")
+            .is_none());
+    }
+
+    #[test]
+    fn reachability_depends_on_the_provider() {
+        let (base_url, requests) = common::serve_http(vec![
+            (200, r#"{"version":"0.5.7"}"#.to_string()),
+            (404, "404 page not found".to_string()),
+            (401, r#"{"error":{"message":"missing key"}}"#.to_string()),
+        ]);
+
+        let ollama = check("ollama", &base_url);
+        assert!(ollama.reachable, "{}", ollama.detail);
+        assert!(ollama.detail.contains("0.5.7"));
+        assert!(requests
+            .recv()
+            .expect("request")
+            .starts_with("GET /api/version"));
+
+        let not_ollama = check("ollama", &base_url);
+        assert!(!not_ollama.reachable);
+        assert_eq!(not_ollama.http_status, Some(404));
+
+        let openai = check("openai", &base_url);
+        assert!(openai.reachable, "a 401 still means the endpoint answers");
+
+        let closed = check("ollama", "http://127.0.0.1:9");
+        assert!(!closed.reachable);
+        assert_eq!(closed.http_status, None);
+    }
+}
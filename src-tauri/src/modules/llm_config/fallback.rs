@@ -0,0 +1,162 @@
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use super::{credentials, ProviderInfo};
+
+/// Reads the model chain, primary first, from `agent.model`. OpenClaw
+/// accepts either a bare model id or `{ primary, fallbacks }`.
+pub fn chain_from_config(config: &Value) -> Vec<String> {
+    let Some(model) = config.get("agent").and_then(|agent| agent.get("model")) else {
+        return Vec::new();
+    };
+    if let Some(id) = model.as_str() {
+        return vec![id.to_string()];
+    }
+
+    let Some(primary) = model.get("primary").and_then(Value::as_str) else {
+        return Vec::new();
+    };
+    let mut chain = vec![primary.to_string()];
+    chain.extend(
+        model
+            .get("fallbacks")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string),
+    );
+    chain
+}
+
+/// The `agent.model` value for `chain`. A chain without fallbacks keeps the
+/// plain string form so configs written before fallbacks existed are
+/// rewritten in the same shape.
+pub fn model_value(chain: &[String]) -> Value {
+    match chain {
+        [primary] => json!(primary),
+        [primary, fallbacks @ ..] => json!({ "primary": primary, "fallbacks": fallbacks }),
+        [] => Value::Null,
+    }
+}
+
+/// Checks that every fallback is a model of a known provider and that no
+/// model appears twice in the chain.
+pub fn check_fallbacks(
+    primary: &str,
+    fallbacks: &[String],
+    providers: &[ProviderInfo],
+) -> Result<(), String> {
+    for (index, model) in fallbacks.iter().enumerate() {
+        if model == primary || fallbacks[..index].contains(model) {
+            return Err(format!("{model} appears more than once in the model chain"));
+        }
+        let provider = provider_of(model, providers)
+            .ok_or_else(|| format!("fallback {model} does not belong to a known provider"))?;
+        if !provider
+            .models
+            .iter()
+            .any(|candidate| &candidate.id == model)
+        {
+            return Err(format!(
+                "fallback {model} is not valid for provider {}",
+                provider.id
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// One warning per fallback that could not be used at runtime because its
+/// provider is unknown or lacks stored credentials.
+pub fn warnings(
+    chain: &[String],
+    providers: &[ProviderInfo],
+    keys: &HashMap<String, String>,
+) -> Vec<String> {
+    chain
+        .iter()
+        .skip(1)
+        .filter_map(|model| match provider_of(model, providers) {
+            None => Some(format!("fallback {model}: unknown provider")),
+            Some(provider)
+                if provider.requires_api_key
+                    && !credentials::is_complete(&provider.credentials, keys) =>
+            {
+                Some(format!(
+                    "fallback {model}: no API key stored for {}",
+                    provider.display_name
+                ))
+            }
+            Some(_) => None,
+        })
+        .collect()
+}
+
+fn provider_of<'a>(model: &str, providers: &'a [ProviderInfo]) -> Option<&'a ProviderInfo> {
+    let (provider, _) = model.split_once('/')?;
+    providers.iter().find(|item| item.id == provider)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_model_shapes_round_trip() {
+        let plain = json!({ "agent": { "model": "anthropic/claude-opus-4-6" } });
+        assert_eq!(chain_from_config(&plain), vec!["anthropic/claude-opus-4-6"]);
+
+        let chain = vec![
+            "anthropic/claude-opus-4-6".to_string(),
+            "openai/gpt-4o".to_string(),
+            "ollama/llama3.2".to_string(),
+        ];
+        let value = model_value(&chain);
+        assert_eq!(value["primary"], "anthropic/claude-opus-4-6");
+        assert_eq!(
+            chain_from_config(&json!({ "agent": { "model": value } })),
+            chain
+        );
+        assert_eq!(model_value(&chain[..1]), json!("anthropic/claude-opus-4-6"));
+        assert!(chain_from_config(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn fallbacks_are_checked_and_warned_about() {
+        let providers = vec![
+            ProviderInfo::for_test(
+                "anthropic",
+                Some("ANTHROPIC_API_KEY"),
+                &["anthropic/claude-opus-4-6"],
+            ),
+            ProviderInfo::for_test("openai", Some("OPENAI_API_KEY"), &["openai/gpt-4o"]),
+            ProviderInfo::for_test("ollama", None, &["ollama/llama3.2"]),
+        ];
+        let primary = "anthropic/claude-opus-4-6";
+        let chain = |models: &[&str]| models.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+
+        assert!(check_fallbacks(
+            primary,
+            &chain(&["openai/gpt-4o", "ollama/llama3.2"]),
+            &providers
+        )
+        .is_ok());
+        assert!(check_fallbacks(primary, &chain(&[primary]), &providers).is_err());
+        assert!(check_fallbacks(primary, &chain(&["openai/gpt-5"]), &providers).is_err());
+        assert!(check_fallbacks(primary, &chain(&["groq/llama"]), &providers).is_err());
+
+        let keys = HashMap::from([("ANTHROPIC_API_KEY".to_string(), "sk-ant".to_string())]);
+        assert_eq!(
+            warnings(
+                &chain(&[primary, "openai/gpt-4o", "ollama/llama3.2", "groq/llama"]),
+                &providers,
+                &keys
+            ),
+            vec![
+                "fallback openai/gpt-4o: no API key stored for openai",
+                "fallback groq/llama: unknown provider"
+            ]
+        );
+    }
+}
@@ -1,17 +1,55 @@
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
+use std::path::Path;
 use tauri::AppHandle;
 
-use crate::modules::common;
+use crate::modules::{common, openclaw_config, secret_store, service_manager};
+
+mod catalog;
+mod credentials;
+mod custom;
+mod detect;
+mod discovery;
+mod endpoints;
+mod fallback;
+mod parameters;
+mod profiles;
+mod validation;
+
+pub use crate::modules::common::{CustomProvider, LlmProfile};
+pub use catalog::ProviderCatalogStatus;
+pub use credentials::{is_env_var_name, CredentialField};
+pub use detect::DetectedCredential;
+pub use endpoints::EndpointCheck;
+pub use parameters::{ModelParameter, ModelParameters};
+pub use validation::ApiKeyValidation;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderInfo {
     pub id: String,
     pub display_name: String,
     pub requires_api_key: bool,
+    /// The field `api_key` arguments are stored under, when the provider
+    /// has a single primary secret.
     pub env_var: Option<String>,
+    /// Every value the provider needs, passed to the gateway as env vars.
+    #[serde(default)]
+    pub credentials: Vec<CredentialField>,
     pub models: Vec<ModelInfo>,
+    /// API root used without an override; `None` for providers without a
+    /// fixed endpoint (Bedrock, Azure).
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Endpoint saved with `save_llm_config`, used instead of `base_url`.
+    #[serde(default)]
+    pub base_url_override: Option<String>,
+    /// Defined by the user with `save_custom_provider`.
+    #[serde(default)]
+    pub custom: bool,
+    /// Generation parameters its models accept unless a model lists its own.
+    #[serde(default)]
+    pub parameters: Vec<ModelParameter>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,217 +57,1007 @@ pub struct ModelInfo {
     pub id: String,
     pub display_name: String,
     pub is_default: bool,
+    /// Reported by the provider's API rather than listed in the catalog.
+    #[serde(default)]
+    pub discovered: bool,
+    /// Generation parameters this model accepts; `None` means the
+    /// provider's.
+    #[serde(default)]
+    pub parameters: Option<Vec<ModelParameter>>,
+}
+
+#[cfg(test)]
+impl ProviderInfo {
+    /// A catalog provider for tests. `env_var`, when given, is its single
+    /// required API key field; the first of `models` is the default.
+    pub(crate) fn for_test(id: &str, env_var: Option<&str>, models: &[&str]) -> Self {
+        Self {
+            id: id.to_string(),
+            display_name: id.to_string(),
+            requires_api_key: env_var.is_some(),
+            env_var: env_var.map(str::to_string),
+            credentials: env_var
+                .map(credentials::api_key_field)
+                .into_iter()
+                .collect(),
+            models: models
+                .iter()
+                .enumerate()
+                .map(|(index, model)| ModelInfo {
+                    id: model.to_string(),
+                    display_name: model.to_string(),
+                    is_default: index == 0,
+                    discovered: false,
+                    parameters: None,
+                })
+                .collect(),
+            base_url: None,
+            base_url_override: None,
+            custom: false,
+            parameters: Vec::new(),
+        }
+    }
+}
+
+/// Credential values by env var name, for tests.
+#[cfg(test)]
+pub(crate) fn test_values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LlmConfigState {
     pub selected_provider: Option<String>,
     pub selected_model: Option<String>,
+    /// `selected_model` followed by its fallbacks, in failover order.
+    #[serde(default)]
+    pub model_chain: Vec<String>,
     pub has_api_key: bool,
+    pub keys_locked: bool,
+    /// Fallbacks that cannot be used as configured.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// The saved profile matching the configured model chain, if any.
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    /// Generation parameters configured in openclaw.json, by model id.
+    #[serde(default)]
+    pub model_parameters: HashMap<String, ModelParameters>,
+}
+
+/// A stored key as the UI sees it: masked, never the full value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyInfo {
+    /// The provider using this env var, if any.
+    pub provider: Option<String>,
+    #[serde(flatten)]
+    pub key: secret_store::KeySummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileActivation {
+    pub profile: LlmProfile,
+    pub state: LlmConfigState,
+    /// The running gateway still uses the previous models until it is
+    /// restarted with `restart_gateway`.
+    pub gateway_running: bool,
+}
+
+/// What `save_llm_config` changes. Only `provider` and `model` are required;
+/// every other field left out keeps what is configured.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveLlmConfigRequest {
+    pub provider: String,
+    pub model: String,
+    /// Shorthand for the provider's primary `env_var`.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Other credential fields by env var name.
+    #[serde(default)]
+    pub credentials: Option<HashMap<String, String>>,
+    /// Failover models tried after `model`.
+    #[serde(default)]
+    pub fallbacks: Option<Vec<String>>,
+    /// Files `api_key` under this label and selects it, or selects an
+    /// already stored key when no key is given.
+    #[serde(default)]
+    pub key_label: Option<String>,
+    #[serde(default)]
+    pub parameters: Option<ModelParameters>,
+    /// Endpoint override; blank goes back to the provider's default.
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+#[tauri::command]
+pub fn list_providers(app: AppHandle) -> Vec<ProviderInfo> {
+    providers(&app)
+}
+
+#[tauri::command]
+pub fn get_provider_catalog_status(app: AppHandle) -> Result<ProviderCatalogStatus, String> {
+    Ok(ProviderCatalogStatus {
+        remote_url: common::get_catalog_url(&app)?,
+        sources: catalog::load(&app).sources,
+    })
+}
+
+/// Downloads the provider catalog from `url`, or from the URL saved by the
+/// last refresh, and caches it for `list_providers`.
+#[tauri::command]
+pub async fn refresh_provider_catalog(
+    app: AppHandle,
+    url: Option<String>,
+) -> Result<ProviderCatalogStatus, String> {
+    let url = match url.filter(|value| !value.trim().is_empty()) {
+        Some(url) => url.trim().to_string(),
+        None => common::get_catalog_url(&app)?
+            .ok_or_else(|| "no provider catalog URL configured".to_string())?,
+    };
+    let app_data_dir = common::app_data_dir(&app)?;
+
+    let fetch_url = url.clone();
+    tauri::async_runtime::spawn_blocking(move || catalog::refresh(&app_data_dir, &fetch_url))
+        .await
+        .map_err(|e| format!("catalog refresh task failed: {e}"))??;
+    common::set_catalog_url(&app, &url)?;
+    get_provider_catalog_status(app)
 }
 
+/// Lists the models `provider` actually serves, using the stored API key.
+/// Results are cached for an hour unless `refresh` is set, and show up in
+/// `list_providers` as discovered models.
 #[tauri::command]
-pub fn list_providers() -> Vec<ProviderInfo> {
-    providers()
+pub async fn discover_models(
+    app: AppHandle,
+    provider: String,
+    refresh: Option<bool>,
+) -> Result<Vec<ModelInfo>, String> {
+    let provider_info =
+        provider_by_id(&app, &provider).ok_or_else(|| format!("unknown provider: {provider}"))?;
+    let app_data_dir = common::app_data_dir(&app)?;
+    if !refresh.unwrap_or(false) {
+        if let Some(models) = discovery::cached_models(&app_data_dir, &provider) {
+            return Ok(models);
+        }
+    }
+
+    let base_url = provider_base_url(&app, &provider)
+        .ok_or_else(|| format!("no model listing endpoint known for provider {provider}"))?;
+    let api_key = match provider_info.env_var.as_deref() {
+        Some(env_name) => secret_store::load_secrets(&app)?
+            .remove(env_name)
+            .filter(|value| !value.trim().is_empty()),
+        None => None,
+    };
+    if provider_info.requires_api_key && provider_info.env_var.is_some() && api_key.is_none() {
+        return Err(format!("no API key stored for {provider}"));
+    }
+
+    let models = tauri::async_runtime::spawn_blocking(move || {
+        discovery::fetch_models(&provider, api_key.as_deref(), &base_url).and_then(|models| {
+            discovery::store_models(&app_data_dir, &provider, &models).map(|_| models)
+        })
+    })
+    .await
+    .map_err(|e| format!("model discovery task failed: {e}"))??;
+    Ok(models)
+}
+
+/// Adds or replaces a custom OpenAI-compatible provider. The endpoint must
+/// answer `GET /v1/models` and serve every listed model before the provider
+/// is saved to settings and written to `models.providers` in openclaw.json.
+#[tauri::command]
+pub async fn save_custom_provider(
+    app: AppHandle,
+    provider: CustomProvider,
+    api_key: Option<String>,
+) -> Result<ProviderInfo, String> {
+    let provider = custom::normalize(provider)?;
+    if catalog::load(&app)
+        .providers
+        .iter()
+        .any(|known| known.id == provider.id)
+    {
+        return Err(format!(
+            "{} is a built-in provider id; choose another id",
+            provider.id
+        ));
+    }
+
+    let api_key = api_key
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let mut keys = secret_store::load_secrets(&app)?;
+    let effective_key = match (&api_key, provider.env_var.as_deref()) {
+        (Some(_), None) => {
+            return Err("an env var name is required to store an API key".to_string())
+        }
+        (Some(key), Some(_)) => Some(key.clone()),
+        (None, Some(env_var)) => keys.get(env_var).cloned(),
+        (None, None) => None,
+    };
+
+    let checked = provider.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        custom::check_endpoint(&checked, effective_key.as_deref())
+    })
+    .await
+    .map_err(|e| format!("custom provider check failed: {e}"))??;
+
+    let previous = common::get_custom_providers(&app)?;
+    let mut saved = previous.clone();
+    saved.retain(|existing| existing.id != provider.id);
+    saved.push(provider.clone());
+
+    let entry = custom::openclaw_entry(&provider);
+    let reason = format!("custom provider {} saved", provider.id);
+    with_custom_providers(&app, previous, saved, || {
+        openclaw_config::edit_config(&app, &reason, |document| {
+            document.set(&["models", "providers", &provider.id], &entry)?;
+            if document.value().pointer("/models/mode").is_none() {
+                document.set(&["models", "mode"], &json!("merge"))?;
+            }
+            Ok(())
+        })
+    })
+    .map_err(|e| format!("custom provider {} was not saved: {e}", provider.id))?;
+
+    if let (Some(key), Some(env_var)) = (api_key, provider.env_var.clone()) {
+        keys.insert(env_var, key);
+        secret_store::save_secrets(&app, &keys)?;
+    }
+
+    Ok(custom::provider_info(&provider))
+}
+
+/// Deletes a custom provider from settings and from openclaw.json. Its
+/// stored API key is kept.
+#[tauri::command]
+pub fn remove_custom_provider(app: AppHandle, id: String) -> Result<(), String> {
+    let previous = common::get_custom_providers(&app)?;
+    let mut saved = previous.clone();
+    saved.retain(|existing| existing.id != id);
+    if saved.len() == previous.len() {
+        return Err(format!("no custom provider named {id}"));
+    }
+
+    let reason = format!("custom provider {id} removed");
+    with_custom_providers(&app, previous, saved, || {
+        openclaw_config::edit_config(&app, &reason, |document| {
+            document.remove(&["models", "providers", &id]).map(|_| ())
+        })
+    })
+    .map_err(|e| format!("custom provider {id} was not removed: {e}"))
+}
+
+/// Saves `saved` as the custom providers and runs `edit` to mirror the
+/// change in openclaw.json. If the edit fails (an unparseable file, say),
+/// `previous` is put back so settings and openclaw.json do not disagree.
+fn with_custom_providers(
+    app: &AppHandle,
+    previous: Vec<CustomProvider>,
+    saved: Vec<CustomProvider>,
+    edit: impl FnOnce() -> Result<(), String>,
+) -> Result<(), String> {
+    common::set_custom_providers(app, saved)?;
+    edit().map_err(|err| match common::set_custom_providers(app, previous) {
+        Ok(()) => err,
+        Err(rollback) => format!("{err}; settings could not be rolled back either: {rollback}"),
+    })
 }
 
 #[tauri::command]
 pub fn get_llm_config_state(app: AppHandle) -> Result<LlmConfigState, String> {
-    let selected_model = read_selected_model()?;
+    let config = openclaw_config::read_config()?;
+    let model_chain = config
+        .as_ref()
+        .map(fallback::chain_from_config)
+        .unwrap_or_default();
+    let model_parameters = config
+        .as_ref()
+        .map(parameters::from_config)
+        .unwrap_or_default();
+    let selected_model = model_chain.first().cloned();
     let selected_provider = selected_model
         .as_deref()
         .and_then(|model| model.split('/').next().map(str::to_string));
 
-    let keys = load_api_keys(app)?;
-    let has_api_key = if let Some(provider_id) = selected_provider.as_deref() {
-        if let Some(provider) = provider_by_id(provider_id) {
-            if let Some(env_name) = provider.env_var {
-                keys.get(&env_name)
-                    .map(|value| !value.trim().is_empty())
-                    .unwrap_or(false)
-            } else {
-                false
-            }
-        } else {
-            false
-        }
+    let active_profile = profiles::active(&common::get_llm_profiles(&app)?, &model_chain)
+        .map(|profile| profile.name.clone());
+    let keys_locked = secret_store::is_locked(&app)?;
+    let providers = providers(&app);
+    let keys = if keys_locked {
+        HashMap::new()
     } else {
-        false
+        secret_store::load_secrets(&app)?
     };
+    // A locked store says nothing about which keys exist.
+    let warnings = if keys_locked {
+        Vec::new()
+    } else {
+        fallback::warnings(&model_chain, &providers, &keys)
+    };
+    let has_api_key = selected_provider
+        .as_deref()
+        .and_then(|provider_id| providers.into_iter().find(|item| item.id == provider_id))
+        .map(|provider| {
+            !provider.credentials.is_empty()
+                && credentials::is_complete(&provider.credentials, &keys)
+        })
+        .unwrap_or(false);
 
     Ok(LlmConfigState {
         selected_provider,
         selected_model,
+        model_chain,
         has_api_key,
+        keys_locked,
+        warnings,
+        active_profile,
+        model_parameters,
     })
 }
 
+/// Selects `model` and stores any entered credentials; blank values leave
+/// stored ones untouched. `parameters` replaces the generation parameters
+/// of `model` after checking them against the ranges its catalog entry
+/// declares. `base_url` points a built-in provider at another endpoint once
+/// it answers.
 #[tauri::command]
-pub fn save_llm_config(
+pub async fn save_llm_config(app: AppHandle, request: SaveLlmConfigRequest) -> Result<(), String> {
+    let SaveLlmConfigRequest {
+        provider,
+        model,
+        api_key,
+        credentials,
+        fallbacks,
+        key_label,
+        parameters,
+        base_url,
+    } = request;
+    let providers = providers(&app);
+    let provider_info = providers
+        .iter()
+        .find(|item| item.id == provider)
+        .cloned()
+        .ok_or_else(|| format!("unknown provider: {provider}"))?;
+
+    let model_info = provider_info
+        .models
+        .iter()
+        .find(|candidate| candidate.id == model)
+        .ok_or_else(|| format!("model {model} is not valid for provider {provider}"))?;
+    if let Some(values) = &parameters {
+        parameters::check(
+            values,
+            parameters::accepted(&provider_info, model_info),
+            &model,
+        )?;
+    }
+
+    let fallbacks = match fallbacks {
+        Some(fallbacks) => fallbacks
+            .into_iter()
+            .map(|model| model.trim().to_string())
+            .filter(|model| !model.is_empty())
+            .collect(),
+        None => read_model_chain()?
+            .into_iter()
+            .skip(1)
+            .filter(|existing| existing != &model)
+            .collect::<Vec<_>>(),
+    };
+    fallback::check_fallbacks(&model, &fallbacks, &providers)?;
+
+    // Some(None) resets the endpoint to the provider's default.
+    let endpoint = match base_url.as_deref().map(str::trim) {
+        None => None,
+        Some(url) => {
+            let url = override_base_url(&provider_info, url)?;
+            if !url.is_empty() {
+                let (checked_provider, checked_url) = (provider.clone(), url.clone());
+                let check = tauri::async_runtime::spawn_blocking(move || {
+                    endpoints::check(&checked_provider, &checked_url)
+                })
+                .await
+                .map_err(|e| format!("endpoint check task failed: {e}"))?;
+                if !check.reachable {
+                    return Err(format!("{url} is not reachable: {}", check.detail));
+                }
+            }
+            Some(Some(url).filter(|url| !url.is_empty()))
+        }
+    };
+
+    let mut entered =
+        entered_credentials(&provider_info, api_key, credentials.unwrap_or_default())?;
+    let key_label = key_label
+        .filter(|label| !label.trim().is_empty())
+        .map(|label| secret_store::validate_label(&label))
+        .transpose()?;
+
+    let mut ring = None;
+    if !entered.is_empty() || key_label.is_some() {
+        let mut keys = secret_store::load_keyring(&app)?;
+        if let Some(label) = key_label.as_deref() {
+            let env_var = primary_env_var(&provider_info)?;
+            match entered.remove(&env_var) {
+                Some(value) => keys.put(&env_var, label, value, &common::iso_utc_now()),
+                None => keys.select(&env_var, label)?,
+            }
+        }
+        keys.set_selected(entered, &common::iso_utc_now());
+        ring = Some(keys);
+    }
+
+    let mut chain = vec![model.clone()];
+    chain.extend(fallbacks);
+    let chain_value = fallback::model_value(&chain);
+    let mut reason = format!("model set to {}", chain.join(", "));
+    match &endpoint {
+        Some(Some(url)) => reason.push_str(&format!("; {provider} endpoint set to {url}")),
+        Some(None) => reason.push_str(&format!("; {provider} endpoint reset")),
+        None => {}
+    }
+    openclaw_config::edit_config(&app, &reason, |document| {
+        document.set(&["agent", "model"], &chain_value)?;
+        match parameters.as_ref().map(parameters::config_value) {
+            Some(Some(params)) => document.set(&["agent", "models", &model, "params"], &params)?,
+            Some(None) => {
+                document.remove(&["agent", "models", &model, "params"])?;
+                let entry_empty = document.value()["agent"]["models"]
+                    .get(&model)
+                    .and_then(|entry| entry.as_object())
+                    .is_some_and(|entry| entry.is_empty());
+                if entry_empty {
+                    document.remove(&["agent", "models", &model])?;
+                }
+            }
+            None => {}
+        }
+
+        if let Some(url) = &endpoint {
+            endpoints::write_override(document, &provider, url.as_deref())?;
+        }
+        Ok(())
+    })?;
+
+    if let Some(url) = endpoint {
+        let mut base_urls = common::get_provider_base_urls(&app)?;
+        match url {
+            Some(url) => base_urls.insert(provider.clone(), url),
+            None => base_urls.remove(&provider),
+        };
+        common::set_provider_base_urls(&app, base_urls)?;
+        // Models listed at the old endpoint may not exist at the new one.
+        discovery::forget_models(&common::app_data_dir(&app)?, &provider)?;
+    }
+
+    match ring {
+        Some(ring) => secret_store::save_keyring(&app, &ring),
+        None => Ok(()),
+    }
+}
+
+/// Checks that `base_url`, or the endpoint `provider` currently uses,
+/// answers. Does not save anything.
+#[tauri::command]
+pub async fn check_provider_endpoint(
     app: AppHandle,
     provider: String,
-    model: String,
-    api_key: Option<String>,
-) -> Result<(), String> {
+    base_url: Option<String>,
+) -> Result<EndpointCheck, String> {
     let provider_info =
-        provider_by_id(&provider).ok_or_else(|| format!("unknown provider: {provider}"))?;
+        provider_by_id(&app, &provider).ok_or_else(|| format!("unknown provider: {provider}"))?;
+    let base_url = match base_url.filter(|url| !url.trim().is_empty()) {
+        Some(url) => endpoints::normalize(&url)?,
+        None => provider_info
+            .base_url_override
+            .or(provider_info.base_url)
+            .ok_or_else(|| format!("provider {provider} has no fixed endpoint"))?,
+    };
+    tauri::async_runtime::spawn_blocking(move || endpoints::check(&provider, &base_url))
+        .await
+        .map_err(|e| format!("endpoint check task failed: {e}"))
+}
 
-    let model_valid = provider_info
-        .models
+/// Lists stored keys with masked previews, optionally only those of
+/// `provider`. Full values are never returned to the UI.
+#[tauri::command]
+pub fn list_api_keys(app: AppHandle, provider: Option<String>) -> Result<Vec<ApiKeyInfo>, String> {
+    let providers = providers(&app);
+    let wanted = match provider.as_deref() {
+        Some(id) => Some(
+            providers
+                .iter()
+                .find(|item| item.id == id)
+                .ok_or_else(|| format!("unknown provider: {id}"))?,
+        ),
+        None => None,
+    };
+    let uses = |item: &ProviderInfo, env_var: &str| {
+        item.credentials
+            .iter()
+            .any(|field| field.env_var == env_var)
+    };
+
+    Ok(secret_store::load_keyring(&app)?
+        .summaries()
+        .into_iter()
+        .filter_map(|key| {
+            let provider = match wanted {
+                Some(item) if uses(item, &key.env_var) => Some(item.id.clone()),
+                Some(_) => return None,
+                None => providers
+                    .iter()
+                    .find(|item| uses(item, &key.env_var))
+                    .map(|item| item.id.clone()),
+            };
+            Some(ApiKeyInfo { provider, key })
+        })
+        .collect())
+}
+
+/// Makes `label` the key handed to the gateway for the provider's primary
+/// key, or for `env_var` when another credential field is meant.
+#[tauri::command]
+pub fn select_api_key(
+    app: AppHandle,
+    provider: String,
+    label: String,
+    env_var: Option<String>,
+) -> Result<(), String> {
+    let env_var = key_env_var(&app, &provider, env_var)?;
+    let mut ring = secret_store::load_keyring(&app)?;
+    ring.select(&env_var, &label)?;
+    secret_store::save_keyring(&app, &ring)
+}
+
+/// Replaces the value behind an existing label, e.g. after the provider
+/// revoked it. Which label is selected does not change.
+#[tauri::command]
+pub fn rotate_api_key(
+    app: AppHandle,
+    provider: String,
+    label: String,
+    api_key: String,
+    env_var: Option<String>,
+) -> Result<(), String> {
+    let provider_info =
+        provider_by_id(&app, &provider).ok_or_else(|| format!("unknown provider: {provider}"))?;
+    let env_var = key_env_var(&app, &provider, env_var)?;
+    let value = api_key.trim().to_string();
+    if value.is_empty() {
+        return Err("new key value is empty".to_string());
+    }
+    if let Some(field) = provider_info
+        .credentials
         .iter()
-        .any(|candidate| candidate.id == model);
-    if !model_valid {
-        return Err(format!(
-            "model {model} is not valid for provider {provider}"
-        ));
+        .find(|field| field.env_var == env_var)
+    {
+        credentials::validate_value(field, &value)?;
     }
 
-    let config_path = common::openclaw_config_path()?;
-    if let Some(parent) = config_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("failed to create config dir {}: {e}", parent.display()))?;
-    }
+    let mut ring = secret_store::load_keyring(&app)?;
+    ring.rotate(&env_var, &label, value, &common::iso_utc_now())?;
+    secret_store::save_keyring(&app, &ring)
+}
 
-    let config_content = json!({
-        "agent": {
-            "model": model,
+/// Deletes a stored key. If it was selected, the next remaining key for the
+/// same env var becomes selected.
+#[tauri::command]
+pub fn delete_api_key(
+    app: AppHandle,
+    provider: String,
+    label: String,
+    env_var: Option<String>,
+) -> Result<(), String> {
+    let env_var = key_env_var(&app, &provider, env_var)?;
+    let mut ring = secret_store::load_keyring(&app)?;
+    ring.remove(&env_var, &label)?;
+    secret_store::save_keyring(&app, &ring)
+}
+
+/// Looks for provider credentials already set up outside the app: the
+/// process environment, `.env` files, and OpenClaw's config and auth
+/// profiles. Values are returned masked; copy them into the key store with
+/// `import_detected_credentials`.
+#[tauri::command]
+pub fn detect_existing_credentials(app: AppHandle) -> Result<Vec<DetectedCredential>, String> {
+    let providers = providers(&app);
+    let ring = secret_store::load_keyring(&app)?;
+    let places = detect::Places::current(&common::home_dir()?);
+    Ok(detect::scan(&places, &providers)
+        .iter()
+        .map(|found| detect::describe(found, &providers, &ring))
+        .collect())
+}
+
+/// Copies detected credentials, by id, into the key store. Each is filed
+/// under `label` if given; otherwise under `default` when its env var has no
+/// stored value yet and under its source name (`environment`, `dotenv`,
+/// `openclaw`) when it does. Values already stored are skipped, an existing
+/// label is never overwritten, and an imported key is only selected when its
+/// env var had none.
+#[tauri::command]
+pub fn import_detected_credentials(
+    app: AppHandle,
+    ids: Vec<String>,
+    label: Option<String>,
+) -> Result<Vec<ApiKeyInfo>, String> {
+    let providers = providers(&app);
+    let label = label
+        .filter(|label| !label.trim().is_empty())
+        .map(|label| secret_store::validate_label(&label))
+        .transpose()?;
+    let found = detect::scan(&detect::Places::current(&common::home_dir()?), &providers);
+
+    let mut ring = secret_store::load_keyring(&app)?;
+    let now = common::iso_utc_now();
+    let mut changed = false;
+    for id in ids {
+        let item = found
+            .iter()
+            .find(|item| item.id() == id)
+            .ok_or_else(|| format!("credential {id} is no longer present"))?;
+        if ring.contains_value(&item.env_var, &item.value) {
+            continue;
         }
-    });
-    let serialized = serde_json::to_string_pretty(&config_content)
-        .map_err(|e| format!("failed to serialize openclaw config: {e}"))?;
-    std::fs::write(&config_path, serialized)
-        .map_err(|e| format!("failed to write {}: {e}", config_path.display()))?;
-
-    if provider_info.requires_api_key {
-        let env_name = provider_info
-            .env_var
-            .clone()
-            .ok_or_else(|| format!("provider {provider} missing env var metadata"))?;
-
-        let app_data_dir = common::app_data_dir(&app)?;
-        let keys_path = common::keys_file_path(&app_data_dir);
-        if let Some(parent) = keys_path.parent() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("failed to create keys dir {}: {e}", parent.display()))?;
+        let field = providers
+            .iter()
+            .flat_map(|provider| provider.credentials.iter())
+            .find(|field| field.env_var == item.env_var);
+        if let Some(field) = field {
+            credentials::validate_value(field, &item.value)
+                .map_err(|e| format!("{}: {e}", item.location))?;
         }
 
-        let mut keys = load_api_keys(app)?;
-        if let Some(key_value) = api_key.and_then(|value| {
-            let trimmed = value.trim().to_string();
-            if trimmed.is_empty() {
-                None
+        let label = label.clone().unwrap_or_else(|| {
+            if ring.selected().contains_key(&item.env_var) {
+                item.source.to_string()
             } else {
-                Some(trimmed)
+                secret_store::DEFAULT_LABEL.to_string()
             }
-        }) {
-            keys.insert(env_name, key_value);
-            write_keys(&keys_path, &keys)?;
+        });
+        if ring.has_label(&item.env_var, &label) {
+            return Err(format!(
+                "{} already has a key labelled {label}; import it under another label",
+                item.env_var
+            ));
         }
+        ring.add(&item.env_var, &label, item.value.clone(), &now);
+        changed = true;
+    }
+
+    if changed {
+        secret_store::save_keyring(&app, &ring)?;
     }
+    list_api_keys(app, None)
+}
 
-    Ok(())
+#[tauri::command]
+pub fn list_llm_profiles(app: AppHandle) -> Result<Vec<LlmProfile>, String> {
+    common::get_llm_profiles(&app)
 }
 
+/// Adds a profile, or replaces the saved profile with the same name. The
+/// configured model is not changed until the profile is activated.
 #[tauri::command]
-pub fn load_api_keys(app: AppHandle) -> Result<HashMap<String, String>, String> {
-    let app_data_dir = common::app_data_dir(&app)?;
-    let keys_path = common::keys_file_path(&app_data_dir);
-    if !keys_path.exists() {
-        return Ok(HashMap::new());
-    }
-
-    let raw = std::fs::read_to_string(&keys_path)
-        .map_err(|e| format!("failed to read {}: {e}", keys_path.display()))?;
-
-    serde_json::from_str(&raw)
-        .map_err(|e| format!("invalid keys json in {}: {e}", keys_path.display()))
-}
-
-fn providers() -> Vec<ProviderInfo> {
-    vec![
-        ProviderInfo {
-            id: "anthropic".to_string(),
-            display_name: "Anthropic (Claude)".to_string(),
-            requires_api_key: true,
-            env_var: Some("ANTHROPIC_API_KEY".to_string()),
-            models: vec![
-                ModelInfo {
-                    id: "anthropic/claude-sonnet-4-5-20250929".to_string(),
-                    display_name: "Claude Sonnet 4.5".to_string(),
-                    is_default: true,
-                },
-                ModelInfo {
-                    id: "anthropic/claude-opus-4-6".to_string(),
-                    display_name: "Claude Opus 4.6".to_string(),
-                    is_default: false,
-                },
-            ],
-        },
-        ProviderInfo {
-            id: "openai".to_string(),
-            display_name: "OpenAI".to_string(),
-            requires_api_key: true,
-            env_var: Some("OPENAI_API_KEY".to_string()),
-            models: vec![
-                ModelInfo {
-                    id: "openai/gpt-4o".to_string(),
-                    display_name: "GPT-4o".to_string(),
-                    is_default: true,
-                },
-                ModelInfo {
-                    id: "openai/gpt-4o-mini".to_string(),
-                    display_name: "GPT-4o Mini".to_string(),
-                    is_default: false,
-                },
-            ],
-        },
-        ProviderInfo {
-            id: "ollama".to_string(),
-            display_name: "Ollama".to_string(),
-            requires_api_key: false,
-            env_var: None,
-            models: vec![
-                ModelInfo {
-                    id: "ollama/llama3.2".to_string(),
-                    display_name: "Llama 3.2".to_string(),
-                    is_default: true,
-                },
-                ModelInfo {
-                    id: "ollama/mistral".to_string(),
-                    display_name: "Mistral".to_string(),
-                    is_default: false,
-                },
-            ],
-        },
-    ]
-}
-
-fn provider_by_id(provider: &str) -> Option<ProviderInfo> {
-    providers().into_iter().find(|item| item.id == provider)
-}
-
-fn read_selected_model() -> Result<Option<String>, String> {
-    let path = common::openclaw_config_path()?;
-    if !path.exists() {
+pub fn save_llm_profile(app: AppHandle, profile: LlmProfile) -> Result<LlmProfile, String> {
+    let profile = profiles::normalize(profile, &providers(&app))?;
+    profile_key(&app, &profile)?;
+
+    let mut saved = common::get_llm_profiles(&app)?;
+    match saved
+        .iter_mut()
+        .find(|existing| existing.name == profile.name)
+    {
+        Some(existing) => *existing = profile.clone(),
+        None => saved.push(profile.clone()),
+    }
+    common::set_llm_profiles(&app, saved)?;
+    Ok(profile)
+}
+
+/// Writes the profile's model chain to openclaw.json. The gateway is not
+/// restarted; `gateway_running` tells the UI to offer `restart_gateway`.
+#[tauri::command]
+pub fn activate_llm_profile(app: AppHandle, name: String) -> Result<ProfileActivation, String> {
+    let profile = common::get_llm_profiles(&app)?
+        .into_iter()
+        .find(|existing| existing.name == name)
+        .ok_or_else(|| format!("no profile named {name}"))?;
+    profiles::check(&profile, &providers(&app))?;
+    if let (Some(env_var), Some(label)) = (profile_key(&app, &profile)?, &profile.key_label) {
+        let mut ring = secret_store::load_keyring(&app)?;
+        ring.select(&env_var, label)?;
+        secret_store::save_keyring(&app, &ring)?;
+    }
+
+    write_model_chain(
+        &app,
+        &profile.chain(),
+        &format!("profile {} activated", profile.name),
+    )?;
+    Ok(ProfileActivation {
+        profile,
+        state: get_llm_config_state(app)?,
+        gateway_running: service_manager::is_gateway_running(),
+    })
+}
+
+/// Deletes a saved profile. The configured models are left as they are.
+#[tauri::command]
+pub fn delete_llm_profile(app: AppHandle, name: String) -> Result<(), String> {
+    let mut saved = common::get_llm_profiles(&app)?;
+    let before = saved.len();
+    saved.retain(|existing| existing.name != name);
+    if saved.len() == before {
+        return Err(format!("no profile named {name}"));
+    }
+    common::set_llm_profiles(&app, saved)
+}
+
+/// The env var a profile's `key_label` refers to, if it names a stored key.
+fn profile_key(app: &AppHandle, profile: &LlmProfile) -> Result<Option<String>, String> {
+    let Some(label) = profile.key_label.as_deref() else {
         return Ok(None);
+    };
+    let provider = provider_by_id(app, &profile.provider)
+        .ok_or_else(|| format!("unknown provider: {}", profile.provider))?;
+    let env_var = primary_env_var(&provider)?;
+    if !secret_store::load_keyring(app)?.has_label(&env_var, label) {
+        return Err(format!(
+            "profile {}: no stored {} key labelled {label}",
+            profile.name, profile.provider
+        ));
+    }
+    Ok(Some(env_var))
+}
+
+/// Checks credentials against the provider. Entered values take precedence
+/// over stored ones, so a blank `api_key` re-checks the stored key.
+#[tauri::command]
+pub async fn validate_api_key(
+    app: AppHandle,
+    provider: String,
+    api_key: String,
+    model: Option<String>,
+    base_url: Option<String>,
+    credentials: Option<HashMap<String, String>>,
+) -> Result<ApiKeyValidation, String> {
+    let provider_info =
+        provider_by_id(&app, &provider).ok_or_else(|| format!("unknown provider: {provider}"))?;
+    let mut values = if secret_store::is_locked(&app)? {
+        HashMap::new()
+    } else {
+        secret_store::load_secrets(&app)?
+    };
+    values.retain(|name, _| {
+        provider_info
+            .credentials
+            .iter()
+            .any(|field| &field.env_var == name)
+    });
+    values.extend(entered_credentials(
+        &provider_info,
+        Some(api_key),
+        credentials.unwrap_or_default(),
+    )?);
+    for field in &provider_info.credentials {
+        if let Some(default) = &field.default {
+            values
+                .entry(field.env_var.clone())
+                .or_insert_with(|| default.clone());
+        }
+    }
+
+    let missing = credentials::missing(&provider_info.credentials, &values);
+    if provider_info.requires_api_key && !missing.is_empty() {
+        return Ok(ApiKeyValidation {
+            provider,
+            status: "invalid_key".to_string(),
+            detail: format!("missing {}", missing.join(", ")),
+            http_status: None,
+        });
+    }
+    let api_key = provider_info
+        .env_var
+        .as_ref()
+        .and_then(|name| values.get(name).cloned())
+        .unwrap_or_default();
+
+    let model = model
+        .or_else(|| {
+            provider_info
+                .models
+                .iter()
+                .find(|candidate| candidate.is_default)
+                .map(|candidate| candidate.id.clone())
+        })
+        .map(|id| bare_model_id(&provider, &id));
+    let base_url = base_url
+        .filter(|value| !value.trim().is_empty())
+        .or(provider_info.base_url_override.clone())
+        .or(provider_info.base_url.clone())
+        .or_else(|| {
+            provider_info
+                .credentials
+                .iter()
+                .filter(|field| field.kind == credentials::KIND_URL)
+                .find_map(|field| values.get(&field.env_var).cloned())
+        })
+        .or_else(|| validation::default_base_url(&provider).map(str::to_string))
+        .ok_or_else(|| {
+            format!("no endpoint known for provider {provider}; pass a base_url to check against")
+        })?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        validation::validate(&provider, &api_key, model.as_deref(), &base_url, &values)
+    })
+    .await
+    .map_err(|e| format!("api key validation task failed: {e}"))
+}
+
+fn providers(app: &AppHandle) -> Vec<ProviderInfo> {
+    let mut providers = catalog::load(app).providers;
+    match common::get_custom_providers(app) {
+        Ok(custom) => providers.extend(
+            custom
+                .iter()
+                .filter(|provider| !providers.iter().any(|known| known.id == provider.id))
+                .map(custom::provider_info)
+                .collect::<Vec<_>>(),
+        ),
+        Err(err) => log::warn!("failed to read custom providers: {err}"),
     }
+    let base_urls = common::get_provider_base_urls(app).unwrap_or_else(|err| {
+        log::warn!("failed to read provider endpoints: {err}");
+        Default::default()
+    });
+    for provider in providers.iter_mut().filter(|provider| !provider.custom) {
+        if provider.base_url.is_none() {
+            provider.base_url = validation::default_base_url(&provider.id).map(str::to_string);
+        }
+        provider.base_url_override = base_urls.get(&provider.id).cloned();
+    }
+    if let Ok(app_data_dir) = common::app_data_dir(app) {
+        discovery::merge_cached(&app_data_dir, &mut providers);
+    }
+    providers
+}
 
-    let raw = std::fs::read_to_string(&path)
-        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
-    let parsed: serde_json::Value = serde_json::from_str(&raw)
-        .map_err(|e| format!("invalid json in {}: {e}", path.display()))?;
+/// Where `provider` is reached: the saved override, else its catalog or
+/// well-known base URL.
+pub fn provider_base_url(app: &AppHandle, provider: &str) -> Option<String> {
+    provider_by_id(app, provider)
+        .and_then(|info| info.base_url_override.or(info.base_url))
+        .or_else(|| validation::default_base_url(provider).map(str::to_string))
+}
 
-    Ok(parsed
-        .get("agent")
-        .and_then(|agent| agent.get("model"))
-        .and_then(|model| model.as_str())
-        .map(|value| value.to_string()))
+/// Normalises `url` as an endpoint override for `provider`. Custom providers
+/// carry their own URL and Bedrock and Azure take theirs from credentials,
+/// so only built-in providers with a fixed endpoint can be overridden.
+fn override_base_url(provider: &ProviderInfo, url: &str) -> Result<String, String> {
+    if provider.custom {
+        return Err(format!(
+            "{} is a custom provider; change its URL with save_custom_provider",
+            provider.id
+        ));
+    }
+    if provider.base_url.is_none() {
+        return Err(format!(
+            "provider {} has no fixed endpoint to override",
+            provider.id
+        ));
+    }
+    if url.is_empty() {
+        return Ok(String::new());
+    }
+    endpoints::normalize(url)
 }
 
-fn write_keys(path: &std::path::Path, keys: &HashMap<String, String>) -> Result<(), String> {
-    let serialized =
-        serde_json::to_string_pretty(keys).map_err(|e| format!("failed to serialize keys: {e}"))?;
-    std::fs::write(path, serialized).map_err(|e| format!("failed to write {}: {e}", path.display()))
+/// Lists `provider`'s models again and caches them for `list_providers`,
+/// for providers that need no API key to list (Ollama). Blocks on the
+/// network.
+pub fn rediscover_models(
+    app_data_dir: &Path,
+    provider: &str,
+    base_url: &str,
+) -> Result<(), String> {
+    let models = discovery::fetch_models(provider, None, base_url)?;
+    discovery::store_models(app_data_dir, provider, &models)
+}
+
+/// Whether the provider of the configured primary model needs stored
+/// credentials. `false` when no model is configured or the config cannot
+/// be read.
+pub fn configured_provider_needs_key(app: &AppHandle) -> bool {
+    let chain = read_model_chain().unwrap_or_default();
+    let Some(provider) = chain.first().and_then(|model| model.split('/').next()) else {
+        return false;
+    };
+    provider_by_id(app, provider)
+        .map(|info| info.requires_api_key && !info.credentials.is_empty())
+        .unwrap_or(false)
+}
+
+fn provider_by_id(app: &AppHandle, provider: &str) -> Option<ProviderInfo> {
+    providers(app).into_iter().find(|item| item.id == provider)
+}
+
+fn primary_env_var(provider: &ProviderInfo) -> Result<String, String> {
+    provider
+        .env_var
+        .clone()
+        .ok_or_else(|| format!("provider {} has no single API key field", provider.id))
+}
+
+/// `env_var` when it is one of the provider's credential fields, otherwise
+/// the provider's primary key field.
+fn key_env_var(app: &AppHandle, provider: &str, env_var: Option<String>) -> Result<String, String> {
+    let provider_info =
+        provider_by_id(app, provider).ok_or_else(|| format!("unknown provider: {provider}"))?;
+    match env_var {
+        Some(name) if provider_info.credentials.iter().any(|f| f.env_var == name) => Ok(name),
+        Some(name) => Err(format!("{name} is not a credential of provider {provider}")),
+        None => primary_env_var(&provider_info),
+    }
+}
+
+/// Collects non-blank entered values by env var name, with `api_key` filed
+/// under the provider's primary `env_var`. Names the provider does not
+/// declare and badly formatted values are rejected.
+fn entered_credentials(
+    provider: &ProviderInfo,
+    api_key: Option<String>,
+    mut values: HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    if let (Some(key), Some(env_var)) = (api_key, provider.env_var.as_ref()) {
+        values.insert(env_var.clone(), key);
+    }
+
+    let mut entered = HashMap::new();
+    for (name, value) in values {
+        let value = value.trim().to_string();
+        if value.is_empty() {
+            continue;
+        }
+        let field = provider
+            .credentials
+            .iter()
+            .find(|field| field.env_var == name)
+            .ok_or_else(|| format!("{name} is not a credential of provider {}", provider.id))?;
+        credentials::validate_value(field, &value)?;
+        entered.insert(name, value);
+    }
+    Ok(entered)
+}
+
+/// Strips the `provider/` prefix used in OpenClaw model ids.
+fn bare_model_id(provider: &str, model: &str) -> String {
+    model
+        .strip_prefix(provider)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .unwrap_or(model)
+        .to_string()
+}
+
+fn read_model_chain() -> Result<Vec<String>, String> {
+    Ok(openclaw_config::read_config()?
+        .map(|parsed| fallback::chain_from_config(&parsed))
+        .unwrap_or_default())
+}
+
+fn write_model_chain(app: &AppHandle, chain: &[String], reason: &str) -> Result<(), String> {
+    let value = fallback::model_value(chain);
+    openclaw_config::edit_config(app, reason, |document| {
+        document.set(&["agent", "model"], &value)
+    })
 }
 
 #[cfg(test)]
@@ -237,11 +1065,46 @@ mod tests {
     use super::*;
 
     #[test]
-    fn provider_ids_are_unique() {
-        let providers = providers();
-        let mut ids = providers.iter().map(|p| p.id.clone()).collect::<Vec<_>>();
-        ids.sort();
-        ids.dedup();
-        assert_eq!(ids.len(), providers.len());
+    fn bare_model_id_strips_provider_prefix() {
+        assert_eq!(
+            bare_model_id("anthropic", "anthropic/claude-opus-4-6"),
+            "claude-opus-4-6"
+        );
+        assert_eq!(bare_model_id("openai", "gpt-4o"), "gpt-4o");
+    }
+
+    #[test]
+    fn entered_credentials_are_checked_against_the_provider() {
+        let mut provider =
+            ProviderInfo::for_test("azure-openai", Some("AZURE_OPENAI_API_KEY"), &[]);
+        provider.credentials.push(CredentialField {
+            label: "Endpoint".to_string(),
+            kind: credentials::KIND_URL.to_string(),
+            ..credentials::api_key_field("AZURE_OPENAI_ENDPOINT")
+        });
+
+        let entered = entered_credentials(
+            &provider,
+            Some(" az-key ".to_string()),
+            test_values(&[("AZURE_OPENAI_ENDPOINT", "https://acme.openai.azure.com")]),
+        )
+        .expect("valid credentials");
+        assert_eq!(entered["AZURE_OPENAI_API_KEY"], "az-key");
+        assert_eq!(entered.len(), 2);
+
+        assert!(
+            entered_credentials(&provider, Some(String::new()), HashMap::new())
+                .expect("blank values are skipped")
+                .is_empty()
+        );
+        assert!(
+            entered_credentials(&provider, None, test_values(&[("OPENAI_API_KEY", "sk")])).is_err()
+        );
+        assert!(entered_credentials(
+            &provider,
+            None,
+            test_values(&[("AZURE_OPENAI_ENDPOINT", "acme.openai.azure.com")])
+        )
+        .is_err());
     }
 }
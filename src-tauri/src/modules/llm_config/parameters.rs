@@ -0,0 +1,330 @@
+//! Generation parameters a model accepts (thinking level, temperature, token
+//! limits). The catalog declares which ones each provider or model takes and
+//! their ranges; chosen values are written to `agent.models.<id>.params` in
+//! openclaw.json.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+use super::{ModelInfo, ProviderInfo};
+
+pub const THINKING: &str = "thinking";
+pub const TEMPERATURE: &str = "temperature";
+pub const MAX_TOKENS: &str = "max_tokens";
+pub const CONTEXT_WINDOW: &str = "context_window";
+const NAMES: [&str; 4] = [THINKING, TEMPERATURE, MAX_TOKENS, CONTEXT_WINDOW];
+pub const THINKING_LEVELS: [&str; 5] = ["off", "minimal", "low", "medium", "high"];
+
+/// One parameter a model accepts, as declared in a catalog.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelParameter {
+    /// `thinking`, `temperature`, `max_tokens` or `context_window`.
+    pub name: String,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Thinking levels offered; every level when omitted.
+    #[serde(default)]
+    pub values: Option<Vec<String>>,
+}
+
+/// Values chosen for one model. `None` leaves the parameter to OpenClaw.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelParameters {
+    #[serde(default)]
+    pub thinking: Option<String>,
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+    #[serde(default)]
+    pub context_window: Option<u64>,
+}
+
+impl ModelParameters {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// The parameters `model` accepts: its own list when the catalog gives one,
+/// otherwise its provider's.
+pub fn accepted<'a>(provider: &'a ProviderInfo, model: &'a ModelInfo) -> &'a [ModelParameter] {
+    model.parameters.as_deref().unwrap_or(&provider.parameters)
+}
+
+/// Checks a parameter list as declared in a catalog.
+pub fn validate_specs(specs: &[ModelParameter]) -> Result<(), String> {
+    for (index, spec) in specs.iter().enumerate() {
+        let at = format!("parameters[{index}]");
+        if !NAMES.contains(&spec.name.as_str()) {
+            return Err(format!(
+                "{at}.name: {:?} is not one of {}",
+                spec.name,
+                NAMES.join(", ")
+            ));
+        }
+        if specs[..index].iter().any(|other| other.name == spec.name) {
+            return Err(format!("{at}.name: duplicate {}", spec.name));
+        }
+
+        if spec.name == THINKING {
+            if spec.min.is_some() || spec.max.is_some() {
+                return Err(format!("{at}: thinking takes values, not min or max"));
+            }
+            if let Some(values) = &spec.values {
+                if values.is_empty() {
+                    return Err(format!("{at}.values: must not be empty"));
+                }
+                if let Some(level) = values
+                    .iter()
+                    .find(|level| !THINKING_LEVELS.contains(&level.as_str()))
+                {
+                    return Err(format!(
+                        "{at}.values: {level:?} is not one of {}",
+                        THINKING_LEVELS.join(", ")
+                    ));
+                }
+            }
+            continue;
+        }
+
+        if spec.values.is_some() {
+            return Err(format!("{at}.values: only thinking takes values"));
+        }
+        let integer = spec.name != TEMPERATURE;
+        for (field, bound) in [("min", spec.min), ("max", spec.max)] {
+            match bound {
+                Some(bound) if !bound.is_finite() || bound < 0.0 => {
+                    return Err(format!("{at}.{field}: must be a non-negative number"));
+                }
+                Some(bound) if integer && (bound < 1.0 || bound.fract() != 0.0) => {
+                    return Err(format!("{at}.{field}: must be a positive whole number"));
+                }
+                _ => {}
+            }
+        }
+        if let (Some(min), Some(max)) = (spec.min, spec.max) {
+            if min > max {
+                return Err(format!("{at}.min: {min} is above max {max}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks chosen `values` against the parameters `model_id` accepts.
+pub fn check(
+    values: &ModelParameters,
+    specs: &[ModelParameter],
+    model_id: &str,
+) -> Result<(), String> {
+    let spec = |name: &str| {
+        specs
+            .iter()
+            .find(|spec| spec.name == name)
+            .ok_or_else(|| format!("{model_id} does not accept {name}"))
+    };
+
+    if let Some(level) = values.thinking.as_deref() {
+        let allowed = spec(THINKING)?
+            .values
+            .clone()
+            .unwrap_or_else(|| THINKING_LEVELS.iter().map(|l| l.to_string()).collect());
+        if !allowed.iter().any(|allowed| allowed == level) {
+            return Err(format!(
+                "thinking for {model_id} must be one of {}",
+                allowed.join(", ")
+            ));
+        }
+    }
+    for (name, value) in [
+        (TEMPERATURE, values.temperature),
+        (MAX_TOKENS, values.max_tokens.map(|v| v as f64)),
+        (CONTEXT_WINDOW, values.context_window.map(|v| v as f64)),
+    ] {
+        let Some(value) = value else {
+            continue;
+        };
+        let spec = spec(name)?;
+        let floor = if name == TEMPERATURE { 0.0 } else { 1.0 };
+        let min = spec.min.unwrap_or(floor).max(floor);
+        if !value.is_finite() || value < min || spec.max.is_some_and(|max| value > max) {
+            let range = match spec.max {
+                Some(max) => format!("between {min} and {max}"),
+                None => format!("at least {min}"),
+            };
+            return Err(format!("{name} for {model_id} must be {range}"));
+        }
+    }
+    Ok(())
+}
+
+/// The `params` object written for `values`, or `None` when nothing is set.
+pub fn config_value(values: &ModelParameters) -> Option<Value> {
+    let mut params = Map::new();
+    if let Some(thinking) = &values.thinking {
+        params.insert("thinking".to_string(), json!(thinking));
+    }
+    if let Some(temperature) = values.temperature {
+        params.insert("temperature".to_string(), json!(temperature));
+    }
+    if let Some(max_tokens) = values.max_tokens {
+        params.insert("maxTokens".to_string(), json!(max_tokens));
+    }
+    if let Some(context_window) = values.context_window {
+        params.insert("contextWindow".to_string(), json!(context_window));
+    }
+    (!params.is_empty()).then_some(Value::Object(params))
+}
+
+/// Parameters configured per model under `agent.models`. Values of the
+/// wrong type are ignored.
+pub fn from_config(config: &Value) -> HashMap<String, ModelParameters> {
+    let Some(models) = config.pointer("/agent/models").and_then(Value::as_object) else {
+        return HashMap::new();
+    };
+    models
+        .iter()
+        .filter_map(|(model, entry)| {
+            let params = entry.get("params")?;
+            let values = ModelParameters {
+                thinking: params
+                    .get("thinking")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                temperature: params.get("temperature").and_then(Value::as_f64),
+                max_tokens: params.get("maxTokens").and_then(Value::as_u64),
+                context_window: params.get("contextWindow").and_then(Value::as_u64),
+            };
+            (!values.is_empty()).then(|| (model.clone(), values))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(name: &str, min: Option<f64>, max: Option<f64>) -> ModelParameter {
+        ModelParameter {
+            name: name.to_string(),
+            min,
+            max,
+            values: None,
+        }
+    }
+
+    #[test]
+    fn catalog_specs_are_validated_with_a_path() {
+        let valid = [
+            ModelParameter {
+                values: Some(vec!["off".to_string(), "high".to_string()]),
+                ..spec(THINKING, None, None)
+            },
+            spec(TEMPERATURE, Some(0.0), Some(1.0)),
+            spec(MAX_TOKENS, Some(1.0), Some(64000.0)),
+        ];
+        validate_specs(&valid).expect("valid specs");
+
+        let cases = [
+            (vec![spec("top_p", None, None)], "parameters[0].name"),
+            (
+                vec![spec(TEMPERATURE, None, None), spec(TEMPERATURE, None, None)],
+                "parameters[1].name: duplicate",
+            ),
+            (
+                vec![spec(TEMPERATURE, Some(2.0), Some(1.0))],
+                "parameters[0].min",
+            ),
+            (vec![spec(MAX_TOKENS, Some(0.5), None)], "parameters[0].min"),
+            (
+                vec![spec(THINKING, Some(0.0), None)],
+                "parameters[0]: thinking",
+            ),
+            (
+                vec![ModelParameter {
+                    values: Some(vec!["extreme".to_string()]),
+                    ..spec(THINKING, None, None)
+                }],
+                "parameters[0].values",
+            ),
+        ];
+        for (specs, expected) in cases {
+            let err = validate_specs(&specs).expect_err(expected);
+            assert!(err.contains(expected), "{err} should mention {expected}");
+        }
+    }
+
+    #[test]
+    fn values_must_be_accepted_and_in_range() {
+        let specs = [
+            ModelParameter {
+                values: Some(vec!["off".to_string(), "low".to_string()]),
+                ..spec(THINKING, None, None)
+            },
+            spec(TEMPERATURE, Some(0.0), Some(1.0)),
+            spec(MAX_TOKENS, None, Some(8192.0)),
+        ];
+        let values = ModelParameters {
+            thinking: Some("low".to_string()),
+            temperature: Some(0.7),
+            max_tokens: Some(4096),
+            context_window: None,
+        };
+        check(&values, &specs, "acme/model").expect("values in range");
+
+        let err = |values: ModelParameters| check(&values, &specs, "acme/model").unwrap_err();
+        assert_eq!(
+            err(ModelParameters {
+                temperature: Some(1.5),
+                ..Default::default()
+            }),
+            "temperature for acme/model must be between 0 and 1"
+        );
+        assert_eq!(
+            err(ModelParameters {
+                max_tokens: Some(0),
+                ..Default::default()
+            }),
+            "max_tokens for acme/model must be between 1 and 8192"
+        );
+        assert_eq!(
+            err(ModelParameters {
+                context_window: Some(32000),
+                ..Default::default()
+            }),
+            "acme/model does not accept context_window"
+        );
+        assert!(err(ModelParameters {
+            thinking: Some("high".to_string()),
+            ..Default::default()
+        })
+        .contains("off, low"));
+    }
+
+    #[test]
+    fn values_round_trip_through_the_config() {
+        let values = ModelParameters {
+            thinking: Some("medium".to_string()),
+            temperature: Some(0.2),
+            max_tokens: Some(2048),
+            context_window: Some(32768),
+        };
+        let params = config_value(&values).expect("params");
+        assert_eq!(params["maxTokens"], 2048);
+        assert_eq!(params["contextWindow"], 32768);
+        assert_eq!(config_value(&ModelParameters::default()), None);
+
+        let config = json!({ "agent": { "models": {
+            "ollama/llama3.2": { "params": params },
+            "openai/gpt-4o": { "alias": "4o" }
+        } } });
+        let read = from_config(&config);
+        assert_eq!(read.len(), 1);
+        assert_eq!(read["ollama/llama3.2"], values);
+    }
+}
@@ -0,0 +1,129 @@
+use super::{fallback, ProviderInfo};
+use crate::modules::common::LlmProfile;
+
+const MAX_NAME_LEN: usize = 64;
+
+impl LlmProfile {
+    /// Primary model followed by the fallbacks, as written to `agent.model`.
+    pub fn chain(&self) -> Vec<String> {
+        let mut chain = vec![self.model.clone()];
+        chain.extend(self.fallbacks.iter().cloned());
+        chain
+    }
+}
+
+/// Trims the profile and checks it against the current provider list.
+pub fn normalize(profile: LlmProfile, providers: &[ProviderInfo]) -> Result<LlmProfile, String> {
+    let name = profile.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(format!(
+            "profile name must be 1 to {MAX_NAME_LEN} characters"
+        ));
+    }
+
+    let profile = LlmProfile {
+        name,
+        provider: profile.provider.trim().to_string(),
+        model: profile.model.trim().to_string(),
+        fallbacks: profile
+            .fallbacks
+            .into_iter()
+            .map(|model| model.trim().to_string())
+            .filter(|model| !model.is_empty())
+            .collect(),
+        key_label: profile
+            .key_label
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty()),
+    };
+    check(&profile, providers)?;
+    Ok(profile)
+}
+
+/// Checks the provider, model and fallbacks still exist. Run again at
+/// activation because catalogs and custom providers change.
+pub fn check(profile: &LlmProfile, providers: &[ProviderInfo]) -> Result<(), String> {
+    let provider = providers
+        .iter()
+        .find(|item| item.id == profile.provider)
+        .ok_or_else(|| {
+            format!(
+                "profile {}: unknown provider {}",
+                profile.name, profile.provider
+            )
+        })?;
+    if !provider
+        .models
+        .iter()
+        .any(|model| model.id == profile.model)
+    {
+        return Err(format!(
+            "profile {}: model {} is not valid for provider {}",
+            profile.name, profile.model, profile.provider
+        ));
+    }
+    fallback::check_fallbacks(&profile.model, &profile.fallbacks, providers)
+        .map_err(|e| format!("profile {}: {e}", profile.name))
+}
+
+/// The profile whose model chain is the one currently configured.
+pub fn active<'a>(profiles: &'a [LlmProfile], chain: &[String]) -> Option<&'a LlmProfile> {
+    if chain.is_empty() {
+        return None;
+    }
+    profiles.iter().find(|profile| profile.chain() == chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn providers() -> Vec<ProviderInfo> {
+        vec![
+            ProviderInfo::for_test("anthropic", None, &["anthropic/claude-opus-4-6"]),
+            ProviderInfo::for_test("ollama", None, &["ollama/llama3.2"]),
+        ]
+    }
+
+    fn opus() -> LlmProfile {
+        LlmProfile {
+            name: " Opus for real work ".to_string(),
+            provider: "anthropic".to_string(),
+            model: "anthropic/claude-opus-4-6".to_string(),
+            fallbacks: vec!["ollama/llama3.2".to_string(), " ".to_string()],
+            key_label: Some(" ".to_string()),
+        }
+    }
+
+    #[test]
+    fn profiles_are_normalized_and_checked() {
+        let profile = normalize(opus(), &providers()).expect("valid profile");
+        assert_eq!(profile.name, "Opus for real work");
+        assert_eq!(profile.fallbacks, vec!["ollama/llama3.2"]);
+        assert_eq!(profile.key_label, None);
+
+        let wrong_model = LlmProfile {
+            model: "ollama/llama3.2".to_string(),
+            ..opus()
+        };
+        assert!(normalize(wrong_model, &providers()).is_err());
+        let unnamed = LlmProfile {
+            name: "  ".to_string(),
+            ..opus()
+        };
+        assert!(normalize(unnamed, &providers()).is_err());
+    }
+
+    #[test]
+    fn active_profile_matches_the_whole_chain() {
+        let profile = normalize(opus(), &providers()).expect("valid profile");
+        let profiles = vec![profile.clone()];
+
+        assert_eq!(active(&profiles, &profile.chain()), Some(&profile));
+        assert_eq!(
+            active(&profiles, &["anthropic/claude-opus-4-6".to_string()]),
+            None
+        );
+        assert_eq!(active(&profiles, &[]), None);
+    }
+}
@@ -0,0 +1,565 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::modules::common;
+
+const VALIDATION_TIMEOUT: Duration = Duration::from_secs(15);
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const AZURE_API_VERSION: &str = "2024-10-21";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyValidation {
+    pub provider: String,
+    pub status: String,
+    pub detail: String,
+    pub http_status: Option<u16>,
+}
+
+impl ApiKeyValidation {
+    fn new(
+        provider: &str,
+        status: &str,
+        detail: impl Into<String>,
+        http_status: Option<u16>,
+    ) -> Self {
+        Self {
+            provider: provider.to_string(),
+            status: status.to_string(),
+            detail: detail.into(),
+            http_status,
+        }
+    }
+}
+
+pub fn default_base_url(provider: &str) -> Option<&'static str> {
+    match provider {
+        "anthropic" => Some("https://api.anthropic.com"),
+        "openai" => Some("https://api.openai.com"),
+        "ollama" => Some("http://127.0.0.1:11434"),
+        "google" => Some("https://generativelanguage.googleapis.com"),
+        "openrouter" => Some("https://openrouter.ai/api"),
+        _ => None,
+    }
+}
+
+/// Runs the provider-specific credential check. `model` is the bare model
+/// name without the `provider/` prefix and `credentials` holds the
+/// provider's other fields by env var name.
+pub fn validate(
+    provider: &str,
+    api_key: &str,
+    model: Option<&str>,
+    base_url: &str,
+    credentials: &HashMap<String, String>,
+) -> ApiKeyValidation {
+    let base_url = base_url.trim_end_matches('/');
+    match provider {
+        "anthropic" => validate_anthropic(api_key, model, base_url),
+        "ollama" => validate_ollama(model, base_url),
+        "google" => validate_google(api_key, model, base_url),
+        "openrouter" => validate_openrouter(api_key, base_url),
+        "azure-openai" => validate_azure(api_key, base_url, credentials),
+        // Bedrock calls need SigV4 request signing, which curl alone cannot
+        // reproduce for every credential shape (profiles, SSO, session tokens).
+        "amazon-bedrock" => ApiKeyValidation::new(
+            provider,
+            "unsupported",
+            "AWS credentials cannot be checked here; the gateway uses them at start-up",
+            None,
+        ),
+        // OpenAI itself and custom OpenAI-compatible endpoints.
+        other => validate_openai(other, api_key, model, base_url),
+    }
+}
+
+fn validate_anthropic(api_key: &str, model: Option<&str>, base_url: &str) -> ApiKeyValidation {
+    let Some(model) = model else {
+        return ApiKeyValidation::new(
+            "anthropic",
+            "model_unavailable",
+            "a model is required to validate an Anthropic key",
+            None,
+        );
+    };
+    let body = json!({
+        "model": model,
+        "max_tokens": 1,
+        "messages": [{ "role": "user", "content": "ping" }],
+    })
+    .to_string();
+
+    let response = common::http_request(
+        "POST",
+        &format!("{base_url}/v1/messages"),
+        &[
+            ("x-api-key", api_key),
+            ("anthropic-version", ANTHROPIC_VERSION),
+            ("content-type", "application/json"),
+        ],
+        Some(&body),
+        VALIDATION_TIMEOUT,
+    );
+    let response = match response {
+        Ok(response) => response,
+        Err(err) => return ApiKeyValidation::new("anthropic", "network_error", err, None),
+    };
+
+    let message = error_message(&response.body);
+    let lowered = message.to_lowercase();
+    let status = match response.status {
+        200..=299 => "valid",
+        401 | 403 => "invalid_key",
+        429 => "quota_exceeded",
+        404 => "model_unavailable",
+        400 if lowered.contains("credit balance") || lowered.contains("billing") => {
+            "quota_exceeded"
+        }
+        400 if lowered.contains("model") => "model_unavailable",
+        _ => "unexpected_response",
+    };
+    let detail = if status == "valid" {
+        format!("Key accepted for {model}")
+    } else {
+        message
+    };
+    ApiKeyValidation::new("anthropic", status, detail, Some(response.status))
+}
+
+fn validate_openai(
+    provider: &str,
+    api_key: &str,
+    model: Option<&str>,
+    base_url: &str,
+) -> ApiKeyValidation {
+    let authorization = format!("Bearer {api_key}");
+    let response = common::http_request(
+        "GET",
+        &format!("{base_url}/v1/models"),
+        &[("authorization", &authorization)],
+        None,
+        VALIDATION_TIMEOUT,
+    );
+    let response = match response {
+        Ok(response) => response,
+        Err(err) => return ApiKeyValidation::new(provider, "network_error", err, None),
+    };
+
+    match response.status {
+        200..=299 => {
+            let available = listed_ids(&response.body, "data", "id");
+            match model {
+                Some(model) if !available.iter().any(|id| id == model) => ApiKeyValidation::new(
+                    provider,
+                    "model_unavailable",
+                    format!("Key is valid but {model} is not available to it"),
+                    Some(response.status),
+                ),
+                _ => ApiKeyValidation::new(
+                    provider,
+                    "valid",
+                    format!("Key accepted ({} models available)", available.len()),
+                    Some(response.status),
+                ),
+            }
+        }
+        401 | 403 => ApiKeyValidation::new(
+            provider,
+            "invalid_key",
+            error_message(&response.body),
+            Some(response.status),
+        ),
+        429 => ApiKeyValidation::new(
+            provider,
+            "quota_exceeded",
+            error_message(&response.body),
+            Some(response.status),
+        ),
+        _ => ApiKeyValidation::new(
+            provider,
+            "unexpected_response",
+            error_message(&response.body),
+            Some(response.status),
+        ),
+    }
+}
+
+fn validate_google(api_key: &str, model: Option<&str>, base_url: &str) -> ApiKeyValidation {
+    let response = common::http_request(
+        "GET",
+        &format!("{base_url}/v1beta/models?pageSize=1000"),
+        &[("x-goog-api-key", api_key)],
+        None,
+        VALIDATION_TIMEOUT,
+    );
+    let response = match response {
+        Ok(response) => response,
+        Err(err) => return ApiKeyValidation::new("google", "network_error", err, None),
+    };
+
+    let message = error_message(&response.body);
+    let status = match response.status {
+        200..=299 => "valid",
+        // Gemini answers a bad key with 400 API_KEY_INVALID.
+        400 if message.to_lowercase().contains("api key") => "invalid_key",
+        401 | 403 => "invalid_key",
+        429 => "quota_exceeded",
+        _ => "unexpected_response",
+    };
+    if status != "valid" {
+        return ApiKeyValidation::new("google", status, message, Some(response.status));
+    }
+
+    let available = listed_ids(&response.body, "models", "name");
+    match model {
+        Some(model)
+            if !available
+                .iter()
+                .any(|name| name == &format!("models/{model}")) =>
+        {
+            ApiKeyValidation::new(
+                "google",
+                "model_unavailable",
+                format!("Key is valid but {model} is not available to it"),
+                Some(response.status),
+            )
+        }
+        _ => ApiKeyValidation::new(
+            "google",
+            "valid",
+            format!("Key accepted ({} models available)", available.len()),
+            Some(response.status),
+        ),
+    }
+}
+
+/// OpenRouter routes to every listed model, so only the key itself is
+/// checked through its `/key` endpoint.
+fn validate_openrouter(api_key: &str, base_url: &str) -> ApiKeyValidation {
+    let authorization = format!("Bearer {api_key}");
+    let response = common::http_request(
+        "GET",
+        &format!("{base_url}/v1/key"),
+        &[("authorization", &authorization)],
+        None,
+        VALIDATION_TIMEOUT,
+    );
+    let response = match response {
+        Ok(response) => response,
+        Err(err) => return ApiKeyValidation::new("openrouter", "network_error", err, None),
+    };
+
+    let (status, detail) = match response.status {
+        200..=299 => ("valid", "Key accepted".to_string()),
+        401 | 403 => ("invalid_key", error_message(&response.body)),
+        402 | 429 => ("quota_exceeded", error_message(&response.body)),
+        _ => ("unexpected_response", error_message(&response.body)),
+    };
+    ApiKeyValidation::new("openrouter", status, detail, Some(response.status))
+}
+
+/// Sends a one-token chat completion to the configured deployment.
+/// `base_url` is the resource endpoint; the deployment and API version come
+/// from the provider's other credential fields.
+fn validate_azure(
+    api_key: &str,
+    base_url: &str,
+    credentials: &HashMap<String, String>,
+) -> ApiKeyValidation {
+    let field = |name: &str| {
+        credentials
+            .get(name)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let Some(deployment) = field("AZURE_OPENAI_DEPLOYMENT") else {
+        return ApiKeyValidation::new(
+            "azure-openai",
+            "model_unavailable",
+            "a deployment name is required to validate an Azure OpenAI key",
+            None,
+        );
+    };
+    let api_version = field("AZURE_OPENAI_API_VERSION").unwrap_or(AZURE_API_VERSION);
+
+    let body = json!({
+        "messages": [{ "role": "user", "content": "ping" }],
+        "max_tokens": 1,
+    })
+    .to_string();
+
+    let response = common::http_request(
+        "POST",
+        &format!(
+            "{base_url}/openai/deployments/{deployment}/chat/completions?api-version={api_version}"
+        ),
+        &[("api-key", api_key), ("content-type", "application/json")],
+        Some(&body),
+        VALIDATION_TIMEOUT,
+    );
+    let response = match response {
+        Ok(response) => response,
+        Err(err) => return ApiKeyValidation::new("azure-openai", "network_error", err, None),
+    };
+
+    let (status, detail) = match response.status {
+        200..=299 => ("valid", format!("Key accepted for deployment {deployment}")),
+        401 | 403 => ("invalid_key", error_message(&response.body)),
+        404 => (
+            "model_unavailable",
+            format!("Deployment {deployment} was not found at {base_url}"),
+        ),
+        429 => ("quota_exceeded", error_message(&response.body)),
+        _ => ("unexpected_response", error_message(&response.body)),
+    };
+    ApiKeyValidation::new("azure-openai", status, detail, Some(response.status))
+}
+
+fn validate_ollama(model: Option<&str>, base_url: &str) -> ApiKeyValidation {
+    let response = common::http_request(
+        "GET",
+        &format!("{base_url}/api/tags"),
+        &[],
+        None,
+        VALIDATION_TIMEOUT,
+    );
+    let response = match response {
+        Ok(response) => response,
+        Err(err) => return ApiKeyValidation::new("ollama", "network_error", err, None),
+    };
+    if !(200..300).contains(&response.status) {
+        return ApiKeyValidation::new(
+            "ollama",
+            "unexpected_response",
+            error_message(&response.body),
+            Some(response.status),
+        );
+    }
+
+    let pulled = listed_ids(&response.body, "models", "name");
+    match model {
+        Some(model) if !pulled.iter().any(|name| ollama_name_matches(name, model)) => {
+            ApiKeyValidation::new(
+                "ollama",
+                "model_unavailable",
+                format!("Ollama is running but {model} has not been pulled"),
+                Some(response.status),
+            )
+        }
+        _ => ApiKeyValidation::new(
+            "ollama",
+            "valid",
+            format!("Ollama is running with {} local model(s)", pulled.len()),
+            Some(response.status),
+        ),
+    }
+}
+
+/// Ollama reports `llama3.2:latest` for a model pulled as `llama3.2`.
+pub fn ollama_name_matches(listed: &str, wanted: &str) -> bool {
+    listed == wanted || listed.strip_suffix(":latest") == Some(wanted)
+}
+
+fn listed_ids(body: &str, list_key: &str, id_key: &str) -> Vec<String> {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|value| value.get(list_key).and_then(Value::as_array).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| item.get(id_key).and_then(Value::as_str).map(str::to_string))
+        .collect()
+}
+
+fn error_message(body: &str) -> String {
+    let parsed = serde_json::from_str::<Value>(body).ok();
+    let message = parsed.as_ref().and_then(|value| {
+        value
+            .pointer("/error/message")
+            .or_else(|| value.get("error"))
+            .or_else(|| value.get("message"))
+            .and_then(Value::as_str)
+    });
+    match message {
+        Some(message) => message.to_string(),
+        None => body.trim().chars().take(200).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anthropic_key_is_checked_with_a_minimal_message() {
+        let (base_url, requests) =
+            common::serve_http(vec![(200, "{\"id\":\"msg_1\"}".to_string())]);
+
+        let result = validate(
+            "anthropic",
+            "sk-ant-1",
+            Some("claude-opus-4-6"),
+            &base_url,
+            &HashMap::new(),
+        );
+
+        assert_eq!(result.status, "valid");
+        let request = requests.recv().expect("request captured");
+        assert!(request.starts_with("POST /v1/messages"));
+        assert!(request.contains("x-api-key: sk-ant-1"));
+        assert!(request.contains("\"model\":\"claude-opus-4-6\""));
+    }
+
+    #[test]
+    fn anthropic_errors_are_classified() {
+        let (base_url, _requests) = common::serve_http(vec![
+            (401, r#"{"type":"error","error":{"type":"authentication_error","message":"invalid x-api-key"}}"#.to_string()),
+            (400, r#"{"type":"error","error":{"type":"invalid_request_error","message":"Your credit balance is too low"}}"#.to_string()),
+            (404, r#"{"type":"error","error":{"type":"not_found_error","message":"model: nope"}}"#.to_string()),
+        ]);
+
+        let invalid = validate(
+            "anthropic",
+            "bad",
+            Some("claude-opus-4-6"),
+            &base_url,
+            &HashMap::new(),
+        );
+        assert_eq!(invalid.status, "invalid_key");
+        assert_eq!(invalid.detail, "invalid x-api-key");
+        assert_eq!(invalid.http_status, Some(401));
+
+        let billing = validate(
+            "anthropic",
+            "k",
+            Some("claude-opus-4-6"),
+            &base_url,
+            &HashMap::new(),
+        );
+        assert_eq!(billing.status, "quota_exceeded");
+
+        let missing = validate("anthropic", "k", Some("nope"), &base_url, &HashMap::new());
+        assert_eq!(missing.status, "model_unavailable");
+    }
+
+    #[test]
+    fn openai_key_reports_quota_and_missing_models() {
+        let (base_url, requests) = common::serve_http(vec![
+            (200, r#"{"data":[{"id":"gpt-4o-mini"}]}"#.to_string()),
+            (429, r#"{"error":{"code":"insufficient_quota","message":"You exceeded your current quota"}}"#.to_string()),
+        ]);
+
+        let missing = validate("openai", "sk-1", Some("gpt-4o"), &base_url, &HashMap::new());
+        assert_eq!(missing.status, "model_unavailable");
+        let request = requests.recv().expect("request captured");
+        assert!(request.starts_with("GET /v1/models"));
+        assert!(request.contains("authorization: Bearer sk-1"));
+
+        let quota = validate("openai", "sk-1", None, &base_url, &HashMap::new());
+        assert_eq!(quota.status, "quota_exceeded");
+    }
+
+    #[test]
+    fn gemini_and_openrouter_keys_are_classified() {
+        let (base_url, requests) = common::serve_http(vec![
+            (
+                200,
+                r#"{"models":[{"name":"models/gemini-2.5-flash"}]}"#.to_string(),
+            ),
+            (
+                400,
+                r#"{"error":{"code":400,"message":"API key not valid. Please pass a valid API key.","status":"INVALID_ARGUMENT"}}"#.to_string(),
+            ),
+            (
+                402,
+                r#"{"error":{"code":402,"message":"Insufficient credits"}}"#.to_string(),
+            ),
+        ]);
+        let none = HashMap::new();
+
+        let missing = validate("google", "AIza-1", Some("gemini-2.5-pro"), &base_url, &none);
+        assert_eq!(missing.status, "model_unavailable");
+        let request = requests.recv().expect("request captured");
+        assert!(request.starts_with("GET /v1beta/models"));
+        assert!(request.contains("x-goog-api-key: AIza-1"));
+
+        let invalid = validate("google", "bad", None, &base_url, &none);
+        assert_eq!(invalid.status, "invalid_key");
+        assert_eq!(invalid.http_status, Some(400));
+
+        let credits = validate("openrouter", "sk-or-1", None, &base_url, &none);
+        assert_eq!(credits.status, "quota_exceeded");
+        let _ = requests.recv();
+        let request = requests.recv().expect("request captured");
+        assert!(request.starts_with("GET /v1/key"));
+        assert!(request.contains("authorization: Bearer sk-or-1"));
+    }
+
+    #[test]
+    fn azure_checks_the_configured_deployment() {
+        let (base_url, requests) =
+            common::serve_http(vec![(200, r#"{"id":"gpt4o-prod"}"#.to_string())]);
+        let credentials = HashMap::from([
+            (
+                "AZURE_OPENAI_DEPLOYMENT".to_string(),
+                "gpt4o-prod".to_string(),
+            ),
+            (
+                "AZURE_OPENAI_API_VERSION".to_string(),
+                "2024-06-01".to_string(),
+            ),
+        ]);
+
+        let result = validate("azure-openai", "az-1", None, &base_url, &credentials);
+        assert_eq!(result.status, "valid");
+        let request = requests.recv().expect("request captured");
+        assert!(request.starts_with(
+            "POST /openai/deployments/gpt4o-prod/chat/completions?api-version=2024-06-01"
+        ));
+        assert!(request.contains("api-key: az-1"));
+
+        let unchecked = validate("amazon-bedrock", "", None, &base_url, &HashMap::new());
+        assert_eq!(unchecked.status, "unsupported");
+    }
+
+    #[test]
+    fn ollama_checks_pulled_models() {
+        let (base_url, _requests) = common::serve_http(vec![
+            (
+                200,
+                r#"{"models":[{"name":"llama3.2:latest"}]}"#.to_string(),
+            ),
+            (
+                200,
+                r#"{"models":[{"name":"llama3.2:latest"}]}"#.to_string(),
+            ),
+        ]);
+
+        assert_eq!(
+            validate("ollama", "", Some("llama3.2"), &base_url, &HashMap::new()).status,
+            "valid"
+        );
+        assert_eq!(
+            validate("ollama", "", Some("mistral"), &base_url, &HashMap::new()).status,
+            "model_unavailable"
+        );
+    }
+
+    #[test]
+    fn unreachable_endpoint_is_a_network_error() {
+        let port = std::net::TcpListener::bind(("127.0.0.1", 0))
+            .and_then(|listener| listener.local_addr())
+            .expect("reserve port")
+            .port();
+
+        let result = validate(
+            "openai",
+            "sk-1",
+            None,
+            &format!("http://127.0.0.1:{port}"),
+            &HashMap::new(),
+        );
+        assert_eq!(result.status, "network_error");
+        assert_eq!(result.http_status, None);
+    }
+}
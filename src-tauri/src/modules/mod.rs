@@ -1,8 +1,15 @@
 pub mod browser_launcher;
 pub mod common;
+pub mod config_watcher;
 pub mod install_location;
 pub mod llm_config;
 pub mod node_runtime;
+pub mod ollama;
+pub mod openclaw_config;
 pub mod openclaw_installer;
 pub mod platform;
+pub mod plugin_manager;
+pub mod secret_store;
 pub mod service_manager;
+pub mod setup_bundle;
+pub mod single_instance;
@@ -66,6 +66,7 @@ pub fn get_node_env(app: AppHandle) -> Result<HashMap<String, String>, String> {
 pub fn install_node(app: AppHandle, os: String, arch: String) -> Result<NodeStatus, String> {
     clear_cached_status();
     let app_data_dir = common::app_data_dir(&app)?;
+    let _lock = common::InstallLock::acquire(&app_data_dir, "install_node")?;
     let desired_version = resolve_latest_stable_node_version()
         .unwrap_or_else(|| common::NODE_FALLBACK_VERSION.to_string());
 
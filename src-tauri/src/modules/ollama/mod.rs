@@ -0,0 +1,489 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::modules::{common, llm_config};
+
+const PROVIDER: &str = "ollama";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Large models take hours on a slow connection.
+const PULL_TIMEOUT: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaStatus {
+    /// An `ollama` binary was found, or a server answers.
+    pub installed: bool,
+    pub binary_path: Option<String>,
+    pub running: bool,
+    /// Server version, when running.
+    pub version: Option<String>,
+    pub base_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OllamaModel {
+    /// As Ollama lists it, e.g. `llama3.2:latest`.
+    pub name: String,
+    /// The id to select in the LLM config, e.g. `ollama/llama3.2`.
+    pub model_id: String,
+    /// Bytes on disk.
+    pub size: u64,
+    pub modified_at: Option<String>,
+    pub parameter_size: Option<String>,
+    pub quantization: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OllamaPullProgress {
+    pub model: String,
+    /// Ollama's status line, e.g. `pulling manifest` or `success`.
+    pub status: String,
+    pub digest: Option<String>,
+    pub completed: Option<u64>,
+    pub total: Option<u64>,
+    pub percent: Option<f64>,
+}
+
+/// Whether Ollama is installed on this machine and whether its server
+/// answers at the provider's base URL.
+#[tauri::command]
+pub async fn get_ollama_status(app: AppHandle) -> Result<OllamaStatus, String> {
+    let base_url = base_url(&app)?;
+    tauri::async_runtime::spawn_blocking(move || status(&base_url))
+        .await
+        .map_err(|e| format!("ollama status task failed: {e}"))
+}
+
+#[tauri::command]
+pub async fn list_ollama_models(app: AppHandle) -> Result<Vec<OllamaModel>, String> {
+    let base_url = base_url(&app)?;
+    tauri::async_runtime::spawn_blocking(move || list_models(&base_url))
+        .await
+        .map_err(|e| format!("ollama model listing task failed: {e}"))?
+}
+
+/// Downloads `model` into the local Ollama, emitting `ollama:pull-progress`
+/// as it goes. Returns the local models afterwards, which also refreshes the
+/// Ollama models offered by `list_providers`.
+#[tauri::command]
+pub async fn pull_ollama_model(app: AppHandle, model: String) -> Result<Vec<OllamaModel>, String> {
+    let model = model_name(&model)?;
+    let base_url = base_url(&app)?;
+    let app_data_dir = common::app_data_dir(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        pull(&base_url, &model, |progress| {
+            if let Err(err) = app.emit("ollama:pull-progress", progress) {
+                log::warn!("failed to emit ollama pull progress: {err}");
+            }
+        })?;
+        models_after_change(&app_data_dir, &base_url)
+    })
+    .await
+    .map_err(|e| format!("ollama pull task failed: {e}"))?
+}
+
+/// Removes `model` from the local Ollama and returns the models left.
+#[tauri::command]
+pub async fn delete_ollama_model(
+    app: AppHandle,
+    model: String,
+) -> Result<Vec<OllamaModel>, String> {
+    let model = model_name(&model)?;
+    let base_url = base_url(&app)?;
+    let app_data_dir = common::app_data_dir(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        delete(&base_url, &model)?;
+        models_after_change(&app_data_dir, &base_url)
+    })
+    .await
+    .map_err(|e| format!("ollama delete task failed: {e}"))?
+}
+
+fn base_url(app: &AppHandle) -> Result<String, String> {
+    llm_config::provider_base_url(app, PROVIDER)
+        .map(|url| url.trim_end_matches('/').to_string())
+        .ok_or_else(|| "no base URL known for ollama".to_string())
+}
+
+fn status(base_url: &str) -> OllamaStatus {
+    let binary_path = binary_path();
+    let version = common::http_request(
+        "GET",
+        &format!("{base_url}/api/version"),
+        &[],
+        None,
+        REQUEST_TIMEOUT,
+    )
+    .ok()
+    .filter(|response| (200..300).contains(&response.status))
+    .map(|response| {
+        serde_json::from_str::<Value>(&response.body)
+            .ok()
+            .and_then(|body| body.get("version")?.as_str().map(str::to_string))
+    });
+
+    OllamaStatus {
+        installed: binary_path.is_some() || version.is_some(),
+        binary_path,
+        running: version.is_some(),
+        version: version.flatten(),
+        base_url: base_url.to_string(),
+    }
+}
+
+fn list_models(base_url: &str) -> Result<Vec<OllamaModel>, String> {
+    let response = common::http_request(
+        "GET",
+        &format!("{base_url}/api/tags"),
+        &[],
+        None,
+        REQUEST_TIMEOUT,
+    )
+    .map_err(|err| format!("Ollama is not running at {base_url}: {err}"))?;
+    if !(200..300).contains(&response.status) {
+        return Err(format!(
+            "Ollama answered HTTP {} when listing models: {}",
+            response.status,
+            error_message(&response.body)
+        ));
+    }
+    parse_models(&response.body)
+}
+
+fn pull(
+    base_url: &str,
+    model: &str,
+    mut on_progress: impl FnMut(OllamaPullProgress),
+) -> Result<(), String> {
+    let body = json!({ "model": model, "stream": true }).to_string();
+    let mut failure = None;
+    let mut finished = false;
+    let mut last_reported = None;
+    let status = common::http_stream_lines(
+        "POST",
+        &format!("{base_url}/api/pull"),
+        &[("Content-Type", "application/json")],
+        Some(&body),
+        PULL_TIMEOUT,
+        |line| match parse_progress(model, line) {
+            Ok(Some(progress)) => {
+                finished |= progress.status == "success";
+                // Ollama reports every chunk; pass on whole percents only.
+                let step = (
+                    progress.status.clone(),
+                    progress.percent.map(|percent| percent as u64),
+                );
+                if last_reported.as_ref() != Some(&step) {
+                    last_reported = Some(step);
+                    on_progress(progress);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => failure = Some(err),
+        },
+    )
+    .map_err(|err| format!("Ollama is not running at {base_url}: {err}"))?;
+
+    if let Some(err) = failure {
+        return Err(format!("Ollama could not pull {model}: {err}"));
+    }
+    if !(200..300).contains(&status) {
+        return Err(format!(
+            "Ollama answered HTTP {status} when pulling {model}"
+        ));
+    }
+    if !finished {
+        return Err(format!(
+            "the download of {model} stopped before it finished"
+        ));
+    }
+    Ok(())
+}
+
+fn delete(base_url: &str, model: &str) -> Result<(), String> {
+    let body = json!({ "model": model }).to_string();
+    let response = common::http_request(
+        "DELETE",
+        &format!("{base_url}/api/delete"),
+        &[("Content-Type", "application/json")],
+        Some(&body),
+        REQUEST_TIMEOUT,
+    )
+    .map_err(|err| format!("Ollama is not running at {base_url}: {err}"))?;
+    match response.status {
+        200..=299 => Ok(()),
+        404 => Err(format!("{model} is not pulled")),
+        status => Err(format!(
+            "Ollama answered HTTP {status} when deleting {model}: {}",
+            error_message(&response.body)
+        )),
+    }
+}
+
+fn models_after_change(app_data_dir: &Path, base_url: &str) -> Result<Vec<OllamaModel>, String> {
+    if let Err(err) = llm_config::rediscover_models(app_data_dir, PROVIDER, base_url) {
+        log::warn!("failed to refresh discovered ollama models: {err}");
+    }
+    list_models(base_url)
+}
+
+/// One line of a streamed pull. Blank and unparsable lines are skipped; an
+/// `error` line fails the pull.
+fn parse_progress(model: &str, line: &str) -> Result<Option<OllamaPullProgress>, String> {
+    let Ok(value) = serde_json::from_str::<Value>(line.trim()) else {
+        return Ok(None);
+    };
+    if let Some(error) = value.get("error").and_then(Value::as_str) {
+        return Err(error.to_string());
+    }
+    let Some(status) = value.get("status").and_then(Value::as_str) else {
+        return Ok(None);
+    };
+    let completed = value.get("completed").and_then(Value::as_u64);
+    let total = value.get("total").and_then(Value::as_u64);
+    let percent = match (completed, total) {
+        (Some(completed), Some(total)) if total > 0 => {
+            Some((completed as f64 / total as f64 * 100.0).min(100.0))
+        }
+        _ if status == "success" => Some(100.0),
+        _ => None,
+    };
+    Ok(Some(OllamaPullProgress {
+        model: model.to_string(),
+        status: status.to_string(),
+        digest: value
+            .get("digest")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        completed,
+        total,
+        percent,
+    }))
+}
+
+fn parse_models(body: &str) -> Result<Vec<OllamaModel>, String> {
+    let value = serde_json::from_str::<Value>(body)
+        .map_err(|e| format!("unexpected model list from Ollama: {e}"))?;
+    Ok(value
+        .get("models")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let name = item
+                .get("name")
+                .or_else(|| item.get("model"))
+                .and_then(Value::as_str)?;
+            let detail = |key: &str| {
+                item.get("details")
+                    .and_then(|details| details.get(key))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            };
+            Some(OllamaModel {
+                name: name.to_string(),
+                model_id: format!(
+                    "{PROVIDER}/{}",
+                    name.strip_suffix(":latest").unwrap_or(name)
+                ),
+                size: item.get("size").and_then(Value::as_u64).unwrap_or(0),
+                modified_at: item
+                    .get("modified_at")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                parameter_size: detail("parameter_size"),
+                quantization: detail("quantization_level"),
+            })
+        })
+        .collect())
+}
+
+/// Accepts `llama3.2`, `qwen2.5:7b` or a model id such as `ollama/llama3.2`.
+fn model_name(model: &str) -> Result<String, String> {
+    let model = model.trim();
+    let name = model.strip_prefix(&format!("{PROVIDER}/")).unwrap_or(model);
+    let allowed = |c: char| c.is_ascii_alphanumeric() || "._-:/".contains(c);
+    if name.is_empty() || !name.chars().all(allowed) {
+        return Err(format!("{model:?} is not an Ollama model name"));
+    }
+    Ok(name.to_string())
+}
+
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|value| value.get("error")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| body.trim().chars().take(200).collect())
+}
+
+fn binary_path() -> Option<String> {
+    if let Some(found) = binary_candidates()
+        .into_iter()
+        .find(|candidate| Path::new(candidate).exists())
+    {
+        return Some(found);
+    }
+
+    let lookup = if cfg!(target_os = "windows") {
+        "where"
+    } else {
+        "which"
+    };
+    let output = Command::new(lookup).arg("ollama").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok().and_then(|stdout| {
+        stdout
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+    })
+}
+
+fn binary_candidates() -> Vec<String> {
+    if cfg!(target_os = "macos") {
+        vec![
+            "/Applications/Ollama.app/Contents/Resources/ollama".to_string(),
+            "/opt/homebrew/bin/ollama".to_string(),
+            "/usr/local/bin/ollama".to_string(),
+        ]
+    } else if cfg!(target_os = "windows") {
+        std::env::var("LOCALAPPDATA")
+            .map(|dir| vec![format!(r"{dir}\Programs\Ollama\ollama.exe")])
+            .unwrap_or_default()
+    } else {
+        vec![
+            "/usr/local/bin/ollama".to_string(),
+            "/usr/bin/ollama".to_string(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_reports_a_running_server() {
+        let (base_url, requests) = common::serve_http(vec![(200, r#"{"version":"0.5.7"}"#.into())]);
+        let running = status(&base_url);
+        assert!(running.running && running.installed);
+        assert_eq!(running.version.as_deref(), Some("0.5.7"));
+        assert!(requests
+            .recv()
+            .expect("request")
+            .starts_with("GET /api/version"));
+
+        let closed = std::net::TcpListener::bind(("127.0.0.1", 0)).expect("bind");
+        let closed_url = format!("http://{}", closed.local_addr().expect("addr"));
+        drop(closed);
+        let stopped = status(&closed_url);
+        assert!(!stopped.running);
+        assert_eq!(stopped.version, None);
+    }
+
+    #[test]
+    fn local_models_are_listed_with_ids() {
+        let body = r#"{"models":[
+            {"name":"llama3.2:latest","size":2019393189,"modified_at":"2026-10-01T10:00:00Z",
+             "details":{"parameter_size":"3.2B","quantization_level":"Q4_K_M"}},
+            {"name":"qwen2.5:7b","size":4683087332}
+        ]}"#;
+        let (base_url, _requests) = common::serve_http(vec![(200, body.into())]);
+
+        let models = list_models(&base_url).expect("models");
+
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].model_id, "ollama/llama3.2");
+        assert_eq!(models[0].parameter_size.as_deref(), Some("3.2B"));
+        assert_eq!(models[0].quantization.as_deref(), Some("Q4_K_M"));
+        assert_eq!(models[1].model_id, "ollama/qwen2.5:7b");
+        assert_eq!(models[1].modified_at, None);
+    }
+
+    #[test]
+    fn pulls_stream_progress_until_success() {
+        let body = [
+            r#"{"status":"pulling manifest"}"#,
+            r#"{"status":"pulling abc","digest":"sha256:abc","total":200,"completed":50}"#,
+            r#"{"status":"pulling abc","digest":"sha256:abc","total":200,"completed":51}"#,
+            r#"{"status":"pulling abc","digest":"sha256:abc","total":200,"completed":200}"#,
+            r#"{"status":"verifying sha256 digest"}"#,
+            r#"{"status":"success"}"#,
+        ]
+        .join("\n");
+        let (base_url, requests) = common::serve_http(vec![(200, body)]);
+        let mut seen = Vec::new();
+
+        pull(&base_url, "llama3.2", |progress| seen.push(progress)).expect("pull");
+
+        let steps = seen
+            .iter()
+            .map(|p| (p.status.as_str(), p.percent))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            steps,
+            vec![
+                ("pulling manifest", None),
+                ("pulling abc", Some(25.0)),
+                ("pulling abc", Some(100.0)),
+                ("verifying sha256 digest", None),
+                ("success", Some(100.0)),
+            ]
+        );
+        let request = requests.recv().expect("request");
+        assert!(request.starts_with("POST /api/pull"));
+        assert!(request.ends_with(r#"{"model":"llama3.2","stream":true}"#));
+    }
+
+    #[test]
+    fn pull_errors_and_early_ends_fail() {
+        let failing = [
+            r#"{"status":"pulling manifest"}"#,
+            r#"{"error":"pull model manifest: file does not exist"}"#,
+        ]
+        .join("\n");
+        let (base_url, _requests) = common::serve_http(vec![
+            (200, failing),
+            (200, r#"{"status":"pulling manifest"}"#.into()),
+        ]);
+
+        let err = pull(&base_url, "nope", |_| {}).expect_err("error line");
+        assert!(err.contains("file does not exist"));
+        let err = pull(&base_url, "llama3.2", |_| {}).expect_err("no success");
+        assert!(err.contains("stopped before it finished"));
+    }
+
+    #[test]
+    fn deleting_reports_missing_models() {
+        let (base_url, requests) = common::serve_http(vec![
+            (200, String::new()),
+            (404, r#"{"error":"model 'x' not found"}"#.into()),
+        ]);
+
+        delete(&base_url, "llama3.2").expect("deleted");
+        let request = requests.recv().expect("request");
+        assert!(request.starts_with("DELETE /api/delete"));
+        assert!(request.ends_with(r#"{"model":"llama3.2"}"#));
+        assert_eq!(
+            delete(&base_url, "x").expect_err("missing"),
+            "x is not pulled"
+        );
+    }
+
+    #[test]
+    fn model_names_accept_ids_and_reject_junk() {
+        assert_eq!(model_name(" ollama/llama3.2 ").expect("id"), "llama3.2");
+        assert_eq!(
+            model_name("hf.co/org/model:Q4").expect("hub"),
+            "hf.co/org/model:Q4"
+        );
+        assert!(model_name("").is_err());
+        assert!(model_name("llama3.2; rm -rf").is_err());
+    }
+}
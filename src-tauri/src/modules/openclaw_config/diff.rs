@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{pointer, redact_secrets};
+
+/// One changed value between two versions of a config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChange {
+    /// JSON pointer to the value; `""` is the whole document.
+    pub path: String,
+    /// `None` when the value was added.
+    pub before: Option<Value>,
+    /// `None` when the value was removed.
+    pub after: Option<Value>,
+}
+
+/// Changes from `before` to `after`, descending into objects. Arrays and
+/// scalars are compared as a whole. Values that look like secrets are masked.
+pub fn diff(before: &Value, after: &Value) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+    walk(String::new(), Some(before), Some(after), &mut changes);
+    for change in &mut changes {
+        for value in [&mut change.before, &mut change.after]
+            .into_iter()
+            .flatten()
+        {
+            redact_secrets(&change.path, value);
+        }
+    }
+    changes
+}
+
+fn walk(path: String, before: Option<&Value>, after: Option<&Value>, out: &mut Vec<ConfigChange>) {
+    match (before, after) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            for (key, old_value) in old {
+                walk(
+                    pointer::child(&path, key),
+                    Some(old_value),
+                    new.get(key),
+                    out,
+                );
+            }
+            for (key, new_value) in new.iter().filter(|(key, _)| !old.contains_key(*key)) {
+                walk(pointer::child(&path, key), None, Some(new_value), out);
+            }
+        }
+        (old, new) if old != new => out.push(ConfigChange {
+            path,
+            before: old.cloned(),
+            after: new.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn nested_changes_are_reported_by_pointer() {
+        let before = json!({
+            "agent": { "model": "anthropic/claude-opus-4-6", "tools": ["web"] },
+            "gateway": { "port": 18789 }
+        });
+        let after = json!({
+            "agent": { "model": "openai/gpt-4o", "tools": ["web", "shell"] },
+            "a/b": true
+        });
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![
+                ConfigChange {
+                    path: "/agent/model".to_string(),
+                    before: Some(json!("anthropic/claude-opus-4-6")),
+                    after: Some(json!("openai/gpt-4o")),
+                },
+                ConfigChange {
+                    path: "/agent/tools".to_string(),
+                    before: Some(json!(["web"])),
+                    after: Some(json!(["web", "shell"])),
+                },
+                ConfigChange {
+                    path: "/gateway".to_string(),
+                    before: Some(json!({ "port": 18789 })),
+                    after: None,
+                },
+                ConfigChange {
+                    path: "/a~1b".to_string(),
+                    before: None,
+                    after: Some(json!(true)),
+                },
+            ]
+        );
+        assert!(diff(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn secrets_are_masked() {
+        let before = json!({});
+        let after = json!({
+            "env": { "OPENAI_API_KEY": "sk-openai-0123456789" },
+            "gateway": { "auth": { "token": "abcdefgh" }, "mode": "local" }
+        });
+        let changes = diff(&before, &after);
+
+        assert_eq!(
+            changes[0].after,
+            Some(json!({ "OPENAI_API_KEY": "sk-o…6789" }))
+        );
+        assert_eq!(
+            changes[1].after,
+            Some(json!({ "auth": { "token": "…gh" }, "mode": "local" }))
+        );
+    }
+}
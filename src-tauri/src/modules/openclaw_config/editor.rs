@@ -0,0 +1,347 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::json5::Document;
+use super::pointer::{self, ConfigPatch};
+use super::schema::{FieldError, Schema};
+
+const SECRET_WORDS: &[&str] = &["key", "token", "secret", "password"];
+
+/// One value of the config with what the schema says about it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigNode {
+    /// JSON pointer; `""` for the root.
+    pub path: String,
+    /// Member name or array index; `""` for the root.
+    pub key: String,
+    /// JSON type: `object`, `array`, `string`, `number`, `boolean` or `null`.
+    pub kind: String,
+    /// Set for scalars only, masked when `secret`.
+    pub value: Option<Value>,
+    pub secret: bool,
+    pub description: Option<String>,
+    pub children: Vec<ConfigNode>,
+}
+
+pub fn tree(value: &Value, schema: &Schema) -> ConfigNode {
+    node(value, schema, &[], String::new())
+}
+
+fn node(value: &Value, schema: &Schema, segments: &[String], path: String) -> ConfigNode {
+    let children = match value {
+        Value::Object(members) => members
+            .iter()
+            .map(|(key, member)| (key.clone(), member, pointer::child(&path, key)))
+            .collect::<Vec<_>>(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (index.to_string(), item, format!("{path}/{index}")))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let secret =
+        is_secret_path(&path) && value.as_str().is_some_and(|text| !is_env_reference(text));
+    let mut scalar = (!value.is_object() && !value.is_array()).then(|| value.clone());
+    if let Some(item) = scalar.as_mut() {
+        redact_secrets(&path, item);
+    }
+
+    ConfigNode {
+        key: segments.last().cloned().unwrap_or_default(),
+        kind: kind(value).to_string(),
+        value: scalar,
+        secret,
+        description: schema.description(segments),
+        children: children
+            .into_iter()
+            .map(|(key, child, child_path)| {
+                let mut child_segments = segments.to_vec();
+                child_segments.push(key);
+                node(child, schema, &child_segments, child_path)
+            })
+            .collect(),
+        path,
+    }
+}
+
+/// Applies `patches` to a copy of `current`. Returns the patched value, or
+/// one error per patch that could not be applied.
+pub fn patched(current: &Value, patches: &[ConfigPatch]) -> Result<Value, Vec<FieldError>> {
+    let mut next = current.clone();
+    let mut errors = Vec::new();
+    for patch in patches {
+        if let Err(message) = pointer::apply(&mut next, patch) {
+            errors.push(FieldError {
+                path: patch.path.clone(),
+                message,
+            });
+        }
+    }
+    if errors.is_empty() {
+        Ok(next)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Replays `patches` on the document text. [`Document`] edits objects only,
+/// so a patch inside an array rewrites the innermost enclosing array as a
+/// whole; comments elsewhere are kept.
+pub fn apply_to_document(document: &mut Document, patches: &[ConfigPatch]) -> Result<(), String> {
+    let mut value = document.value();
+    for patch in patches {
+        pointer::apply(&mut value, patch)?;
+        let segments = pointer::parse(&patch.path)?;
+        let mut object_depth = segments.len();
+        let mut node = Some(&value);
+        for (depth, segment) in segments.iter().enumerate() {
+            match node {
+                Some(Value::Array(_)) => {
+                    object_depth = depth;
+                    break;
+                }
+                Some(Value::Object(members)) => node = members.get(segment),
+                _ => break,
+            }
+        }
+
+        let prefix = segments[..object_depth]
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        match value.pointer(&prefix_pointer(&prefix)) {
+            Some(target) => document.set(&prefix, target)?,
+            None => {
+                document.remove(&prefix)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn prefix_pointer(segments: &[&str]) -> String {
+    segments.iter().fold(String::new(), |path, segment| {
+        pointer::child(&path, segment)
+    })
+}
+
+/// A secret string taken out of a config by [`strip_secrets`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecretValue {
+    pub path: String,
+    pub value: String,
+}
+
+/// Removes the strings [`redact_secrets`] would mask, returning them in
+/// document order.
+pub fn strip_secrets(document: &mut Document) -> Result<Vec<SecretValue>, String> {
+    let mut secrets = Vec::new();
+    collect_secrets("", &document.value(), &mut secrets);
+    // Last first, so array indices of the remaining secrets stay valid.
+    let patches = secrets
+        .iter()
+        .rev()
+        .map(|secret| ConfigPatch {
+            op: "remove".to_string(),
+            path: secret.path.clone(),
+            value: None,
+        })
+        .collect::<Vec<_>>();
+    apply_to_document(document, &patches)?;
+    Ok(secrets)
+}
+
+/// Puts `secrets` back where [`strip_secrets`] took them from. A secret
+/// whose parent no longer exists is skipped.
+pub fn restore_secrets(document: &mut Document, secrets: &[SecretValue]) -> Result<(), String> {
+    for secret in secrets {
+        let patch = ConfigPatch {
+            op: "add".to_string(),
+            path: secret.path.clone(),
+            value: Some(Value::String(secret.value.clone())),
+        };
+        if pointer::apply(&mut document.value(), &patch).is_ok() {
+            apply_to_document(document, &[patch])?;
+        }
+    }
+    Ok(())
+}
+
+fn collect_secrets(path: &str, value: &Value, secrets: &mut Vec<SecretValue>) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map {
+                collect_secrets(&pointer::child(path, key), item, secrets);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                collect_secrets(&format!("{path}/{index}"), item, secrets);
+            }
+        }
+        Value::String(text) if is_secret_path(path) && !is_env_reference(text) => {
+            secrets.push(SecretValue {
+                path: path.to_string(),
+                value: text.clone(),
+            })
+        }
+        _ => {}
+    }
+}
+
+/// Errors in `after` that were not already in `before`, so an edit is not
+/// blocked by problems it did not cause.
+pub fn introduced(before: &[FieldError], after: &[FieldError]) -> Vec<FieldError> {
+    after
+        .iter()
+        .filter(|error| !before.contains(error))
+        .cloned()
+        .collect()
+}
+
+/// Masks string values under `/env` or under a name mentioning a key, token,
+/// secret or password, so they can be shown in the UI. `${VAR}` references
+/// are left readable since they hold no secret.
+pub fn redact_secrets(path: &str, value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                redact_secrets(&pointer::child(path, key), item);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                redact_secrets(&format!("{path}/{index}"), item);
+            }
+        }
+        Value::String(text) if is_secret_path(path) && !is_env_reference(text) => {
+            *text = crate::modules::secret_store::mask(text)
+        }
+        _ => {}
+    }
+}
+
+fn is_secret_path(path: &str) -> bool {
+    if path.starts_with("/env/") {
+        return true;
+    }
+    path.split('/')
+        .rfind(|segment| segment.parse::<usize>().is_err())
+        .map(|name| {
+            let name = name.to_ascii_lowercase();
+            SECRET_WORDS.iter().any(|word| name.contains(word))
+        })
+        .unwrap_or(false)
+}
+
+fn is_env_reference(text: &str) -> bool {
+    text.strip_prefix("${")
+        .and_then(|rest| rest.strip_suffix('}'))
+        .map(crate::modules::llm_config::is_env_var_name)
+        .unwrap_or(false)
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patch(op: &str, path: &str, value: Option<Value>) -> ConfigPatch {
+        ConfigPatch {
+            op: op.to_string(),
+            path: path.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn tree_masks_secrets_and_carries_descriptions() {
+        let schema = Schema::parse(super::super::BUNDLED_SCHEMA).expect("bundled schema");
+        let config = json!({
+            "gateway": { "port": 18789, "auth": { "token": "tok-0123456789abcdef" } },
+            "models": { "providers": { "corp": { "apiKey": "${CORP_KEY}" } } }
+        });
+        let root = tree(&config, &schema);
+
+        let gateway = &root.children[0];
+        assert_eq!(gateway.path, "/gateway");
+        assert_eq!(
+            gateway.description.as_deref(),
+            Some("The local gateway the app starts.")
+        );
+        assert_eq!(gateway.children[0].value, Some(json!(18789)));
+        let token = &gateway.children[1].children[0];
+        assert!(token.secret);
+        assert_eq!(token.value, Some(json!("tok-…cdef")));
+
+        let api_key = &root.children[1].children[0].children[0].children[0];
+        assert_eq!(api_key.path, "/models/providers/corp/apiKey");
+        assert_eq!(api_key.value, Some(json!("${CORP_KEY}")));
+    }
+
+    #[test]
+    fn document_edits_keep_comments() {
+        let mut document = Document::parse(
+            "{\n  // the model\n  agent: { model: { primary: 'a/b', fallbacks: ['c/d'] } },\n  gateway: { port: 1 },\n}\n",
+        )
+        .expect("parse");
+        let patches = vec![
+            patch("replace", "/gateway/port", Some(json!(18789))),
+            patch("add", "/agent/model/fallbacks/-", Some(json!("e/f"))),
+            patch("remove", "/agent/model/fallbacks/0", None),
+            patch("add", "/tools", Some(json!({ "web": true }))),
+        ];
+        let expected = patched(&document.value(), &patches).expect("patches apply");
+        apply_to_document(&mut document, &patches).expect("document edits");
+
+        assert_eq!(document.value(), expected);
+        assert!(document.text().contains("// the model"));
+
+        apply_to_document(&mut document, &[patch("remove", "/tools", None)]).expect("remove");
+        assert!(document.value().get("tools").is_none());
+    }
+
+    #[test]
+    fn secrets_are_stripped_and_restored() {
+        let text = "{\n  // shared\n  env: { OPENAI_API_KEY: 'sk-1', vars: { A: '${A}' } },\n  gateway: { port: 1, auth: { mode: 'token', token: 'tok' } },\n}\n";
+        let mut document = Document::parse(text).expect("parse");
+        let original = document.value();
+
+        let secrets = strip_secrets(&mut document).expect("strip");
+        let paths = secrets.iter().map(|s| s.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["/env/OPENAI_API_KEY", "/gateway/auth/token"]);
+        assert!(!document.text().contains("sk-1"));
+        assert!(document.text().contains("// shared"));
+        assert_eq!(
+            document.value().pointer("/env/vars/A"),
+            Some(&json!("${A}"))
+        );
+
+        restore_secrets(&mut document, &secrets).expect("restore");
+        assert_eq!(document.value(), original);
+    }
+
+    #[test]
+    fn only_new_errors_block_an_edit() {
+        let error = |path: &str| FieldError {
+            path: path.to_string(),
+            message: "bad".to_string(),
+        };
+        assert_eq!(
+            introduced(&[error("/a")], &[error("/a"), error("/b")]),
+            vec![error("/b")]
+        );
+    }
+}
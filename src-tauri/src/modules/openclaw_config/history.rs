@@ -0,0 +1,220 @@
+//! Snapshots of `openclaw.json` taken after every write the app makes, kept
+//! as `<app_data>/config-history/<id>.json`. Ids start with the Unix time so
+//! file names sort oldest first.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::modules::common;
+
+pub const MAX_VERSIONS: usize = 50;
+pub const MAX_AGE_SECS: u64 = 90 * 24 * 60 * 60;
+
+/// One recorded version, without its text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigVersion {
+    pub id: String,
+    pub created_at: String,
+    /// What wrote this version, e.g. "model set to openai/gpt-4o".
+    pub reason: String,
+    /// The version this one restored, for restores.
+    #[serde(default)]
+    pub restored_from: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Snapshot {
+    #[serde(flatten)]
+    version: ConfigVersion,
+    text: String,
+}
+
+/// What to record and when.
+pub struct Entry<'a> {
+    pub text: &'a str,
+    pub reason: &'a str,
+    pub restored_from: Option<&'a str>,
+    pub now_secs: u64,
+    pub created_at: &'a str,
+}
+
+/// Stores `entry` as a new version unless its text equals the newest one;
+/// restores are always stored. Prunes old versions afterwards.
+pub fn record(dir: &Path, entry: Entry) -> Result<Option<ConfigVersion>, String> {
+    let ids = ids(dir)?;
+    if entry.restored_from.is_none() {
+        if let Some(latest) = ids.last() {
+            if read(dir, latest)? == entry.text {
+                return Ok(None);
+            }
+        }
+    }
+
+    std::fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+    let id = (0..)
+        .map(|seq| format!("{:011}-{seq:03}", entry.now_secs))
+        .find(|id| !dir.join(format!("{id}.json")).exists())
+        .expect("some sequence number is free");
+    let version = ConfigVersion {
+        id: id.clone(),
+        created_at: entry.created_at.to_string(),
+        reason: entry.reason.to_string(),
+        restored_from: entry.restored_from.map(str::to_string),
+    };
+    let snapshot = Snapshot {
+        version: version.clone(),
+        text: entry.text.to_string(),
+    };
+    let serialized = serde_json::to_vec_pretty(&snapshot)
+        .map_err(|e| format!("failed to serialize config version: {e}"))?;
+    common::write_file_atomic(&dir.join(format!("{id}.json")), &serialized)?;
+
+    prune(dir, entry.now_secs, MAX_VERSIONS, MAX_AGE_SECS);
+    Ok(Some(version))
+}
+
+/// Recorded versions, newest first. Unreadable snapshots are skipped.
+pub fn list(dir: &Path) -> Result<Vec<ConfigVersion>, String> {
+    Ok(ids(dir)?
+        .iter()
+        .rev()
+        .filter_map(|id| load(dir, id).ok())
+        .map(|snapshot| snapshot.version)
+        .collect())
+}
+
+pub fn read(dir: &Path, id: &str) -> Result<String, String> {
+    load(dir, id).map(|snapshot| snapshot.text)
+}
+
+/// Drops version `id`, for a version recorded ahead of a write that then
+/// failed.
+pub fn remove(dir: &Path, id: &str) {
+    let _ = std::fs::remove_file(dir.join(format!("{id}.json")));
+}
+
+pub fn is_empty(dir: &Path) -> Result<bool, String> {
+    ids(dir).map(|ids| ids.is_empty())
+}
+
+fn load(dir: &Path, id: &str) -> Result<Snapshot, String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return Err(format!("invalid config version id: {id}"));
+    }
+    let path = dir.join(format!("{id}.json"));
+    let raw = std::fs::read_to_string(&path).map_err(|_| format!("no config version {id}"))?;
+    serde_json::from_str(&raw).map_err(|e| format!("corrupt config version {id}: {e}"))
+}
+
+fn ids(dir: &Path) -> Result<Vec<String>, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("failed to read {}: {err}", dir.display())),
+    };
+    let mut ids = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.strip_suffix(".json").map(str::to_string)
+        })
+        .filter(|id| id.chars().all(|c| c.is_ascii_digit() || c == '-'))
+        .collect::<Vec<_>>();
+    ids.sort();
+    Ok(ids)
+}
+
+/// Drops versions beyond the newest `max_versions` and versions older than
+/// `max_age_secs`. The newest version is always kept.
+fn prune(dir: &Path, now_secs: u64, max_versions: usize, max_age_secs: u64) {
+    let Ok(ids) = ids(dir) else {
+        return;
+    };
+    let excess = ids.len().saturating_sub(max_versions);
+    for (index, id) in ids.iter().enumerate() {
+        if index + 1 == ids.len() {
+            break;
+        }
+        let created = id
+            .split('-')
+            .next()
+            .and_then(|secs| secs.parse::<u64>().ok())
+            .unwrap_or(0);
+        if index < excess || now_secs.saturating_sub(created) > max_age_secs {
+            let _ = std::fs::remove_file(dir.join(format!("{id}.json")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry<'a>(text: &'a str, now_secs: u64) -> Entry<'a> {
+        Entry {
+            text,
+            reason: "test",
+            restored_from: None,
+            now_secs,
+            created_at: "2026-10-18T00:00:00Z",
+        }
+    }
+
+    #[test]
+    fn versions_are_recorded_once_and_listed_newest_first() {
+        let dir = std::env::temp_dir().join(format!("openclawini-history-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let first = record(&dir, entry("{ a: 1 }", 100))
+            .expect("record")
+            .expect("new");
+        assert_eq!(record(&dir, entry("{ a: 1 }", 101)).expect("record"), None);
+        let second = record(&dir, entry("{ a: 2 }", 100))
+            .expect("record")
+            .expect("new");
+        assert_eq!(first.id, "00000000100-000");
+        assert_eq!(second.id, "00000000100-001");
+
+        let restore = Entry {
+            restored_from: Some(&first.id),
+            ..entry("{ a: 1 }", 102)
+        };
+        record(&dir, restore)
+            .expect("record")
+            .expect("restores always recorded");
+
+        let listed = list(&dir).expect("list");
+        assert_eq!(listed.len(), 3);
+        assert_eq!(listed[0].restored_from.as_deref(), Some("00000000100-000"));
+        assert_eq!(read(&dir, &second.id).expect("read"), "{ a: 2 }");
+        assert!(read(&dir, "../settings").is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn retention_is_bounded_by_count_and_age() {
+        let dir =
+            std::env::temp_dir().join(format!("openclawini-retention-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for (index, secs) in [10, 500, 600, 700].into_iter().enumerate() {
+            record(&dir, entry(&format!("{{ v: {index} }}"), secs)).expect("record");
+        }
+
+        prune(&dir, 1_000, 3, 900);
+        let ids = list(&dir)
+            .expect("list")
+            .into_iter()
+            .map(|version| version.id)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec!["00000000700-000", "00000000600-000", "00000000500-000"]
+        );
+
+        prune(&dir, 10_000, 3, 900);
+        assert_eq!(list(&dir).expect("list").len(), 1, "newest is always kept");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
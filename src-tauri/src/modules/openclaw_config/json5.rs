@@ -0,0 +1,859 @@
+//! JSON5 reader plus a small editor that rewrites only the spans it changes,
+//! so comments, key order and formatting elsewhere in the file survive.
+
+use serde_json::{Map, Number, Value};
+
+const INDENT_UNIT: &str = "  ";
+
+#[derive(Debug, Clone)]
+pub struct Document {
+    text: String,
+    root: Node,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    kind: NodeKind,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Debug, Clone)]
+enum NodeKind {
+    Object(Vec<Member>),
+    Array(Vec<Node>),
+    Scalar(Value),
+}
+
+#[derive(Debug, Clone)]
+struct Member {
+    key: String,
+    quoted: bool,
+    key_start: usize,
+    value: Node,
+}
+
+pub fn parse(text: &str) -> Result<Value, String> {
+    Document::parse(text).map(|document| document.value())
+}
+
+impl Document {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let root = Parser::new(text).parse_document()?;
+        Ok(Self {
+            text: text.to_string(),
+            root,
+        })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn value(&self) -> Value {
+        self.root.to_value()
+    }
+
+    /// Sets the value at `path`, creating missing objects along the way.
+    /// Existing values are replaced in place; new keys are appended to the
+    /// innermost existing object using the indentation of its members.
+    pub fn set(&mut self, path: &[&str], value: &Value) -> Result<(), String> {
+        let edits = self.plan_set(path, value)?;
+        self.apply(edits)
+    }
+
+    /// Deletes the member at `path` together with its line when it sits on
+    /// its own line. Returns `false` if there was nothing to remove.
+    pub fn remove(&mut self, path: &[&str]) -> Result<bool, String> {
+        let Some((key, parents)) = path.split_last() else {
+            return Err("cannot remove the root value".to_string());
+        };
+        let mut node = &self.root;
+        for key in parents {
+            let NodeKind::Object(members) = &node.kind else {
+                return Ok(false);
+            };
+            match members.iter().rev().find(|member| member.key == *key) {
+                Some(member) => node = &member.value,
+                None => return Ok(false),
+            }
+        }
+        let NodeKind::Object(members) = &node.kind else {
+            return Ok(false);
+        };
+        let Some(index) = members.iter().rposition(|member| member.key == *key) else {
+            return Ok(false);
+        };
+
+        let edits = self.plan_remove(node, members, index);
+        self.apply(edits)?;
+        Ok(true)
+    }
+
+    fn plan_remove(&self, object: &Node, members: &[Member], index: usize) -> Vec<Edit> {
+        let member = &members[index];
+        if members.len() == 1 {
+            return vec![Edit {
+                start: object.start + 1,
+                end: object.end - 1,
+                text: String::new(),
+            }];
+        }
+
+        let trailing_comma = comma_after(&self.text, member.value.end);
+        let own_line = self.text[line_start(&self.text, member.key_start)..member.key_start]
+            .trim()
+            .is_empty();
+
+        if own_line {
+            let member_end = trailing_comma
+                .map(|position| position + 1)
+                .unwrap_or(member.value.end);
+            let mut end = end_of_line_if_trailing_trivia(&self.text, member_end);
+            if self.text[end..].starts_with('\n') {
+                end += 1;
+            }
+            let mut edits = vec![Edit {
+                start: line_start(&self.text, member.key_start),
+                end,
+                text: String::new(),
+            }];
+            // The previous member now ends the object; drop its comma to
+            // match the style of the removed one.
+            if index == members.len() - 1 && trailing_comma.is_none() {
+                if let Some(comma) = comma_after(&self.text, members[index - 1].value.end) {
+                    edits.push(Edit {
+                        start: comma,
+                        end: comma + 1,
+                        text: String::new(),
+                    });
+                }
+            }
+            return edits;
+        }
+
+        let (start, end) = if index == 0 {
+            (member.key_start, members[1].key_start)
+        } else {
+            let previous_end = members[index - 1].value.end;
+            (
+                comma_after(&self.text, previous_end).unwrap_or(previous_end),
+                member.value.end,
+            )
+        };
+        vec![Edit {
+            start,
+            end,
+            text: String::new(),
+        }]
+    }
+
+    fn plan_set(&self, path: &[&str], value: &Value) -> Result<Vec<Edit>, String> {
+        let mut node = &self.root;
+        for (depth, key) in path.iter().enumerate() {
+            let NodeKind::Object(members) = &node.kind else {
+                return Err(format!(
+                    "config path {} is not an object",
+                    display_path(&path[..depth])
+                ));
+            };
+            match members.iter().rev().find(|member| member.key == *key) {
+                Some(member) => node = &member.value,
+                None => {
+                    let nested = nest_value(&path[depth + 1..], value);
+                    return Ok(self.plan_insert(node, members, key, &nested));
+                }
+            }
+        }
+
+        let indent = line_indent(&self.text, node.start);
+        Ok(vec![Edit {
+            start: node.start,
+            end: node.end,
+            text: render_value(value, &indent),
+        }])
+    }
+
+    fn plan_insert(
+        &self,
+        object: &Node,
+        members: &[Member],
+        key: &str,
+        value: &Value,
+    ) -> Vec<Edit> {
+        let object_indent = line_indent(&self.text, object.start);
+        let quoted = members.is_empty() || members.iter().any(|member| member.quoted);
+        let key_text = render_key(key, quoted);
+
+        let Some(last) = members.last() else {
+            let inner_start = object.start + 1;
+            let inner_end = object.end - 1;
+            let member_indent = format!("{object_indent}{INDENT_UNIT}");
+            let member = format!("{key_text}: {}", render_value(value, &member_indent));
+            return if self.text[inner_start..inner_end].trim().is_empty() {
+                vec![Edit {
+                    start: inner_start,
+                    end: inner_end,
+                    text: format!("\n{member_indent}{member}\n{object_indent}"),
+                }]
+            } else {
+                vec![Edit {
+                    start: inner_start,
+                    end: inner_start,
+                    text: format!("\n{member_indent}{member},"),
+                }]
+            };
+        };
+
+        let first = &members[0];
+        let inline =
+            line_start(&self.text, first.key_start) == line_start(&self.text, object.start);
+        let comma = comma_after(&self.text, last.value.end);
+
+        if inline {
+            let member = format!("{key_text}: {value}");
+            return match comma {
+                Some(position) => vec![Edit {
+                    start: position + 1,
+                    end: position + 1,
+                    text: format!(" {member},"),
+                }],
+                None => vec![Edit {
+                    start: last.value.end,
+                    end: last.value.end,
+                    text: format!(", {member}"),
+                }],
+            };
+        }
+
+        let member_indent = line_indent(&self.text, first.key_start);
+        let member = format!("{key_text}: {}", render_value(value, &member_indent));
+        let anchor = comma.map(|position| position + 1).unwrap_or(last.value.end);
+        let insert_at = end_of_line_if_trailing_trivia(&self.text, anchor);
+
+        let mut edits = vec![Edit {
+            start: insert_at,
+            end: insert_at,
+            text: format!(
+                "\n{member_indent}{member}{}",
+                if comma.is_some() { "," } else { "" }
+            ),
+        }];
+        if comma.is_none() {
+            edits.push(Edit {
+                start: last.value.end,
+                end: last.value.end,
+                text: ",".to_string(),
+            });
+        }
+        edits
+    }
+
+    fn apply(&mut self, mut edits: Vec<Edit>) -> Result<(), String> {
+        edits.sort_by_key(|edit| std::cmp::Reverse(edit.start));
+        let mut text = self.text.clone();
+        for edit in edits {
+            text.replace_range(edit.start..edit.end, &edit.text);
+        }
+        *self = Self::parse(&text)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Edit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl Node {
+    fn to_value(&self) -> Value {
+        match &self.kind {
+            NodeKind::Object(members) => {
+                let mut map = Map::new();
+                for member in members {
+                    map.insert(member.key.clone(), member.value.to_value());
+                }
+                Value::Object(map)
+            }
+            NodeKind::Array(items) => Value::Array(items.iter().map(Node::to_value).collect()),
+            NodeKind::Scalar(value) => value.clone(),
+        }
+    }
+}
+
+fn nest_value(path: &[&str], value: &Value) -> Value {
+    path.iter().rev().fold(value.clone(), |inner, key| {
+        let mut map = Map::new();
+        map.insert((*key).to_string(), inner);
+        Value::Object(map)
+    })
+}
+
+fn display_path(path: &[&str]) -> String {
+    if path.is_empty() {
+        "<root>".to_string()
+    } else {
+        path.join(".")
+    }
+}
+
+fn render_key(key: &str, quoted: bool) -> String {
+    if !quoted && is_identifier(key) {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+fn render_value(value: &Value, indent: &str) -> String {
+    let pretty = serde_json::to_string_pretty(value).unwrap_or_else(|_| "null".to_string());
+    pretty
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 {
+                line.to_string()
+            } else {
+                format!("{indent}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_identifier(value: &str) -> bool {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) if is_identifier_start(first) => chars.all(is_identifier_part),
+        _ => false,
+    }
+}
+
+fn is_identifier_start(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_' || ch == '$'
+}
+
+fn is_identifier_part(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '$'
+}
+
+fn line_start(text: &str, position: usize) -> usize {
+    text[..position]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0)
+}
+
+fn line_indent(text: &str, position: usize) -> String {
+    let start = line_start(text, position);
+    text[start..]
+        .chars()
+        .take_while(|ch| *ch == ' ' || *ch == '\t')
+        .collect()
+}
+
+/// Position of the comma following a value, skipping whitespace and comments.
+fn comma_after(text: &str, position: usize) -> Option<usize> {
+    let mut parser = Parser::new(text);
+    parser.pos = position;
+    parser.skip_trivia().ok()?;
+    if parser.peek() == Some(',') {
+        Some(parser.pos)
+    } else {
+        None
+    }
+}
+
+/// Moves an insertion point past a trailing line comment so the comment stays
+/// with the member it annotates.
+fn end_of_line_if_trailing_trivia(text: &str, position: usize) -> usize {
+    let rest = &text[position..];
+    let line_end = rest.find('\n').unwrap_or(rest.len());
+    let line = rest[..line_end].trim_start();
+    if line.is_empty() || line.starts_with("//") {
+        position + line_end
+    } else {
+        position
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    fn parse_document(&mut self) -> Result<Node, String> {
+        let root = self.parse_value()?;
+        self.skip_trivia()?;
+        if self.pos < self.text.len() {
+            return Err(self.error("unexpected content after the top-level value"));
+        }
+        Ok(root)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        let mut chars = self.text[self.pos..].chars();
+        chars.next();
+        chars.next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn error(&self, message: &str) -> String {
+        let consumed = &self.text[..self.pos];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.len() - line_start(self.text, self.pos) + 1;
+        format!("{message} at line {line}, column {column}")
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), String> {
+        loop {
+            match (self.peek(), self.peek_second()) {
+                (Some(ch), _) if ch.is_whitespace() || ch == '\u{feff}' => {
+                    self.bump();
+                }
+                (Some('/'), Some('/')) => {
+                    while let Some(ch) = self.bump() {
+                        if ch == '\n' {
+                            break;
+                        }
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    self.pos += 2;
+                    match self.text[self.pos..].find("*/") {
+                        Some(offset) => self.pos += offset + 2,
+                        None => return Err(self.error("unterminated block comment")),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Node, String> {
+        self.skip_trivia()?;
+        let start = self.pos;
+        let kind = match self.peek() {
+            Some('{') => self.parse_object()?,
+            Some('[') => self.parse_array()?,
+            Some('"') | Some('\'') => NodeKind::Scalar(Value::String(self.parse_string()?)),
+            Some(ch) if ch.is_ascii_digit() || matches!(ch, '+' | '-' | '.') => {
+                NodeKind::Scalar(self.parse_number()?)
+            }
+            Some(ch) if is_identifier_start(ch) => NodeKind::Scalar(self.parse_literal()?),
+            Some(_) => return Err(self.error("unexpected character")),
+            None => return Err(self.error("unexpected end of input")),
+        };
+        Ok(Node {
+            kind,
+            start,
+            end: self.pos,
+        })
+    }
+
+    fn parse_object(&mut self) -> Result<NodeKind, String> {
+        self.bump();
+        let mut members = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            if self.peek() == Some('}') {
+                self.bump();
+                return Ok(NodeKind::Object(members));
+            }
+
+            let key_start = self.pos;
+            let (key, quoted) = match self.peek() {
+                Some('"') | Some('\'') => (self.parse_string()?, true),
+                Some(ch) if is_identifier_start(ch) => (self.parse_identifier(), false),
+                _ => return Err(self.error("expected an object key")),
+            };
+
+            self.skip_trivia()?;
+            if self.bump() != Some(':') {
+                return Err(self.error("expected ':' after object key"));
+            }
+            let value = self.parse_value()?;
+            members.push(Member {
+                key,
+                quoted,
+                key_start,
+                value,
+            });
+
+            self.skip_trivia()?;
+            match self.bump() {
+                Some(',') => {}
+                Some('}') => return Ok(NodeKind::Object(members)),
+                _ => return Err(self.error("expected ',' or '}' in object")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<NodeKind, String> {
+        self.bump();
+        let mut items = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            if self.peek() == Some(']') {
+                self.bump();
+                return Ok(NodeKind::Array(items));
+            }
+
+            items.push(self.parse_value()?);
+
+            self.skip_trivia()?;
+            match self.bump() {
+                Some(',') => {}
+                Some(']') => return Ok(NodeKind::Array(items)),
+                _ => return Err(self.error("expected ',' or ']' in array")),
+            }
+        }
+    }
+
+    fn parse_identifier(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().map(is_identifier_part).unwrap_or(false) {
+            self.bump();
+        }
+        self.text[start..self.pos].to_string()
+    }
+
+    fn parse_literal(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        match self.parse_identifier().as_str() {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            // JSON has no non-finite numbers; they read as null, and the
+            // document keeps their text unless the value is replaced.
+            "null" | "Infinity" | "NaN" => Ok(Value::Null),
+            _ => {
+                self.pos = start;
+                Err(self.error("unexpected identifier"))
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        let quote = self.bump().unwrap_or('"');
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(self.error("unterminated string")),
+                Some(ch) if ch == quote => return Ok(value),
+                Some('\n') | Some('\r') => return Err(self.error("unescaped line break in string")),
+                Some('\\') => self.parse_escape(&mut value)?,
+                Some(ch) => value.push(ch),
+            }
+        }
+    }
+
+    fn parse_escape(&mut self, value: &mut String) -> Result<(), String> {
+        match self.bump() {
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            Some('r') => value.push('\r'),
+            Some('b') => value.push('\u{8}'),
+            Some('f') => value.push('\u{c}'),
+            Some('v') => value.push('\u{b}'),
+            Some('0') if !self.peek().map(|ch| ch.is_ascii_digit()).unwrap_or(false) => {
+                value.push('\0')
+            }
+            Some('x') => {
+                let code = self.parse_hex_digits(2)?;
+                value.push(char::from_u32(code).ok_or_else(|| self.error("invalid \\x escape"))?);
+            }
+            Some('u') => {
+                let code = self.parse_hex_digits(4)?;
+                let ch = if (0xD800..0xDC00).contains(&code) {
+                    if self.bump() != Some('\\') || self.bump() != Some('u') {
+                        return Err(self.error("unpaired surrogate in \\u escape"));
+                    }
+                    let low = self.parse_hex_digits(4)?;
+                    char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00)))
+                } else {
+                    char::from_u32(code)
+                };
+                value.push(ch.ok_or_else(|| self.error("invalid \\u escape"))?);
+            }
+            Some('\r') => {
+                if self.peek() == Some('\n') {
+                    self.bump();
+                }
+            }
+            Some('\n') | Some('\u{2028}') | Some('\u{2029}') => {}
+            Some(ch) if ch.is_ascii_digit() => {
+                return Err(self.error("octal escapes are not allowed"))
+            }
+            Some(ch) => value.push(ch),
+            None => return Err(self.error("unterminated string")),
+        }
+        Ok(())
+    }
+
+    fn parse_hex_digits(&mut self, count: usize) -> Result<u32, String> {
+        let start = self.pos;
+        for _ in 0..count {
+            match self.bump() {
+                Some(ch) if ch.is_ascii_hexdigit() => {}
+                _ => return Err(self.error("invalid hex escape")),
+            }
+        }
+        u32::from_str_radix(&self.text[start..self.pos], 16)
+            .map_err(|_| self.error("invalid hex escape"))
+    }
+
+    fn parse_number(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        let negative = match self.peek() {
+            Some('-') => {
+                self.bump();
+                true
+            }
+            Some('+') => {
+                self.bump();
+                false
+            }
+            _ => false,
+        };
+
+        if self.peek().map(is_identifier_start).unwrap_or(false) {
+            return match self.parse_identifier().as_str() {
+                "Infinity" | "NaN" => Ok(Value::Null),
+                _ => {
+                    self.pos = start;
+                    Err(self.error("unexpected identifier"))
+                }
+            };
+        }
+
+        if self.peek() == Some('0') && matches!(self.peek_second(), Some('x') | Some('X')) {
+            self.pos += 2;
+            let digits_start = self.pos;
+            while self
+                .peek()
+                .map(|ch| ch.is_ascii_hexdigit())
+                .unwrap_or(false)
+            {
+                self.bump();
+            }
+            let magnitude = i64::from_str_radix(&self.text[digits_start..self.pos], 16)
+                .map_err(|_| self.error("invalid hexadecimal number"))?;
+            return Ok(Value::Number(Number::from(if negative {
+                -magnitude
+            } else {
+                magnitude
+            })));
+        }
+
+        let digits_start = self.pos;
+        let mut is_float = false;
+        while let Some(ch) = self.peek() {
+            match ch {
+                '0'..='9' => {}
+                '.' => is_float = true,
+                'e' | 'E' => {
+                    is_float = true;
+                    if matches!(self.peek_second(), Some('+') | Some('-')) {
+                        self.bump();
+                    }
+                }
+                _ => break,
+            }
+            self.bump();
+        }
+
+        let digits = &self.text[digits_start..self.pos];
+        let number = if is_float {
+            digits
+                .parse::<f64>()
+                .ok()
+                .map(|value| if negative { -value } else { value })
+                .and_then(Number::from_f64)
+        } else if negative {
+            format!("-{digits}").parse::<i64>().ok().map(Number::from)
+        } else {
+            digits.parse::<u64>().ok().map(Number::from)
+        };
+
+        number
+            .map(Value::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const HAND_EDITED: &str = r#"// OpenClaw config, see docs
+{
+  agent: {
+    model: 'openai/gpt-4o', // switched back after the outage
+    workspace: "~/clawd",
+  },
+  /* gateway settings */
+  gateway: { port: 0x4965, bind: "loopback", },
+}
+"#;
+
+    #[test]
+    fn parses_comments_trailing_commas_and_unquoted_keys() {
+        let value = parse(HAND_EDITED).expect("json5 parses");
+        assert_eq!(
+            value,
+            json!({
+                "agent": { "model": "openai/gpt-4o", "workspace": "~/clawd" },
+                "gateway": { "port": 18789, "bind": "loopback" }
+            })
+        );
+    }
+
+    #[test]
+    fn parses_json5_scalars() {
+        let value = parse("[+1, -2, .5, 5., 1e3, 'it\\'s', \"\\u00e9\\x41\", 'a\\\nb', null]")
+            .expect("scalars parse");
+        assert_eq!(
+            value,
+            json!([1, -2, 0.5, 5.0, 1000.0, "it's", "éA", "ab", null])
+        );
+    }
+
+    #[test]
+    fn reports_position_of_syntax_errors() {
+        let err = parse("{\n  agent: {\n    model: \n  }\n}").expect_err("missing value");
+        assert!(err.contains("line 4"), "{err}");
+        assert!(parse("{ a: -Infinite }").is_err());
+        assert!(parse("{ a: 1 } extra").is_err());
+    }
+
+    #[test]
+    fn non_finite_numbers_read_as_null_and_keep_their_text() {
+        let text = "{ a: Infinity, b: -Infinity, c: +NaN, d: 1 }";
+        assert_eq!(
+            parse(text).expect("non-finite numbers parse"),
+            json!({ "a": null, "b": null, "c": null, "d": 1 })
+        );
+
+        let mut document = Document::parse(text).expect("parses");
+        document.set(&["d"], &json!(2)).expect("set succeeds");
+        assert_eq!(
+            document.text(),
+            "{ a: Infinity, b: -Infinity, c: +NaN, d: 2 }"
+        );
+    }
+
+    #[test]
+    fn replacing_a_value_keeps_comments_and_formatting() {
+        let mut document = Document::parse(HAND_EDITED).expect("parses");
+        document
+            .set(&["agent", "model"], &json!("anthropic/claude-opus-4-6"))
+            .expect("set succeeds");
+
+        let expected = HAND_EDITED.replace("'openai/gpt-4o'", "\"anthropic/claude-opus-4-6\"");
+        assert_eq!(document.text(), expected);
+    }
+
+    #[test]
+    fn inserting_a_key_follows_member_indentation() {
+        let mut document = Document::parse(HAND_EDITED).expect("parses");
+        document
+            .set(&["agent", "thinking"], &json!("high"))
+            .expect("set succeeds");
+
+        assert!(document
+            .text()
+            .contains("    workspace: \"~/clawd\",\n    thinking: \"high\",\n  },"));
+        assert_eq!(document.value()["agent"]["thinking"], "high");
+        assert!(document
+            .text()
+            .starts_with("// OpenClaw config, see docs\n"));
+    }
+
+    #[test]
+    fn inserting_after_member_without_trailing_comma_keeps_line_comment() {
+        let mut document =
+            Document::parse("{\n  \"agent\": {\n    \"model\": \"a\" // pinned\n  }\n}\n")
+                .expect("parses");
+        document
+            .set(&["agent", "workspace"], &json!("~/w"))
+            .expect("set succeeds");
+
+        assert_eq!(
+            document.text(),
+            "{\n  \"agent\": {\n    \"model\": \"a\", // pinned\n    \"workspace\": \"~/w\"\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn missing_parents_are_created() {
+        let mut document = Document::parse("{}").expect("parses");
+        document
+            .set(&["agent", "model"], &json!("ollama/llama3.2"))
+            .expect("set succeeds");
+        assert_eq!(
+            document.text(),
+            "{\n  \"agent\": {\n    \"model\": \"ollama/llama3.2\"\n  }\n}"
+        );
+
+        let mut inline = Document::parse("{ gateway: { port: 1 } }").expect("parses");
+        inline
+            .set(&["gateway", "bind"], &json!("loopback"))
+            .expect("set succeeds");
+        assert_eq!(
+            inline.text(),
+            "{ gateway: { port: 1, bind: \"loopback\" } }"
+        );
+    }
+
+    #[test]
+    fn setting_through_a_non_object_fails() {
+        let mut document = Document::parse("{ agent: \"x\" }").expect("parses");
+        assert!(document.set(&["agent", "model"], &json!("y")).is_err());
+    }
+
+    #[test]
+    fn removing_members_keeps_neighbours_and_comments() {
+        let mut document = Document::parse(HAND_EDITED).expect("parses");
+        assert!(document
+            .remove(&["agent", "workspace"])
+            .expect("remove succeeds"));
+        assert!(document
+            .remove(&["gateway", "bind"])
+            .expect("remove succeeds"));
+        assert!(!document.remove(&["agent", "missing"]).expect("no-op"));
+
+        assert_eq!(
+            document.text(),
+            r#"// OpenClaw config, see docs
+{
+  agent: {
+    model: 'openai/gpt-4o', // switched back after the outage
+  },
+  /* gateway settings */
+  gateway: { port: 0x4965, },
+}
+"#
+        );
+
+        let mut last = Document::parse("{\n  \"a\": 1, // keep\n  \"b\": 2\n}").expect("parses");
+        last.remove(&["b"]).expect("remove succeeds");
+        assert_eq!(last.text(), "{\n  \"a\": 1 // keep\n}");
+    }
+}
@@ -0,0 +1,492 @@
+mod diff;
+mod editor;
+mod history;
+pub mod json5;
+pub mod pointer;
+mod schema;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::modules::common;
+
+pub use diff::{diff, ConfigChange};
+pub use editor::{redact_secrets, ConfigNode, SecretValue};
+pub use history::ConfigVersion;
+pub use pointer::ConfigPatch;
+pub use schema::FieldError;
+
+const CONFIG_BACKUPS_KEPT: usize = 10;
+/// The subset of OpenClaw's config schema the editor validates against.
+/// OpenClaw builds its schema in code and publishes no schema file, so
+/// sections this subset does not describe are accepted as they are.
+const BUNDLED_SCHEMA: &str = include_str!("../../../resources/openclaw.schema.json");
+
+/// The OpenClaw config as the editor sees it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenClawConfigView {
+    pub path: String,
+    pub exists: bool,
+    pub tree: ConfigNode,
+    pub schema: SchemaSource,
+    /// Every schema violation in the current file.
+    pub errors: Vec<FieldError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaSource {
+    /// Always `bundled`.
+    pub name: String,
+    pub location: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigPatchResult {
+    pub applied: bool,
+    /// Why the patches were not applied: patches that do not fit the
+    /// config, or schema violations they would introduce.
+    pub errors: Vec<FieldError>,
+    /// The config after the patches, or unchanged when not applied.
+    pub config: OpenClawConfigView,
+}
+
+/// Reads `~/.openclaw/openclaw.json` as a tree annotated from the schema,
+/// with secret-looking values masked.
+#[tauri::command]
+pub fn get_openclaw_config() -> Result<OpenClawConfigView, String> {
+    let (schema, source) = load_schema();
+    let path = common::openclaw_config_path()?;
+    let raw = read_raw_config(&path)?;
+    let value = parse_document(raw.as_deref(), &path)?.value();
+    Ok(view(&path, raw.is_some(), &value, &schema, source))
+}
+
+/// Applies JSON Patch `add`/`replace`/`remove` operations to the OpenClaw
+/// config. Nothing is written when a patch does not fit, when the result
+/// has schema errors the current file did not have, or with `dryRun`. The
+/// checks run against the file as it is when written, so an outside edit
+/// made meanwhile is validated too.
+#[tauri::command]
+pub fn apply_config_patch(
+    app: AppHandle,
+    patches: Vec<ConfigPatch>,
+    dry_run: Option<bool>,
+) -> Result<ConfigPatchResult, String> {
+    let (schema, source) = load_schema();
+    let path = common::openclaw_config_path()?;
+    let reason = match patches.len() {
+        1 => format!("edited {}", patches[0].path),
+        count => format!("edited {count} settings"),
+    };
+
+    let mut outcome = None;
+    let applied = edit_config_if(&app, &reason, |document| {
+        let current = document.value();
+        let next = match editor::patched(&current, &patches) {
+            Ok(next) => next,
+            Err(errors) => {
+                outcome = Some((current, errors));
+                return Ok(false);
+            }
+        };
+        let introduced = editor::introduced(&schema.validate(&current), &schema.validate(&next));
+        if !introduced.is_empty() {
+            outcome = Some((current, introduced));
+            return Ok(false);
+        }
+        let write = !dry_run.unwrap_or(false) && !patches.is_empty();
+        if write {
+            editor::apply_to_document(document, &patches)?;
+        }
+        outcome = Some((next, Vec::new()));
+        Ok(write)
+    })?;
+
+    let (value, errors) = outcome.ok_or_else(|| "config patch was not evaluated".to_string())?;
+    let exists = applied || path.exists();
+    Ok(ConfigPatchResult {
+        applied,
+        errors,
+        config: view(&path, exists, &value, &schema, source),
+    })
+}
+
+fn view(
+    path: &Path,
+    exists: bool,
+    value: &Value,
+    schema: &schema::Schema,
+    source: SchemaSource,
+) -> OpenClawConfigView {
+    OpenClawConfigView {
+        path: path.display().to_string(),
+        exists,
+        tree: editor::tree(value, schema),
+        schema: source,
+        errors: schema.validate(value),
+    }
+}
+
+fn load_schema() -> (schema::Schema, SchemaSource) {
+    (
+        schema::Schema::parse(BUNDLED_SCHEMA).expect("bundled schema is valid"),
+        SchemaSource {
+            name: "bundled".to_string(),
+            location: "resources/openclaw.schema.json".to_string(),
+        },
+    )
+}
+
+fn parse_document(raw: Option<&str>, path: &Path) -> Result<json5::Document, String> {
+    json5::Document::parse(raw.unwrap_or("{}\n"))
+        .map_err(|e| format!("invalid json5 in {}: {e}", path.display()))
+}
+
+/// Reads `~/.openclaw/openclaw.json` as JSON5. Returns `None` if the file
+/// does not exist yet.
+pub fn read_config() -> Result<Option<Value>, String> {
+    let path = common::openclaw_config_path()?;
+    let Some(raw) = read_raw_config(&path)? else {
+        return Ok(None);
+    };
+    json5::parse(&raw)
+        .map(Some)
+        .map_err(|e| format!("invalid json5 in {}: {e}", path.display()))
+}
+
+/// Applies `edit` to the OpenClaw config text and writes the result back.
+/// Only the spans touched by the edit change, so comments and formatting in
+/// a hand-edited file survive. The previous file is backed up first, the new
+/// one is swapped in atomically and recorded in the config history with
+/// `reason`.
+pub fn edit_config<F>(app: &AppHandle, reason: &str, edit: F) -> Result<(), String>
+where
+    F: FnOnce(&mut json5::Document) -> Result<(), String>,
+{
+    edit_config_if(app, reason, |document| edit(document).map(|_| true)).map(|_| ())
+}
+
+/// Like [`edit_config`], but nothing is written when `edit` returns
+/// `Ok(false)`. `edit` sees the file as it is right before the write, so
+/// checks made there hold for what is written. Returns whether it was.
+fn edit_config_if<F>(app: &AppHandle, reason: &str, edit: F) -> Result<bool, String>
+where
+    F: FnOnce(&mut json5::Document) -> Result<bool, String>,
+{
+    let path = common::openclaw_config_path()?;
+    let existing = read_raw_config(&path)?;
+    let mut document = parse_document(existing.as_deref(), &path)?;
+    if !edit(&mut document)? {
+        return Ok(false);
+    }
+    commit(app, &path, existing.as_deref(), document.text(), reason)?;
+    Ok(true)
+}
+
+/// Replaces the whole OpenClaw config with `text`, backed up and recorded
+/// in the history like any other write.
+pub fn replace_config(app: &AppHandle, text: &str, reason: &str) -> Result<(), String> {
+    let path = common::openclaw_config_path()?;
+    if !parse_document(Some(text), &path)?.value().is_object() {
+        return Err("the OpenClaw config must be an object".to_string());
+    }
+    let existing = read_raw_config(&path)?;
+    commit(app, &path, existing.as_deref(), text, reason)
+}
+
+/// The text of `openclaw.json`, or `None` when it does not exist yet.
+pub fn read_config_text() -> Result<Option<String>, String> {
+    read_raw_config(&common::openclaw_config_path()?)
+}
+
+/// Returns `text` with its secret strings taken out, together with the
+/// removed values and the paths they were found at.
+pub fn strip_secrets(text: &str) -> Result<(String, Vec<SecretValue>), String> {
+    let mut document =
+        json5::Document::parse(text).map_err(|e| format!("invalid json5 config: {e}"))?;
+    let secrets = editor::strip_secrets(&mut document)?;
+    Ok((document.text().to_string(), secrets))
+}
+
+/// `text` with `secrets` put back where [`strip_secrets`] found them.
+pub fn restore_secrets(text: &str, secrets: &[SecretValue]) -> Result<String, String> {
+    let mut document =
+        json5::Document::parse(text).map_err(|e| format!("invalid json5 config: {e}"))?;
+    editor::restore_secrets(&mut document, secrets)?;
+    Ok(document.text().to_string())
+}
+
+/// Schema errors in `next` that `current` does not have, checked against
+/// the same schema as the editor.
+pub fn introduced_errors(current: &Value, next: &Value) -> Vec<FieldError> {
+    let (schema, _) = load_schema();
+    editor::introduced(&schema.validate(current), &schema.validate(next))
+}
+
+/// Recorded versions of `openclaw.json`, newest first.
+#[tauri::command]
+pub fn list_config_versions(app: AppHandle) -> Result<Vec<ConfigVersion>, String> {
+    history::list(&history_dir(&app)?)
+}
+
+/// Changes from version `from` to version `to`, or to the current file when
+/// `to` is omitted. Secret-looking values are masked.
+#[tauri::command]
+pub fn diff_config_versions(
+    app: AppHandle,
+    from: String,
+    to: Option<String>,
+) -> Result<Vec<ConfigChange>, String> {
+    let dir = history_dir(&app)?;
+    let parse_version = |id: &str| {
+        json5::parse(&history::read(&dir, id)?)
+            .map_err(|e| format!("config version {id} is not valid json5: {e}"))
+    };
+    let before = parse_version(&from)?;
+    let after = match to {
+        Some(id) => parse_version(&id)?,
+        None => read_config()?.unwrap_or_else(|| Value::Object(Default::default())),
+    };
+    Ok(diff(&before, &after))
+}
+
+/// Writes version `id` back to `openclaw.json`. The restore is recorded as
+/// a new version, so it can be undone the same way. It is recorded before
+/// the file is written, so a restore that reports success is always in the
+/// history, and dropped again if the write fails.
+#[tauri::command]
+pub fn restore_config_version(app: AppHandle, id: String) -> Result<ConfigVersion, String> {
+    let dir = history_dir(&app)?;
+    let text = history::read(&dir, &id)?;
+    json5::parse(&text).map_err(|e| format!("config version {id} is not valid json5: {e}"))?;
+
+    let path = common::openclaw_config_path()?;
+    let existing = read_raw_config(&path)?;
+    let version = record_history(
+        &app,
+        existing.as_deref(),
+        &text,
+        &format!("restored {id}"),
+        Some(&id),
+    )?
+    .ok_or_else(|| "restore was not recorded".to_string())?;
+    if let Err(err) = write_config(&path, existing.is_some(), &text) {
+        history::remove(&dir, &version.id);
+        return Err(err);
+    }
+    Ok(version)
+}
+
+fn commit(
+    app: &AppHandle,
+    path: &Path,
+    previous: Option<&str>,
+    text: &str,
+    reason: &str,
+) -> Result<(), String> {
+    write_config(path, previous.is_some(), text)?;
+    if let Err(err) = record_history(app, previous, text, reason, None) {
+        log::warn!("failed to record config history: {err}");
+    }
+    Ok(())
+}
+
+fn write_config(path: &Path, exists: bool, text: &str) -> Result<(), String> {
+    if exists {
+        backup_config(path)?;
+    }
+    common::write_file_atomic(path, text.as_bytes())?;
+    if let Ok(mut last) = last_written().lock() {
+        *last = Some(text.to_string());
+    }
+    Ok(())
+}
+
+/// Text of the last `openclaw.json` the app wrote itself.
+fn last_written() -> &'static Mutex<Option<String>> {
+    static LAST_WRITTEN: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+    LAST_WRITTEN.get_or_init(|| Mutex::new(None))
+}
+
+/// Whether `text` is what the app last wrote to `openclaw.json`, so the
+/// config watcher can tell its own writes from outside edits.
+pub fn is_own_write(text: &str) -> bool {
+    last_written()
+        .lock()
+        .map(|last| last.as_deref() == Some(text))
+        .unwrap_or(false)
+}
+
+/// Records `text`. The first time, the file as it was before the app
+/// changed it is recorded too, so the first change can be undone.
+fn record_history(
+    app: &AppHandle,
+    previous: Option<&str>,
+    text: &str,
+    reason: &str,
+    restored_from: Option<&str>,
+) -> Result<Option<ConfigVersion>, String> {
+    let dir = history_dir(app)?;
+    let now_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let created_at = common::iso_utc_now();
+    let entry = |text, reason| history::Entry {
+        text,
+        reason,
+        restored_from: None,
+        now_secs,
+        created_at: &created_at,
+    };
+
+    if let Some(previous) = previous {
+        if history::is_empty(&dir)? {
+            history::record(&dir, entry(previous, "before the first recorded change"))?;
+        }
+    }
+    history::record(
+        &dir,
+        history::Entry {
+            restored_from,
+            ..entry(text, reason)
+        },
+    )
+}
+
+fn history_dir(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(common::config_history_dir(&common::app_data_dir(app)?))
+}
+
+fn read_raw_config(path: &Path) -> Result<Option<String>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    std::fs::read_to_string(path)
+        .map(Some)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))
+}
+
+fn backup_config(config_path: &Path) -> Result<(), String> {
+    let parent = config_path
+        .parent()
+        .ok_or_else(|| format!("{} has no parent directory", config_path.display()))?;
+    let file_name = config_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stamp = common::iso_utc_now().replace(':', "");
+    // Several writes within one second each keep their own backup.
+    let backup_path = (0..)
+        .map(|seq| parent.join(format!("{file_name}.{stamp}-{seq:03}.bak")))
+        .find(|path| !path.exists())
+        .expect("some sequence number is free");
+    std::fs::copy(config_path, &backup_path).map_err(|e| {
+        format!(
+            "failed to back up {} to {}: {e}",
+            config_path.display(),
+            backup_path.display()
+        )
+    })?;
+
+    prune_backups(parent, &file_name, CONFIG_BACKUPS_KEPT);
+    Ok(())
+}
+
+fn prune_backups(dir: &Path, file_name: &str, keep: usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let prefix = format!("{file_name}.");
+    let mut backups = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(&prefix) && name.ends_with(".bak"))
+        .collect::<Vec<_>>();
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(keep);
+    for name in backups.into_iter().take(excess) {
+        let _ = std::fs::remove_file(dir.join(name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn own_writes_are_remembered() {
+        let dir = std::env::temp_dir().join(format!("openclawini-own-{}", std::process::id()));
+        let path = dir.join("openclaw.json");
+        let text = format!("{{ gateway: {{ port: {} }} }}\n", std::process::id());
+
+        assert!(!is_own_write(&text));
+        write_config(&path, false, &text).expect("write config");
+        assert!(is_own_write(&text));
+        assert!(!is_own_write("{ gateway: { port: 1 } }\n"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn backups_within_one_second_are_all_kept() {
+        let dir =
+            std::env::temp_dir().join(format!("openclawini-backup-seq-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create backup dir");
+        let path = dir.join("openclaw.json");
+        for text in ["{ a: 1 }", "{ a: 2 }", "{ a: 3 }"] {
+            std::fs::write(&path, text).expect("write config");
+            backup_config(&path).expect("back up");
+        }
+
+        let mut backups = std::fs::read_dir(&dir)
+            .expect("read dir")
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "bak"))
+            .map(|path| std::fs::read_to_string(path).expect("read backup"))
+            .collect::<Vec<_>>();
+        backups.sort();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(backups, vec!["{ a: 1 }", "{ a: 2 }", "{ a: 3 }"]);
+    }
+
+    #[test]
+    fn backups_are_pruned_to_newest() {
+        let dir = std::env::temp_dir().join(format!("openclawini-backups-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create backup dir");
+        for stamp in [
+            "2026-01-01T000000Z",
+            "2026-01-02T000000Z",
+            "2026-01-03T000000Z",
+        ] {
+            std::fs::write(dir.join(format!("openclaw.json.{stamp}.bak")), b"{}")
+                .expect("write backup");
+        }
+        std::fs::write(dir.join("openclaw.json"), b"{}").expect("write config");
+
+        prune_backups(&dir, "openclaw.json", 2);
+
+        let mut remaining = std::fs::read_dir(&dir)
+            .expect("read dir")
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        remaining.sort();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            remaining,
+            vec![
+                "openclaw.json",
+                "openclaw.json.2026-01-02T000000Z.bak",
+                "openclaw.json.2026-01-03T000000Z.bak",
+            ]
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! JSON pointers (RFC 6901) and the `add`/`replace`/`remove` operations of
+//! JSON Patch (RFC 6902) over `serde_json` values.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigPatch {
+    /// `add`, `replace` or `remove`.
+    pub op: String,
+    /// JSON pointer, e.g. `/gateway/port`.
+    pub path: String,
+    /// Required for `add` and `replace`.
+    #[serde(default)]
+    pub value: Option<Value>,
+}
+
+/// Splits a pointer into unescaped segments; `""` is the whole document.
+pub fn parse(pointer: &str) -> Result<Vec<String>, String> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(format!("{pointer:?} is not a JSON pointer"));
+    };
+    Ok(rest
+        .split('/')
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+/// `path` extended by one member `key`, escaped.
+pub fn child(path: &str, key: &str) -> String {
+    format!("{path}/{}", key.replace('~', "~0").replace('/', "~1"))
+}
+
+pub fn apply(document: &mut Value, patch: &ConfigPatch) -> Result<(), String> {
+    let segments = parse(&patch.path)?;
+    let Some((last, parents)) = segments.split_last() else {
+        return Err("the whole config cannot be patched at once".to_string());
+    };
+    let parent = parents.iter().try_fold(&mut *document, |node, segment| {
+        match node {
+            Value::Object(members) => members.get_mut(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get_mut(i)),
+            _ => None,
+        }
+        .ok_or_else(|| format!("{} does not exist", patch.path))
+    })?;
+    let value = || {
+        patch
+            .value
+            .clone()
+            .ok_or_else(|| format!("{} needs a value", patch.op))
+    };
+
+    match (patch.op.as_str(), parent) {
+        ("add", Value::Object(members)) => {
+            members.insert(last.clone(), value()?);
+        }
+        ("replace", Value::Object(members)) => match members.get_mut(last) {
+            Some(slot) => *slot = value()?,
+            None => return Err(format!("{} does not exist", patch.path)),
+        },
+        ("remove", Value::Object(members)) => {
+            if members.shift_remove(last).is_none() {
+                return Err(format!("{} does not exist", patch.path));
+            }
+        }
+        (op @ ("add" | "replace" | "remove"), Value::Array(items)) => {
+            let index = match last.as_str() {
+                "-" if op == "add" => items.len(),
+                other => other
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|index| *index < items.len() || (op == "add" && *index == items.len()))
+                    .ok_or_else(|| format!("{} is not a valid array index", patch.path))?,
+            };
+            match op {
+                "add" => items.insert(index, value()?),
+                "replace" => items[index] = value()?,
+                _ => {
+                    items.remove(index);
+                }
+            }
+        }
+        ("add" | "replace" | "remove", _) => {
+            return Err(format!("{} is not inside an object or array", patch.path))
+        }
+        (other, _) => {
+            return Err(format!(
+                "unsupported patch op {other:?}; use add, replace or remove"
+            ))
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patch(op: &str, path: &str, value: Option<Value>) -> ConfigPatch {
+        ConfigPatch {
+            op: op.to_string(),
+            path: path.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn pointers_round_trip_escapes() {
+        assert_eq!(parse("/a~1b/c~0d").expect("pointer"), vec!["a/b", "c~d"]);
+        assert_eq!(child("/env", "a/b~c"), "/env/a~1b~0c");
+        assert!(parse("gateway").is_err());
+    }
+
+    #[test]
+    fn patches_edit_objects_and_arrays() {
+        let mut config = json!({
+            "gateway": { "port": 18789 },
+            "agent": { "model": { "primary": "a/b", "fallbacks": ["c/d"] } }
+        });
+        for item in [
+            patch("replace", "/gateway/port", Some(json!(18800))),
+            patch("add", "/gateway/mode", Some(json!("local"))),
+            patch("add", "/agent/model/fallbacks/-", Some(json!("e/f"))),
+            patch("add", "/agent/model/fallbacks/0", Some(json!("g/h"))),
+            patch("remove", "/agent/model/fallbacks/1", None),
+        ] {
+            apply(&mut config, &item).expect("patch applies");
+        }
+        assert_eq!(
+            config,
+            json!({
+                "gateway": { "port": 18800, "mode": "local" },
+                "agent": { "model": { "primary": "a/b", "fallbacks": ["g/h", "e/f"] } }
+            })
+        );
+
+        assert!(apply(
+            &mut config,
+            &patch("replace", "/gateway/bind", Some(json!("lan")))
+        )
+        .is_err());
+        assert!(apply(&mut config, &patch("add", "/tools/web", Some(json!({})))).is_err());
+        assert!(apply(&mut config, &patch("move", "/gateway/port", None)).is_err());
+        assert!(apply(&mut config, &patch("add", "/gateway/port", None)).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! The part of JSON Schema (draft 7) that config schemas use: `type`,
+//! `properties`, `required`, `additionalProperties`, `items`, `enum`,
+//! `const`, numeric and length bounds, `anyOf`/`oneOf` and local `$ref`s.
+//! Other keywords are ignored.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::pointer;
+
+const MAX_REF_DEPTH: usize = 32;
+
+/// A problem with one value, addressed by JSON pointer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FieldError {
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Schema {
+    root: Value,
+}
+
+impl Schema {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let root = serde_json::from_str::<Value>(raw).map_err(|e| e.to_string())?;
+        if !root.is_object() {
+            return Err("schema must be an object".to_string());
+        }
+        Ok(Self { root })
+    }
+
+    pub fn validate(&self, value: &Value) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        self.check(&self.root, value, "", &mut errors);
+        errors
+    }
+
+    /// The schema describing the value at `path`, following `properties`,
+    /// `additionalProperties` and `items`. Inside `anyOf`, the first branch
+    /// that describes the path is used.
+    pub fn at(&self, path: &[String]) -> Option<&Value> {
+        let mut schema = self.resolve(&self.root);
+        for segment in path {
+            schema = self.child(schema, segment)?;
+        }
+        Some(schema)
+    }
+
+    pub fn description(&self, path: &[String]) -> Option<String> {
+        let schema = self.at(path)?;
+        schema
+            .get("description")
+            .or_else(|| schema.get("title"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    }
+
+    fn child<'a>(&'a self, schema: &'a Value, segment: &str) -> Option<&'a Value> {
+        if let Some(found) = schema
+            .get("properties")
+            .and_then(|properties| properties.get(segment))
+            .or_else(|| schema.get("additionalProperties").filter(|v| v.is_object()))
+            .or_else(|| {
+                segment
+                    .parse::<usize>()
+                    .ok()
+                    .and(schema.get("items").filter(|v| v.is_object()))
+            })
+        {
+            return Some(self.resolve(found));
+        }
+        ["anyOf", "oneOf"]
+            .iter()
+            .filter_map(|keyword| schema.get(*keyword).and_then(Value::as_array))
+            .flatten()
+            .find_map(|branch| self.child(self.resolve(branch), segment))
+    }
+
+    /// Follows `$ref`s to definitions in the same document.
+    fn resolve<'a>(&'a self, mut schema: &'a Value) -> &'a Value {
+        for _ in 0..MAX_REF_DEPTH {
+            let Some(reference) = schema.get("$ref").and_then(Value::as_str) else {
+                break;
+            };
+            match reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.root.pointer(pointer))
+            {
+                Some(target) => schema = target,
+                None => break,
+            }
+        }
+        schema
+    }
+
+    fn check(&self, schema: &Value, value: &Value, path: &str, errors: &mut Vec<FieldError>) {
+        let schema = self.resolve(schema);
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            errors.push(error(
+                path,
+                format!("schema reference {reference} not found"),
+            ));
+            return;
+        }
+        let start = errors.len();
+
+        if let Some(expected) = schema.get("type") {
+            let types = match expected {
+                Value::String(name) => vec![name.as_str()],
+                Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !types.is_empty() && !types.iter().any(|name| has_type(value, name)) {
+                errors.push(error(
+                    path,
+                    format!(
+                        "expected {}, found {}",
+                        types.join(" or "),
+                        type_name(value)
+                    ),
+                ));
+                return;
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                let listed = allowed
+                    .iter()
+                    .map(Value::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                errors.push(error(path, format!("must be one of {listed}")));
+            }
+        }
+        if let Some(expected) = schema.get("const") {
+            if expected != value {
+                errors.push(error(path, format!("must be {expected}")));
+            }
+        }
+
+        match value {
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or_default();
+                if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+                    if number < minimum {
+                        errors.push(error(path, format!("must be at least {minimum}")));
+                    }
+                }
+                if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+                    if number > maximum {
+                        errors.push(error(path, format!("must be at most {maximum}")));
+                    }
+                }
+            }
+            Value::String(text) => {
+                let length = text.chars().count() as u64;
+                if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                    if length < min {
+                        errors.push(error(path, format!("must be at least {min} characters")));
+                    }
+                }
+                if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                    if length > max {
+                        errors.push(error(path, format!("must be at most {max} characters")));
+                    }
+                }
+            }
+            Value::Array(items) => {
+                let length = items.len() as u64;
+                if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                    if length < min {
+                        errors.push(error(path, format!("must have at least {min} items")));
+                    }
+                }
+                if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                    if length > max {
+                        errors.push(error(path, format!("must have at most {max} items")));
+                    }
+                }
+                if let Some(item_schema) = schema.get("items").filter(|v| v.is_object()) {
+                    for (index, item) in items.iter().enumerate() {
+                        self.check(item_schema, item, &format!("{path}/{index}"), errors);
+                    }
+                }
+            }
+            Value::Object(members) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+                for name in schema
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                {
+                    if !members.contains_key(name) {
+                        errors.push(error(
+                            &pointer::child(path, name),
+                            "is required".to_string(),
+                        ));
+                    }
+                }
+                for (key, member) in members {
+                    let member_path = pointer::child(path, key);
+                    match properties.and_then(|properties| properties.get(key)) {
+                        Some(member_schema) => {
+                            self.check(member_schema, member, &member_path, errors)
+                        }
+                        None => match schema.get("additionalProperties") {
+                            Some(Value::Bool(false)) => errors
+                                .push(error(&member_path, "is not a known setting".to_string())),
+                            Some(extra) if extra.is_object() => {
+                                self.check(extra, member, &member_path, errors)
+                            }
+                            _ => {}
+                        },
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if errors.len() > start {
+            return;
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            let Some(branches) = schema.get(keyword).and_then(Value::as_array) else {
+                continue;
+            };
+            let attempts = branches
+                .iter()
+                .map(|branch| {
+                    let mut branch_errors = Vec::new();
+                    self.check(branch, value, path, &mut branch_errors);
+                    branch_errors
+                })
+                .collect::<Vec<_>>();
+            if attempts.iter().any(Vec::is_empty) {
+                continue;
+            }
+            // Report the branch that got furthest: the one whose errors are
+            // deepest in the value, which is usually the intended shape.
+            let closest = attempts
+                .into_iter()
+                .max_by_key(|attempt| attempt.iter().map(|e| e.path.len()).max().unwrap_or(0))
+                .unwrap_or_default();
+            if closest.iter().all(|e| e.path == path) {
+                errors.push(error(path, "does not match any allowed form".to_string()));
+            } else {
+                errors.extend(closest);
+            }
+        }
+    }
+}
+
+fn error(path: &str, message: String) -> FieldError {
+    FieldError {
+        path: path.to_string(),
+        message,
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundled() -> Schema {
+        Schema::parse(super::super::BUNDLED_SCHEMA).expect("bundled schema parses")
+    }
+
+    #[test]
+    fn valid_configs_pass() {
+        let config = json!({
+            "agent": {
+                "model": { "primary": "anthropic/claude-opus-4-6", "fallbacks": ["openai/gpt-4o"] }
+            },
+            "gateway": { "port": 18789, "mode": "local" },
+            "env": { "OPENAI_API_KEY": "sk", "vars": { "A": "b" } },
+            "somethingNewer": { "anything": true }
+        });
+        assert_eq!(bundled().validate(&config), Vec::new());
+        assert!(bundled()
+            .validate(&json!({ "agent": { "model": "ollama/llama3.2" } }))
+            .is_empty());
+    }
+
+    #[test]
+    fn errors_point_at_the_field() {
+        let config = json!({
+            "agent": { "model": { "primary": "anthropic/claude-opus-4-6", "fallbacks": [7] } },
+            "gateway": { "port": 70000, "mode": "cloud", "auth": { "tokn": "x" } },
+            "models": { "providers": { "corp": { "baseUrl": "https://x" } } }
+        });
+        let errors = bundled().validate(&config);
+        let paths = errors.iter().map(|e| e.path.as_str()).collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            vec![
+                "/agent/model/fallbacks/0",
+                "/gateway/port",
+                "/gateway/mode",
+                "/gateway/auth/tokn",
+                "/models/providers/corp/models",
+            ]
+        );
+        assert_eq!(errors[0].message, "expected string, found number");
+        assert_eq!(errors[1].message, "must be at most 65535");
+    }
+
+    #[test]
+    fn descriptions_follow_refs_and_any_of() {
+        let schema = bundled();
+        let path = |segments: &[&str]| segments.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            schema
+                .description(&path(&["agent", "model", "primary"]))
+                .as_deref(),
+            Some("provider/model, e.g. anthropic/claude-opus-4-6.")
+        );
+        assert!(schema.at(&path(&["channels", "telegram"])).is_some());
+        assert_eq!(schema.description(&path(&["gateway", "nope"])), None);
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, BufReader};
+use std::net::TcpListener;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::{Mutex, OnceLock};
@@ -8,7 +9,8 @@ use std::thread;
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
-use crate::modules::{common, node_runtime};
+use crate::modules::platform::PrereqCheck;
+use crate::modules::{common, node_runtime, service_manager};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenClawStatus {
@@ -22,6 +24,14 @@ pub struct UpdateInfo {
     pub installed_version: String,
     pub latest_version: String,
     pub update_available: bool,
+    pub release_notes: Vec<ReleaseNotes>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNotes {
+    pub version: String,
+    pub date: Option<String>,
+    pub notes: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +42,25 @@ pub struct InstallProgress {
 }
 
 const STATUS_CACHE_TTL: Duration = Duration::from_secs(30);
+const DOCTOR_TIMEOUT: Duration = Duration::from_secs(60);
+const NATIVE_MODULES_TIMEOUT: Duration = Duration::from_secs(30);
+const GATEWAY_SMOKE_TIMEOUT: Duration = Duration::from_secs(30);
+const GATEWAY_STOP_GRACE: Duration = Duration::from_secs(5);
+const NPM_TIMEOUT: Duration = Duration::from_secs(60);
+const NATIVE_MODULES_SCRIPT: &str = r#"
+const failures = [];
+for (const file of process.argv.slice(1)) {
+  try {
+    require(file);
+  } catch (err) {
+    failures.push(`${file}: ${String(err && err.message).split("\n")[0]}`);
+  }
+}
+if (failures.length > 0) {
+  console.error(failures.join("\n"));
+  process.exit(1);
+}
+"#;
 
 #[derive(Debug, Clone)]
 struct OpenClawStatusCache {
@@ -73,32 +102,67 @@ pub fn check_openclaw_update(app: AppHandle) -> Result<UpdateInfo, String> {
         version_is_newer(&installed_version, &latest_version)
     };
 
+    let release_notes = if update_available {
+        match fetch_changelog(&app, &latest_version) {
+            Ok(raw) => {
+                release_notes_between(parse_changelog(&raw), &installed_version, &latest_version)
+            }
+            Err(err) => {
+                log::warn!("no release notes for openclaw {latest_version}: {err}");
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
     Ok(UpdateInfo {
         installed_version,
         latest_version,
         update_available,
+        release_notes,
     })
 }
 
-fn install_or_update_openclaw(app: AppHandle) -> Result<OpenClawStatus, String> {
-    let lock = install_lock();
-    {
-        let mut running = lock
-            .lock()
-            .map_err(|_| "openclaw install lock poisoned".to_string())?;
-        if *running {
-            return Err("OpenClaw install is already in progress".to_string());
-        }
-        *running = true;
-    }
+#[tauri::command]
+pub fn verify_openclaw_install(app: AppHandle) -> Result<Vec<PrereqCheck>, String> {
+    clear_cached_status();
+    let app_data_dir = common::app_data_dir(&app)?;
+    let status = openclaw_status_from_dir(&app_data_dir);
+    write_cached_status(&app_data_dir, &status);
 
-    let result = install_or_update_openclaw_inner(app);
+    let binary_check = binary_version_check(&status);
+    let Some(binary) = status.binary_path.clone().filter(|_| status.installed) else {
+        return Ok(vec![binary_check]);
+    };
 
-    if let Ok(mut running) = lock.lock() {
-        *running = false;
-    }
+    let env = node_runtime::get_node_env(app.clone())?;
+    let node_status = node_runtime::get_node_status(app)?;
+    let scratch = ScratchState::create(&app_data_dir)?;
+    let isolated_env = scratch.env(&env);
+    let prefix_dir = common::openclaw_global_dir(&app_data_dir);
+    let package_dir = if Path::new(&binary).starts_with(&prefix_dir) {
+        Some(common::openclaw_package_dir(&app_data_dir))
+    } else {
+        None
+    };
 
-    result
+    Ok(vec![
+        binary_check,
+        doctor_check(&binary, &isolated_env, &scratch.dir),
+        native_modules_check(
+            node_status.node_path.as_deref(),
+            package_dir.as_deref(),
+            &env,
+        ),
+        gateway_smoke_check(&binary, &isolated_env, &scratch.dir),
+    ])
+}
+
+fn install_or_update_openclaw(app: AppHandle) -> Result<OpenClawStatus, String> {
+    let app_data_dir = common::app_data_dir(&app)?;
+    let _lock = common::InstallLock::acquire(&app_data_dir, "install_openclaw")?;
+    install_or_update_openclaw_inner(app)
 }
 
 fn install_or_update_openclaw_inner(app: AppHandle) -> Result<OpenClawStatus, String> {
@@ -200,11 +264,6 @@ fn install_or_update_openclaw_inner(app: AppHandle) -> Result<OpenClawStatus, St
     Ok(openclaw_status)
 }
 
-fn install_lock() -> &'static Mutex<bool> {
-    static LOCK: OnceLock<Mutex<bool>> = OnceLock::new();
-    LOCK.get_or_init(|| Mutex::new(false))
-}
-
 fn query_latest_version(app: &AppHandle) -> Result<String, String> {
     let node_status = node_runtime::get_node_status(app.clone())?;
     if !node_status.installed {
@@ -216,12 +275,9 @@ fn query_latest_version(app: &AppHandle) -> Result<String, String> {
         .ok_or_else(|| "npm path missing from node status".to_string())?;
     let env = node_runtime::get_node_env(app.clone())?;
 
-    let output = Command::new(npm_path)
-        .arg("view")
-        .arg("openclaw")
-        .arg("version")
-        .envs(env)
-        .output()
+    let mut command = Command::new(npm_path);
+    command.arg("view").arg("openclaw").arg("version").envs(env);
+    let output = common::output_with_timeout(&mut command, NPM_TIMEOUT)
         .map_err(|e| format!("failed to query npm version: {e}"))?;
 
     if !output.status.success() {
@@ -243,6 +299,504 @@ fn query_latest_version(app: &AppHandle) -> Result<String, String> {
     Ok(version)
 }
 
+fn binary_version_check(status: &OpenClawStatus) -> PrereqCheck {
+    match (&status.binary_path, &status.version) {
+        (Some(binary), Some(version)) if status.installed => PrereqCheck {
+            name: "openclaw_version".to_string(),
+            passed: true,
+            detail: format!("{binary} reports version {version}"),
+        },
+        _ => PrereqCheck {
+            name: "openclaw_version".to_string(),
+            passed: false,
+            detail: "OpenClaw binary not found or did not report a version".to_string(),
+        },
+    }
+}
+
+/// A throwaway OpenClaw home for `verify_openclaw_install`: a minimal
+/// local-gateway config with no channels, so `doctor` cannot migrate or
+/// repair the user's `~/.openclaw` and the smoke-test gateway does not
+/// connect their channels a second time. Removed on drop.
+struct ScratchState {
+    dir: PathBuf,
+}
+
+impl ScratchState {
+    const CONFIG: &'static str =
+        "{\n  \"gateway\": { \"mode\": \"local\", \"bind\": \"loopback\" }\n}\n";
+
+    fn create(app_data_dir: &Path) -> Result<Self, String> {
+        let state = Self {
+            dir: common::create_scratch_dir(app_data_dir, "openclaw-verify")?,
+        };
+        common::write_private_file_atomic(&state.config_path(), Self::CONFIG.as_bytes())?;
+        Ok(state)
+    }
+
+    fn config_path(&self) -> PathBuf {
+        self.dir.join("openclaw.json")
+    }
+
+    /// `env` with OpenClaw's state and config locations, and the home
+    /// directory it would fall back to, pointed at the scratch directory.
+    fn env(&self, env: &HashMap<String, String>) -> HashMap<String, String> {
+        let dir = self.dir.to_string_lossy().to_string();
+        let mut env = env.clone();
+        env.insert("OPENCLAW_STATE_DIR".to_string(), dir.clone());
+        env.insert(
+            "OPENCLAW_CONFIG_PATH".to_string(),
+            self.config_path().to_string_lossy().to_string(),
+        );
+        env.insert("HOME".to_string(), dir.clone());
+        env.insert("USERPROFILE".to_string(), dir);
+        env
+    }
+}
+
+impl Drop for ScratchState {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn doctor_check(binary: &str, env: &HashMap<String, String>, state_dir: &Path) -> PrereqCheck {
+    let mut command = Command::new(binary);
+    command
+        .arg("doctor")
+        .arg("--non-interactive")
+        .envs(env)
+        .current_dir(state_dir);
+
+    match common::output_with_timeout(&mut command, DOCTOR_TIMEOUT) {
+        Ok(output) => {
+            let summary = last_output_line(&output.stdout, &output.stderr);
+            if output.status.success() {
+                PrereqCheck {
+                    name: "openclaw_doctor".to_string(),
+                    passed: true,
+                    detail: summary.unwrap_or_else(|| "openclaw doctor passed".to_string()),
+                }
+            } else {
+                PrereqCheck {
+                    name: "openclaw_doctor".to_string(),
+                    passed: false,
+                    detail: summary.unwrap_or_else(|| {
+                        format!("openclaw doctor failed with status {}", output.status)
+                    }),
+                }
+            }
+        }
+        Err(err) => PrereqCheck {
+            name: "openclaw_doctor".to_string(),
+            passed: false,
+            detail: format!("Failed to run openclaw doctor: {err}"),
+        },
+    }
+}
+
+fn native_modules_check(
+    node_path: Option<&str>,
+    package_dir: Option<&Path>,
+    env: &HashMap<String, String>,
+) -> PrereqCheck {
+    let Some(package_dir) = package_dir else {
+        return PrereqCheck {
+            name: "native_modules".to_string(),
+            passed: true,
+            detail: "Skipped: OpenClaw is not installed in the app prefix".to_string(),
+        };
+    };
+    let Some(node_path) = node_path else {
+        return PrereqCheck {
+            name: "native_modules".to_string(),
+            passed: false,
+            detail: "Node.js binary path is unknown".to_string(),
+        };
+    };
+    if !package_dir.exists() {
+        return PrereqCheck {
+            name: "native_modules".to_string(),
+            passed: false,
+            detail: format!("OpenClaw package not found at {}", package_dir.display()),
+        };
+    }
+
+    let mut modules = Vec::new();
+    collect_native_modules(package_dir, &mut modules);
+    if modules.is_empty() {
+        return PrereqCheck {
+            name: "native_modules".to_string(),
+            passed: true,
+            detail: "No native modules to load".to_string(),
+        };
+    }
+
+    let mut command = Command::new(node_path);
+    command
+        .arg("-e")
+        .arg(NATIVE_MODULES_SCRIPT)
+        .arg("--")
+        .args(&modules)
+        .envs(env);
+
+    match common::output_with_timeout(&mut command, NATIVE_MODULES_TIMEOUT) {
+        Ok(output) if output.status.success() => PrereqCheck {
+            name: "native_modules".to_string(),
+            passed: true,
+            detail: format!("Loaded {} native module(s)", modules.len()),
+        },
+        Ok(output) => PrereqCheck {
+            name: "native_modules".to_string(),
+            passed: false,
+            detail: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        },
+        Err(err) => PrereqCheck {
+            name: "native_modules".to_string(),
+            passed: false,
+            detail: format!("Failed to load native modules: {err}"),
+        },
+    }
+}
+
+/// Collects the addons Node would load on this machine: those compiled into
+/// `build/Release` and the prebuilds under `prebuilds/<platform>-<arch>`
+/// for this platform. Prebuilds shipped for other platforms never load here
+/// and are skipped.
+fn collect_native_modules(dir: &Path, found: &mut Vec<PathBuf>) {
+    scan_native_modules(dir, false, found);
+}
+
+fn scan_native_modules(dir: &Path, in_prebuild: bool, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if entry.file_name() == "prebuilds" {
+                scan_native_modules(&path.join(prebuild_platform()), true, found);
+            } else {
+                scan_native_modules(&path, in_prebuild, found);
+            }
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some("node")
+            && (in_prebuild
+                || path
+                    .parent()
+                    .is_some_and(|parent| parent.ends_with("build/Release")))
+            && !for_other_libc(&entry.file_name().to_string_lossy())
+        {
+            found.push(path);
+        }
+    }
+}
+
+/// `process.platform` and `process.arch` of the bundled Node, as used in
+/// prebuild directory names (`linux-x64`, `darwin-arm64`, `win32-x64`).
+fn prebuild_platform() -> String {
+    let platform = match std::env::consts::OS {
+        "macos" => "darwin",
+        "windows" => "win32",
+        other => other,
+    };
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        "x86" => "ia32",
+        "powerpc64" => "ppc64",
+        other => other,
+    };
+    format!("{platform}-{arch}")
+}
+
+/// Linux prebuilds tag their C library in the file name
+/// (`node.napi.musl.node`); only the one matching this build can load.
+fn for_other_libc(file_name: &str) -> bool {
+    let other = if cfg!(target_env = "musl") {
+        ".glibc."
+    } else {
+        ".musl."
+    };
+    file_name.contains(other)
+}
+
+fn gateway_smoke_check(
+    binary: &str,
+    env: &HashMap<String, String>,
+    state_dir: &Path,
+) -> PrereqCheck {
+    let port = match TcpListener::bind(("127.0.0.1", 0)).and_then(|listener| listener.local_addr())
+    {
+        Ok(address) => address.port(),
+        Err(err) => {
+            return PrereqCheck {
+                name: "gateway_http".to_string(),
+                passed: false,
+                detail: format!("Failed to reserve an ephemeral port: {err}"),
+            }
+        }
+    };
+
+    let mut command = Command::new(binary);
+    command
+        .arg("gateway")
+        .arg("--port")
+        .arg(port.to_string())
+        .envs(env)
+        .current_dir(state_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let spawned = common::own_process_group(&mut command).spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(err) => {
+            return PrereqCheck {
+                name: "gateway_http".to_string(),
+                passed: false,
+                detail: format!("Failed to spawn gateway: {err}"),
+            }
+        }
+    };
+
+    let start = Instant::now();
+    let result = loop {
+        if service_manager::health_check_inner(port) {
+            break PrereqCheck {
+                name: "gateway_http".to_string(),
+                passed: true,
+                detail: format!("Gateway answered HTTP on port {port}"),
+            };
+        }
+        if let Ok(Some(status)) = child.try_wait() {
+            break PrereqCheck {
+                name: "gateway_http".to_string(),
+                passed: false,
+                detail: format!("Gateway exited before answering HTTP (status {status})"),
+            };
+        }
+        if start.elapsed() >= GATEWAY_SMOKE_TIMEOUT {
+            break PrereqCheck {
+                name: "gateway_http".to_string(),
+                passed: false,
+                detail: format!(
+                    "Gateway did not answer HTTP on port {port} within {} seconds",
+                    GATEWAY_SMOKE_TIMEOUT.as_secs()
+                ),
+            };
+        }
+        thread::sleep(Duration::from_millis(500));
+    };
+
+    common::stop_process_tree(&mut child, GATEWAY_STOP_GRACE);
+    result
+}
+
+fn last_output_line(stdout: &[u8], stderr: &[u8]) -> Option<String> {
+    [stdout, stderr].into_iter().find_map(|stream| {
+        strip_ansi_sequences(&String::from_utf8_lossy(stream))
+            .lines()
+            .map(str::trim)
+            .rfind(|line| !line.is_empty())
+            .map(ToString::to_string)
+    })
+}
+
+/// The CHANGELOG.md published with `openclaw@{version}`, or an empty
+/// string if the package has none. A release never changes once published,
+/// so the result is cached per version and the tarball is fetched only once.
+fn fetch_changelog(app: &AppHandle, version: &str) -> Result<String, String> {
+    let app_data_dir = common::app_data_dir(app)?;
+    let cache_path = changelog_cache_path(&app_data_dir, version)?;
+    if let Ok(cached) = std::fs::read_to_string(&cache_path) {
+        return Ok(cached);
+    }
+
+    let node_status = node_runtime::get_node_status(app.clone())?;
+    let npm_path = node_status
+        .npm_path
+        .ok_or_else(|| "npm path missing from node status".to_string())?;
+    let env = node_runtime::get_node_env(app.clone())?;
+
+    let work_dir = common::create_scratch_dir(&app_data_dir, "openclaw-changelog")?;
+    let result = extract_changelog(&npm_path, env, version, &work_dir);
+    let _ = std::fs::remove_dir_all(&work_dir);
+    let changelog = result?;
+    if let Err(err) = common::write_file_atomic(&cache_path, changelog.as_bytes()) {
+        log::warn!("failed to cache the openclaw {version} changelog: {err}");
+    }
+    Ok(changelog)
+}
+
+fn changelog_cache_path(app_data_dir: &Path, version: &str) -> Result<PathBuf, String> {
+    let safe = !version.is_empty()
+        && !version.starts_with('.')
+        && version
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-' | '+'));
+    if !safe {
+        return Err(format!("unexpected openclaw version {version:?}"));
+    }
+    Ok(common::changelog_cache_dir(app_data_dir).join(format!("openclaw-{version}.md")))
+}
+
+fn extract_changelog(
+    npm_path: &str,
+    env: HashMap<String, String>,
+    version: &str,
+    work_dir: &Path,
+) -> Result<String, String> {
+    let mut pack = Command::new(npm_path);
+    pack.arg("pack")
+        .arg(format!("openclaw@{version}"))
+        .arg("--pack-destination")
+        .arg(work_dir)
+        .arg("--silent")
+        .envs(env);
+    let output = common::output_with_timeout(&mut pack, NPM_TIMEOUT)
+        .map_err(|e| format!("failed to run npm pack: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "npm pack openclaw failed with status {}",
+            output.status
+        ));
+    }
+
+    let tarball_name = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .rfind(|line| line.ends_with(".tgz"))
+        .map(ToString::to_string)
+        .ok_or_else(|| "npm pack did not report a tarball name".to_string())?;
+    let tarball = work_dir.join(tarball_name);
+
+    let mut list = Command::new("tar");
+    list.arg("-tzf").arg(&tarball);
+    let listing = common::output_with_timeout(&mut list, NPM_TIMEOUT)
+        .map_err(|e| format!("failed to list package tarball: {e}"))?;
+    if !listing.status.success() {
+        return Err(format!("tar listing failed with status {}", listing.status));
+    }
+    let Some(entry) = String::from_utf8_lossy(&listing.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| line.eq_ignore_ascii_case("package/changelog.md"))
+        .map(ToString::to_string)
+    else {
+        return Ok(String::new());
+    };
+
+    let mut extract = Command::new("tar");
+    extract
+        .arg("-xzf")
+        .arg(&tarball)
+        .arg("-C")
+        .arg(work_dir)
+        .arg(&entry);
+    let status = common::output_with_timeout(&mut extract, NPM_TIMEOUT)
+        .map_err(|e| format!("failed to extract changelog: {e}"))?
+        .status;
+    if !status.success() {
+        return Err(format!("changelog extraction failed with status {status}"));
+    }
+
+    std::fs::read_to_string(work_dir.join(&entry))
+        .map_err(|e| format!("failed to read extracted changelog: {e}"))
+}
+
+/// Splits a markdown changelog into one section per version heading. The
+/// heading level of the first versioned heading is treated as the release
+/// level; deeper headings stay part of the section body.
+fn parse_changelog(raw: &str) -> Vec<ReleaseNotes> {
+    let mut sections = Vec::new();
+    let mut release_level: Option<usize> = None;
+    let mut current: Option<(String, Option<String>, Vec<&str>)> = None;
+
+    for line in raw.lines() {
+        if let Some((level, title)) = markdown_heading(line) {
+            let at_release_level = release_level.map(|value| level <= value).unwrap_or(true);
+            let version = title.split_whitespace().find_map(parse_version_token);
+            if let (true, Some(version)) = (at_release_level, version) {
+                if let Some(section) = current.take() {
+                    sections.push(finish_release_section(section));
+                }
+                release_level.get_or_insert(level);
+                current = Some((version, parse_date_token(title), Vec::new()));
+                continue;
+            }
+        }
+
+        if let Some((_, _, body)) = current.as_mut() {
+            body.push(line);
+        }
+    }
+
+    if let Some(section) = current.take() {
+        sections.push(finish_release_section(section));
+    }
+
+    sections
+}
+
+fn finish_release_section(section: (String, Option<String>, Vec<&str>)) -> ReleaseNotes {
+    let (version, date, body) = section;
+    ReleaseNotes {
+        version,
+        date,
+        notes: body.join("\n").trim().to_string(),
+    }
+}
+
+fn markdown_heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let title = &trimmed[level..];
+    if !title.starts_with(' ') {
+        return None;
+    }
+    Some((level, title.trim()))
+}
+
+fn parse_date_token(title: &str) -> Option<String> {
+    title.split_whitespace().find_map(|token| {
+        let candidate = token.trim_matches(|ch: char| !ch.is_ascii_digit());
+        let parts = candidate.split('-').collect::<Vec<_>>();
+        let valid = parts.len() == 3
+            && parts[0].len() == 4
+            && parts[1].len() == 2
+            && parts[2].len() == 2
+            && parts
+                .iter()
+                .all(|part| part.chars().all(|ch| ch.is_ascii_digit()));
+        if valid {
+            Some(candidate.to_string())
+        } else {
+            None
+        }
+    })
+}
+
+fn release_notes_between(
+    sections: Vec<ReleaseNotes>,
+    installed: &str,
+    latest: &str,
+) -> Vec<ReleaseNotes> {
+    sections
+        .into_iter()
+        .filter(|section| {
+            version_is_newer(installed, &section.version)
+                && !version_is_newer(latest, &section.version)
+        })
+        .collect()
+}
+
 fn emit_install_progress(app: &AppHandle, payload: InstallProgress) -> Result<(), String> {
     app.emit("openclaw:install-progress", payload)
         .map_err(|e| format!("failed to emit openclaw install progress: {e}"))
@@ -583,4 +1137,101 @@ mod tests {
             Some("2026.2.1".to_string())
         );
     }
+
+    #[test]
+    fn native_module_scan_finds_nested_addons() {
+        let root =
+            std::env::temp_dir().join(format!("openclawini-native-scan-{}", std::process::id()));
+        let nested = root
+            .join("node_modules")
+            .join("sharp")
+            .join("build")
+            .join("Release");
+        std::fs::create_dir_all(&nested).expect("create nested dir");
+        std::fs::write(nested.join("sharp.node"), b"").expect("write addon");
+        std::fs::write(root.join("index.js"), b"").expect("write js");
+
+        let mut found = Vec::new();
+        collect_native_modules(&root, &mut found);
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(found, vec![nested.join("sharp.node")]);
+    }
+
+    #[test]
+    fn native_module_scan_skips_foreign_prebuilds() {
+        let root =
+            std::env::temp_dir().join(format!("openclawini-prebuilds-{}", std::process::id()));
+        let prebuilds = root
+            .join("node_modules")
+            .join("bufferutil")
+            .join("prebuilds");
+        let foreign = if prebuild_platform() == "win32-x64" {
+            "darwin-arm64"
+        } else {
+            "win32-x64"
+        };
+        for platform in [prebuild_platform().as_str(), foreign] {
+            std::fs::create_dir_all(prebuilds.join(platform)).expect("create prebuild dir");
+            std::fs::write(prebuilds.join(platform).join("node.napi.node"), b"")
+                .expect("write prebuild");
+        }
+        let other_libc = if cfg!(target_env = "musl") {
+            "node.napi.glibc.node"
+        } else {
+            "node.napi.musl.node"
+        };
+        std::fs::write(prebuilds.join(prebuild_platform()).join(other_libc), b"")
+            .expect("write prebuild");
+        let stray = root
+            .join("node_modules")
+            .join("bufferutil")
+            .join("fallback");
+        std::fs::create_dir_all(&stray).expect("create stray dir");
+        std::fs::write(stray.join("addon.node"), b"").expect("write stray addon");
+
+        let mut found = Vec::new();
+        collect_native_modules(&root, &mut found);
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(
+            found,
+            vec![prebuilds.join(prebuild_platform()).join("node.napi.node")]
+        );
+    }
+
+    #[test]
+    fn changelog_is_split_into_version_sections() {
+        let raw = "# Changelog\n\nIntro text.\n\n## 2026.3.0 (2026-03-02)\n\n### Features\n- Added 1.2 support\n\n## [2026.2.1] - 2026-02-10\n- Fixed gateway crash\n\n## v2026.1.0\n- Initial release\n";
+        let sections = parse_changelog(raw);
+
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].version, "2026.3.0");
+        assert_eq!(sections[0].date.as_deref(), Some("2026-03-02"));
+        assert_eq!(sections[0].notes, "### Features\n- Added 1.2 support");
+        assert_eq!(sections[1].version, "2026.2.1");
+        assert_eq!(sections[1].date.as_deref(), Some("2026-02-10"));
+        assert_eq!(sections[2].version, "2026.1.0");
+        assert_eq!(sections[2].date, None);
+    }
+
+    #[test]
+    fn release_notes_are_limited_to_versions_after_installed() {
+        let raw = "## 2026.3.0\n- c\n## 2026.2.1\n- b\n## 2026.2.0\n- a\n## 2026.1.0\n- old\n";
+        let notes = release_notes_between(parse_changelog(raw), "2026.2.0", "2026.2.1");
+        let versions = notes.iter().map(|n| n.version.as_str()).collect::<Vec<_>>();
+        assert_eq!(versions, vec!["2026.2.1"]);
+    }
+
+    #[test]
+    fn changelogs_are_cached_under_safe_names_only() {
+        let dir = Path::new("/data");
+        assert_eq!(
+            changelog_cache_path(dir, "2026.3.0-beta.1").expect("semver"),
+            common::changelog_cache_dir(dir).join("openclaw-2026.3.0-beta.1.md")
+        );
+        for version in ["", "../x", "1.0/2", ".."] {
+            assert!(changelog_cache_path(dir, version).is_err(), "{version}");
+        }
+    }
 }
@@ -0,0 +1,606 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::process::Command;
+use tauri::AppHandle;
+
+use crate::modules::{common, node_runtime, openclaw_installer};
+
+const RESERVED_PACKAGES: [&str; 3] = ["openclaw", "npm", "corepack"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginInfo {
+    pub name: String,
+    pub version: Option<String>,
+    pub spec: Option<String>,
+    pub managed: bool,
+    pub openclaw_range: Option<String>,
+    pub compatible: bool,
+    pub compatibility_detail: String,
+    pub installed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PluginRecord {
+    spec: String,
+    version: String,
+    openclaw_version: Option<String>,
+    installed_at: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PackageManifest {
+    version: Option<String>,
+    #[serde(default, rename = "peerDependencies")]
+    peer_dependencies: HashMap<String, String>,
+    #[serde(default)]
+    engines: HashMap<String, String>,
+}
+
+impl PackageManifest {
+    fn openclaw_range(&self) -> Option<String> {
+        self.peer_dependencies
+            .get("openclaw")
+            .or_else(|| self.engines.get("openclaw"))
+            .cloned()
+    }
+}
+
+#[tauri::command]
+pub fn list_plugins(app: AppHandle) -> Result<Vec<PluginInfo>, String> {
+    let app_data_dir = common::app_data_dir(&app)?;
+    let openclaw_version = openclaw_installer::get_openclaw_status(app)?.version;
+    let records = read_manifest(&app_data_dir)?;
+    let modules_dir = common::global_node_modules_dir(&app_data_dir);
+
+    let mut names = installed_package_names(&modules_dir);
+    names.extend(records.keys().cloned());
+    names.sort();
+    names.dedup();
+
+    Ok(names
+        .into_iter()
+        .map(|name| {
+            let package = read_package_manifest(&modules_dir.join(&name));
+            plugin_info(
+                &name,
+                package.as_ref(),
+                records.get(&name),
+                openclaw_version.as_deref(),
+            )
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn install_plugin(app: AppHandle, spec: String) -> Result<PluginInfo, String> {
+    let spec = spec.trim().to_string();
+    let name = package_name_from_spec(&spec)?;
+    install_plugin_spec(&app, &name, &spec, &spec)
+}
+
+#[tauri::command]
+pub fn update_plugin(app: AppHandle, name: String) -> Result<PluginInfo, String> {
+    let app_data_dir = common::app_data_dir(&app)?;
+    let records = read_manifest(&app_data_dir)?;
+    let spec = records
+        .get(&name)
+        .map(|record| record.spec.clone())
+        .ok_or_else(|| format!("plugin {name} is not managed by the app"))?;
+
+    // A bare package name was installed as "latest"; a range keeps resolving
+    // to the newest release that satisfies it.
+    let install_spec = if spec == name {
+        format!("{name}@latest")
+    } else {
+        spec.clone()
+    };
+    install_plugin_spec(&app, &name, &install_spec, &spec)
+}
+
+#[tauri::command]
+pub fn remove_plugin(app: AppHandle, name: String) -> Result<(), String> {
+    if package_name_from_spec(&name)? != name {
+        return Err(format!("{name} is not a plugin package name"));
+    }
+    ensure_not_reserved(&name)?;
+    let app_data_dir = common::app_data_dir(&app)?;
+    let _lock = common::InstallLock::acquire(&app_data_dir, "remove_plugin")?;
+
+    let mut records = read_manifest(&app_data_dir)?;
+    let installed = installed_package_names(&common::global_node_modules_dir(&app_data_dir));
+    ensure_known_plugin(&name, &records, &installed)?;
+
+    run_npm(&app, &app_data_dir, &["uninstall", "-g", &name])?;
+
+    records.remove(&name);
+    write_manifest(&app_data_dir, &records)
+}
+
+fn install_plugin_spec(
+    app: &AppHandle,
+    name: &str,
+    spec: &str,
+    recorded_spec: &str,
+) -> Result<PluginInfo, String> {
+    ensure_not_reserved(name)?;
+    let app_data_dir = common::app_data_dir(app)?;
+    let openclaw_version = openclaw_installer::get_openclaw_status(app.clone())?
+        .version
+        .ok_or_else(|| "OpenClaw is not installed; install it before adding plugins".to_string())?;
+
+    let published = view_published_manifest(app, spec)?;
+    if let Some(range) = published.openclaw_range() {
+        if !version_satisfies(&openclaw_version, &range) {
+            return Err(format!(
+                "{spec} requires openclaw {range}, but {openclaw_version} is installed"
+            ));
+        }
+    }
+
+    let _lock = common::InstallLock::acquire(&app_data_dir, "install_plugin")?;
+    run_npm(app, &app_data_dir, &["install", "-g", spec])?;
+
+    let package_dir = common::global_node_modules_dir(&app_data_dir).join(name);
+    let installed = read_package_manifest(&package_dir)
+        .ok_or_else(|| format!("npm install finished but {name} was not found"))?;
+
+    let mut records = read_manifest(&app_data_dir)?;
+    let installed_at = records
+        .get(name)
+        .map(|record| record.installed_at.clone())
+        .unwrap_or_else(common::iso_utc_now);
+    records.insert(
+        name.to_string(),
+        PluginRecord {
+            spec: recorded_spec.to_string(),
+            version: installed.version.clone().unwrap_or_default(),
+            openclaw_version: Some(openclaw_version.clone()),
+            installed_at,
+        },
+    );
+    write_manifest(&app_data_dir, &records)?;
+
+    Ok(plugin_info(
+        name,
+        Some(&installed),
+        records.get(name),
+        Some(&openclaw_version),
+    ))
+}
+
+fn ensure_not_reserved(name: &str) -> Result<(), String> {
+    if RESERVED_PACKAGES.contains(&name) {
+        return Err(format!(
+            "{name} is managed by the app and cannot be changed as a plugin"
+        ));
+    }
+    Ok(())
+}
+
+/// Only packages `list_plugins` would show can be removed: the ones the app
+/// recorded and the ones found in the prefix.
+fn ensure_known_plugin(
+    name: &str,
+    records: &BTreeMap<String, PluginRecord>,
+    installed: &[String],
+) -> Result<(), String> {
+    if records.contains_key(name) || installed.iter().any(|item| item == name) {
+        Ok(())
+    } else {
+        Err(format!("plugin {name} is not installed"))
+    }
+}
+
+fn plugin_info(
+    name: &str,
+    package: Option<&PackageManifest>,
+    record: Option<&PluginRecord>,
+    openclaw_version: Option<&str>,
+) -> PluginInfo {
+    let openclaw_range = package.and_then(PackageManifest::openclaw_range);
+    let (compatible, compatibility_detail) = match (package, openclaw_version, &openclaw_range) {
+        (None, _, _) => (
+            false,
+            "Package is missing from the OpenClaw prefix".to_string(),
+        ),
+        (Some(_), None, _) => (false, "OpenClaw is not installed".to_string()),
+        (Some(_), Some(_), None) => (
+            true,
+            "Package does not declare an OpenClaw version constraint".to_string(),
+        ),
+        (Some(_), Some(version), Some(range)) => {
+            if version_satisfies(version, range) {
+                (true, format!("OpenClaw {version} satisfies {range}"))
+            } else {
+                (
+                    false,
+                    format!("Requires OpenClaw {range}, installed {version}"),
+                )
+            }
+        }
+    };
+
+    PluginInfo {
+        name: name.to_string(),
+        version: package.and_then(|value| value.version.clone()),
+        spec: record.map(|value| value.spec.clone()),
+        managed: record.is_some(),
+        openclaw_range,
+        compatible,
+        compatibility_detail,
+        installed_at: record.map(|value| value.installed_at.clone()),
+    }
+}
+
+fn run_npm(app: &AppHandle, app_data_dir: &Path, args: &[&str]) -> Result<(), String> {
+    let npm_path = node_runtime::get_node_status(app.clone())?
+        .npm_path
+        .ok_or_else(|| "npm path missing from node status".to_string())?;
+    let env = node_runtime::get_node_env(app.clone())?;
+    let prefix_dir = common::openclaw_global_dir(app_data_dir);
+
+    let output = Command::new(npm_path)
+        .args(args)
+        .arg("--prefix")
+        .arg(prefix_dir)
+        .envs(env)
+        .output()
+        .map_err(|e| format!("failed to run npm {}: {e}", args.join(" ")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr
+            .lines()
+            .map(str::trim)
+            .rfind(|line| !line.is_empty())
+            .unwrap_or("no output");
+        return Err(format!(
+            "npm {} failed with status {}: {detail}",
+            args.join(" "),
+            output.status
+        ));
+    }
+    Ok(())
+}
+
+fn view_published_manifest(app: &AppHandle, spec: &str) -> Result<PackageManifest, String> {
+    let npm_path = node_runtime::get_node_status(app.clone())?
+        .npm_path
+        .ok_or_else(|| "npm path missing from node status".to_string())?;
+    let env = node_runtime::get_node_env(app.clone())?;
+
+    let output = Command::new(npm_path)
+        .args([
+            "view",
+            spec,
+            "name",
+            "version",
+            "peerDependencies",
+            "engines",
+        ])
+        .arg("--json")
+        .envs(env)
+        .output()
+        .map_err(|e| format!("failed to query npm for {spec}: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "npm view {spec} failed with status {}",
+            output.status
+        ));
+    }
+
+    parse_view_output(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| format!("npm returned no metadata for {spec}"))
+}
+
+/// `npm view --json` prints an object for a single match and an array when a
+/// range matches several versions; the last entry is the one npm installs.
+fn parse_view_output(raw: &str) -> Option<PackageManifest> {
+    let value: serde_json::Value = serde_json::from_str(raw.trim()).ok()?;
+    let entry = match value {
+        serde_json::Value::Array(items) => items.into_iter().last()?,
+        other => other,
+    };
+    serde_json::from_value(entry).ok()
+}
+
+fn read_package_manifest(package_dir: &Path) -> Option<PackageManifest> {
+    let raw = std::fs::read_to_string(package_dir.join("package.json")).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn installed_package_names(modules_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(modules_dir) else {
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name.starts_with('.') {
+            continue;
+        }
+        if file_name.starts_with('@') {
+            let Ok(scoped) = std::fs::read_dir(entry.path()) else {
+                continue;
+            };
+            names.extend(
+                scoped
+                    .filter_map(Result::ok)
+                    .map(|item| format!("{file_name}/{}", item.file_name().to_string_lossy())),
+            );
+        } else if !RESERVED_PACKAGES.contains(&file_name.as_str()) {
+            names.push(file_name);
+        }
+    }
+    names
+}
+
+fn read_manifest(app_data_dir: &Path) -> Result<BTreeMap<String, PluginRecord>, String> {
+    let path = common::plugins_manifest_path(app_data_dir);
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let raw = std::fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    serde_json::from_str(&raw)
+        .map_err(|e| format!("invalid plugins json in {}: {e}", path.display()))
+}
+
+fn write_manifest(
+    app_data_dir: &Path,
+    records: &BTreeMap<String, PluginRecord>,
+) -> Result<(), String> {
+    let path = common::plugins_manifest_path(app_data_dir);
+    let serialized = serde_json::to_string_pretty(records)
+        .map_err(|e| format!("failed to serialize plugins: {e}"))?;
+    common::write_file_atomic(&path, serialized.as_bytes())
+}
+
+fn package_name_from_spec(spec: &str) -> Result<String, String> {
+    if spec.is_empty() {
+        return Err("plugin package spec cannot be empty".to_string());
+    }
+
+    let (scope, rest) = match spec.strip_prefix('@') {
+        Some(rest) => ("@", rest),
+        None => ("", spec),
+    };
+    let name = rest.split('@').next().unwrap_or_default();
+    let valid = !name.is_empty()
+        && name.chars().all(|ch| {
+            ch.is_ascii_lowercase() || ch.is_ascii_digit() || matches!(ch, '-' | '_' | '.' | '/')
+        })
+        && (scope.is_empty() != name.contains('/'))
+        && name
+            .split('/')
+            .all(|segment| !matches!(segment, "" | "." | ".."));
+    if !valid {
+        return Err(format!("invalid npm package spec: {spec}"));
+    }
+    Ok(format!("{scope}{name}"))
+}
+
+/// Minimal npm semver range check covering the forms packages use for peer
+/// constraints: `*`, exact and partial versions, comparison operators (also
+/// written apart from their version, `>= 1.0.0`), `^`, `~`, hyphen ranges,
+/// whitespace-joined comparators and `||` alternatives.
+fn version_satisfies(version: &str, range: &str) -> bool {
+    let version = version_triplet(version);
+    range.split("||").any(|alternative| {
+        if let Some((from, to)) = alternative.split_once(" - ") {
+            return hyphen_range_matches(&version, from.trim(), to.trim());
+        }
+        comparators(alternative)
+            .iter()
+            .all(|comparator| comparator_matches(&version, comparator))
+    })
+}
+
+/// Splits a space-separated comparator set, joining a bare operator with
+/// the version after it.
+fn comparators(alternative: &str) -> Vec<String> {
+    let mut comparators: Vec<String> = Vec::new();
+    let mut pending_operator = false;
+    for token in alternative.split_whitespace() {
+        match comparators.last_mut() {
+            Some(operator) if pending_operator => operator.push_str(token),
+            _ => comparators.push(token.to_string()),
+        }
+        pending_operator = token
+            .chars()
+            .all(|ch| matches!(ch, '<' | '>' | '=' | '^' | '~'));
+    }
+    comparators
+}
+
+/// `from - to`: at least `from`, and at most `to`, where a partial `to`
+/// takes in everything it covers (`1.0.0 - 2` is `>=1.0.0 <3.0.0`).
+fn hyphen_range_matches(version: &[u64; 3], from: &str, to: &str) -> bool {
+    let lower = pad_version(&partial_version(from.trim_start_matches('v')));
+    let upper = partial_version(to.trim_start_matches('v'));
+    if version.cmp(&lower) == Ordering::Less {
+        return false;
+    }
+    match upper.len() {
+        0 => true,
+        3 => version.cmp(&pad_version(&upper)) != Ordering::Greater,
+        len => version.cmp(&bump(&upper, len - 1)) == Ordering::Less,
+    }
+}
+
+fn comparator_matches(version: &[u64; 3], comparator: &str) -> bool {
+    let (op, raw) = ["<=", ">=", "<", ">", "=", "^", "~"]
+        .iter()
+        .find_map(|op| comparator.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("", comparator));
+    let raw = raw.trim().trim_start_matches('v');
+    let parts = partial_version(raw);
+    if parts.is_empty() {
+        return true;
+    }
+    let lower = pad_version(&parts);
+
+    match op {
+        ">=" => version.cmp(&lower) != Ordering::Less,
+        ">" => version.cmp(&lower) == Ordering::Greater,
+        "<=" => version.cmp(&lower) != Ordering::Greater,
+        "<" => version.cmp(&lower) == Ordering::Less,
+        "^" => {
+            let bump_at = parts
+                .iter()
+                .position(|part| *part != 0)
+                .unwrap_or(parts.len() - 1);
+            in_range(version, &lower, &bump(&parts, bump_at))
+        }
+        "~" => in_range(version, &lower, &bump(&parts, parts.len().min(2) - 1)),
+        _ => {
+            if parts.len() == 3 {
+                version.cmp(&lower) == Ordering::Equal
+            } else {
+                in_range(version, &lower, &bump(&parts, parts.len() - 1))
+            }
+        }
+    }
+}
+
+fn in_range(version: &[u64; 3], lower: &[u64; 3], upper: &[u64; 3]) -> bool {
+    version.cmp(lower) != Ordering::Less && version.cmp(upper) == Ordering::Less
+}
+
+fn bump(parts: &[u64], index: usize) -> [u64; 3] {
+    let mut upper = [0; 3];
+    upper[..index].copy_from_slice(&parts[..index]);
+    upper[index] = parts[index] + 1;
+    upper
+}
+
+/// Leading numeric components of a range operand; `x`/`*` wildcards end it.
+fn partial_version(value: &str) -> Vec<u64> {
+    value
+        .split('-')
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map_while(|segment| segment.parse::<u64>().ok())
+        .take(3)
+        .collect()
+}
+
+fn pad_version(parts: &[u64]) -> [u64; 3] {
+    let mut padded = [0; 3];
+    padded[..parts.len()].copy_from_slice(parts);
+    padded
+}
+
+fn version_triplet(value: &str) -> [u64; 3] {
+    pad_version(&partial_version(&common::normalize_version(value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_name_is_extracted_from_spec() {
+        assert_eq!(package_name_from_spec("foo").unwrap(), "foo");
+        assert_eq!(package_name_from_spec("foo@^1.2.0").unwrap(), "foo");
+        assert_eq!(
+            package_name_from_spec("@acme/openclaw-plugin@2.0.0").unwrap(),
+            "@acme/openclaw-plugin"
+        );
+        assert!(package_name_from_spec("").is_err());
+        assert!(package_name_from_spec("foo/bar").is_err());
+        assert!(package_name_from_spec("Foo").is_err());
+        assert!(package_name_from_spec("..").is_err());
+        assert!(package_name_from_spec("@a/../../x").is_err());
+        assert!(package_name_from_spec("@a/").is_err());
+        assert!(package_name_from_spec("@./x").is_err());
+    }
+
+    #[test]
+    fn only_listed_plugins_can_be_removed() {
+        let mut records = BTreeMap::new();
+        records.insert(
+            "@acme/recorded".to_string(),
+            PluginRecord {
+                spec: "@acme/recorded@^1.0.0".to_string(),
+                version: "1.2.0".to_string(),
+                openclaw_version: None,
+                installed_at: "2026-01-01T00:00:00Z".to_string(),
+            },
+        );
+        let installed = vec!["found-in-prefix".to_string()];
+
+        assert!(ensure_known_plugin("@acme/recorded", &records, &installed).is_ok());
+        assert!(ensure_known_plugin("found-in-prefix", &records, &installed).is_ok());
+        assert!(ensure_known_plugin("left-pad", &records, &installed).is_err());
+    }
+
+    #[test]
+    fn version_ranges_match_npm_semantics() {
+        assert!(version_satisfies("2026.2.1", "*"));
+        assert!(version_satisfies("2026.2.1", ">=2026.1.0"));
+        assert!(!version_satisfies("2026.2.1", ">=2026.3.0"));
+        assert!(version_satisfies("2026.2.1", "^2026.1.0"));
+        assert!(!version_satisfies("2027.0.0", "^2026.1.0"));
+        assert!(version_satisfies("0.2.5", "^0.2.1"));
+        assert!(!version_satisfies("0.3.0", "^0.2.1"));
+        assert!(version_satisfies("1.2.9", "~1.2.3"));
+        assert!(!version_satisfies("1.3.0", "~1.2.3"));
+        assert!(version_satisfies("1.4.0", "1.x"));
+        assert!(version_satisfies("1.4.0", ">=1.0.0 <2.0.0"));
+        assert!(!version_satisfies("2.0.0", ">=1.0.0 <2.0.0"));
+        assert!(version_satisfies("3.1.0", "^1.0.0 || ^3.0.0"));
+        assert!(version_satisfies("1.2.3", "1.2.3"));
+        assert!(!version_satisfies("1.2.4", "=1.2.3"));
+    }
+
+    #[test]
+    fn spaced_operators_and_hyphen_ranges_are_understood() {
+        assert!(version_satisfies("1.4.0", ">= 1.0.0"));
+        assert!(!version_satisfies("0.9.0", ">= 1.0.0"));
+        assert!(version_satisfies("1.4.0", ">= 1.0.0 < 2.0.0"));
+        assert!(!version_satisfies("2.0.0", ">= 1.0.0 < 2.0.0"));
+        assert!(version_satisfies("2.1.0", "^ 2.0.0 || < 1"));
+
+        assert!(version_satisfies("1.0.0", "1.0.0 - 2.0.0"));
+        assert!(version_satisfies("2.0.0", "1.0.0 - 2.0.0"));
+        assert!(!version_satisfies("2.0.1", "1.0.0 - 2.0.0"));
+        assert!(!version_satisfies("0.9.9", "1.0.0 - 2.0.0"));
+        assert!(version_satisfies("2.9.0", "1.2 - 2"));
+        assert!(!version_satisfies("3.0.0", "1.2 - 2"));
+        assert!(version_satisfies("4.0.0", "1.0.0 - 2.0.0 || >=4"));
+    }
+
+    #[test]
+    fn view_output_accepts_object_and_array() {
+        let single = parse_view_output(
+            r#"{"name":"p","version":"1.0.0","peerDependencies":{"openclaw":">=2026.1.0"}}"#,
+        )
+        .expect("object parses");
+        assert_eq!(single.openclaw_range().as_deref(), Some(">=2026.1.0"));
+
+        let many = parse_view_output(
+            r#"[{"name":"p","version":"1.0.0"},{"name":"p","version":"1.1.0","engines":{"openclaw":"^2026.2.0"}}]"#,
+        )
+        .expect("array parses");
+        assert_eq!(many.version.as_deref(), Some("1.1.0"));
+        assert_eq!(many.openclaw_range().as_deref(), Some("^2026.2.0"));
+    }
+
+    #[test]
+    fn plugin_info_reports_incompatible_range() {
+        let package = PackageManifest {
+            version: Some("1.0.0".to_string()),
+            peer_dependencies: HashMap::from([("openclaw".to_string(), ">=2026.3.0".to_string())]),
+            engines: HashMap::new(),
+        };
+        let info = plugin_info("p", Some(&package), None, Some("2026.2.1"));
+        assert!(!info.compatible);
+        assert!(!info.managed);
+    }
+}
@@ -0,0 +1,414 @@
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::SecretBackend;
+use crate::modules::common;
+
+pub const NAME: &str = "encrypted_file";
+
+const FORMAT_VERSION: u32 = 1;
+const KDF: &str = "pbkdf2-hmac-sha256";
+const KDF_ITERATIONS: u32 = 210_000;
+pub const KEY_SOURCE_MACHINE: &str = "machine";
+pub const KEY_SOURCE_PASSPHRASE: &str = "passphrase";
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const AAD: &[u8] = b"openclawini-keys-v1";
+
+/// On-disk layout of `keys.enc`. Everything needed to re-derive the key
+/// except the secret itself is stored alongside the AES-256-GCM ciphertext.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Envelope {
+    version: u32,
+    key_source: String,
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Fallback for machines without a usable keychain (headless Linux, CI
+/// runners): the key map is sealed with AES-256-GCM under a key derived
+/// either from a machine-bound secret, so a copy of the file is useless on
+/// another machine or user account, or from a user passphrase.
+#[derive(Debug, Clone)]
+pub struct EncryptedFile {
+    path: PathBuf,
+    passphrase: Option<String>,
+    iterations: u32,
+}
+
+impl EncryptedFile {
+    pub fn machine_bound(path: PathBuf) -> Self {
+        Self {
+            path,
+            passphrase: None,
+            iterations: KDF_ITERATIONS,
+        }
+    }
+
+    pub fn with_passphrase(path: PathBuf, passphrase: String) -> Self {
+        Self {
+            path,
+            passphrase: Some(passphrase),
+            iterations: KDF_ITERATIONS,
+        }
+    }
+
+    /// Key source recorded in an existing file, without decrypting it.
+    pub fn stored_key_source(path: &Path) -> Result<Option<String>, String> {
+        Ok(read_envelope(path)?.map(|envelope| envelope.key_source))
+    }
+
+    fn key_source(&self) -> &'static str {
+        if self.passphrase.is_some() {
+            KEY_SOURCE_PASSPHRASE
+        } else {
+            KEY_SOURCE_MACHINE
+        }
+    }
+
+    fn secret(&self) -> Result<Vec<u8>, String> {
+        match &self.passphrase {
+            Some(passphrase) => Ok(passphrase.as_bytes().to_vec()),
+            None => machine_secret(),
+        }
+    }
+}
+
+impl SecretBackend for EncryptedFile {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn is_available(&self) -> bool {
+        self.secret().is_ok()
+    }
+
+    fn read(&self) -> Result<Option<String>, String> {
+        let Some(envelope) = read_envelope(&self.path)? else {
+            return Ok(None);
+        };
+        if envelope.key_source != self.key_source() {
+            return Err(format!(
+                "{} is protected by a {} key, not a {} key",
+                self.path.display(),
+                envelope.key_source,
+                self.key_source()
+            ));
+        }
+        restrict_to_owner(&self.path)?;
+        open(&envelope, &self.secret()?, self.iterations)
+            .map(Some)
+            .map_err(|e| format!("{}: {e}", self.path.display()))
+    }
+
+    fn write(&self, secret: &str) -> Result<(), String> {
+        let envelope = seal(secret, &self.secret()?, self.key_source(), self.iterations)?;
+        let serialized = serde_json::to_string_pretty(&envelope)
+            .map_err(|e| format!("failed to serialize encrypted keys: {e}"))?;
+        common::write_private_file_atomic(&self.path, serialized.as_bytes())
+    }
+
+    fn delete(&self) -> Result<(), String> {
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("failed to remove {}: {err}", self.path.display())),
+        }
+    }
+
+    /// The file as stored: every write picks a fresh salt and nonce, so the
+    /// text changes whenever the keys do. The Unix mode is included so
+    /// loosened permissions lead to a full read, which tightens them again.
+    fn fingerprint(&self) -> Result<Option<String>, String> {
+        let raw = match std::fs::read_to_string(&self.path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Some(String::new()))
+            }
+            Err(err) => return Err(format!("failed to read {}: {err}", self.path.display())),
+        };
+        #[cfg(unix)]
+        let raw = {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&self.path)
+                .map(|meta| meta.permissions().mode())
+                .unwrap_or_default();
+            format!("{mode:o}:{raw}")
+        };
+        Ok(Some(raw))
+    }
+}
+
+/// Seals `plaintext` under a passphrase alone, for secrets that leave this
+/// machine (setup bundles).
+pub fn seal_with_passphrase(plaintext: &str, passphrase: &str) -> Result<Envelope, String> {
+    seal(
+        plaintext,
+        passphrase.as_bytes(),
+        KEY_SOURCE_PASSPHRASE,
+        KDF_ITERATIONS,
+    )
+}
+
+pub fn open_with_passphrase(envelope: &Envelope, passphrase: &str) -> Result<String, String> {
+    if envelope.key_source != KEY_SOURCE_PASSPHRASE {
+        return Err(format!(
+            "secrets are protected by a {} key, not a passphrase",
+            envelope.key_source
+        ));
+    }
+    open(envelope, passphrase.as_bytes(), KDF_ITERATIONS)
+}
+
+fn read_envelope(path: &Path) -> Result<Option<Envelope>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    serde_json::from_str(&raw)
+        .map(Some)
+        .map_err(|e| format!("invalid encrypted key file {}: {e}", path.display()))
+}
+
+fn seal(
+    plaintext: &str,
+    secret: &[u8],
+    key_source: &str,
+    iterations: u32,
+) -> Result<Envelope, String> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt)
+        .and_then(|_| rng.fill(&mut nonce))
+        .map_err(|_| "system random number generator failed".to_string())?;
+
+    let key = derive_key(secret, &salt, iterations)?;
+    let mut in_out = plaintext.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(AAD),
+        &mut in_out,
+    )
+    .map_err(|_| "failed to encrypt keys".to_string())?;
+
+    Ok(Envelope {
+        version: FORMAT_VERSION,
+        key_source: key_source.to_string(),
+        kdf: KDF.to_string(),
+        iterations,
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(in_out),
+    })
+}
+
+/// Decrypts `envelope`. Its iteration count must be the one this build
+/// writes (`iterations`), so a crafted file or bundle cannot make key
+/// derivation run for hours.
+fn open(envelope: &Envelope, secret: &[u8], iterations: u32) -> Result<String, String> {
+    if envelope.version != FORMAT_VERSION || envelope.kdf != KDF {
+        return Err(format!(
+            "unsupported encrypted key format (version {}, kdf {})",
+            envelope.version, envelope.kdf
+        ));
+    }
+    if envelope.iterations != iterations {
+        return Err(format!(
+            "unsupported key derivation strength ({} iterations, expected {iterations})",
+            envelope.iterations
+        ));
+    }
+    let salt = hex::decode(&envelope.salt).map_err(|e| format!("corrupt salt: {e}"))?;
+    let nonce: [u8; NONCE_LEN] = hex::decode(&envelope.nonce)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| "corrupt nonce".to_string())?;
+    let mut in_out =
+        hex::decode(&envelope.ciphertext).map_err(|e| format!("corrupt ciphertext: {e}"))?;
+
+    let key = derive_key(secret, &salt, envelope.iterations)?;
+    let plaintext = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(AAD),
+            &mut in_out,
+        )
+        .map_err(|_| {
+            "keys could not be decrypted (wrong passphrase, file was modified or belongs to another machine)"
+                .to_string()
+        })?;
+    String::from_utf8(plaintext.to_vec()).map_err(|e| format!("decrypted keys are not utf-8: {e}"))
+}
+
+fn derive_key(secret: &[u8], salt: &[u8], iterations: u32) -> Result<LessSafeKey, String> {
+    let iterations = NonZeroU32::new(iterations)
+        .ok_or_else(|| "key derivation needs at least one iteration".to_string())?;
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        secret,
+        &mut key,
+    );
+    UnboundKey::new(&AES_256_GCM, &key)
+        .map(LessSafeKey::new)
+        .map_err(|_| "failed to build encryption key".to_string())
+}
+
+/// Machine identifier plus the current user name. Neither is secret on its
+/// own machine, but together they tie the file to this account.
+fn machine_secret() -> Result<Vec<u8>, String> {
+    let machine_id = machine_id()?;
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default();
+    Ok(format!("openclawini:{machine_id}:{user}").into_bytes())
+}
+
+fn machine_id() -> Result<String, String> {
+    if cfg!(target_os = "windows") {
+        let output = Command::new("reg")
+            .args([
+                "query",
+                r"HKLM\SOFTWARE\Microsoft\Cryptography",
+                "/v",
+                "MachineGuid",
+            ])
+            .output()
+            .map_err(|e| format!("failed to query MachineGuid: {e}"))?;
+        return String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.contains("MachineGuid"))
+            .and_then(|line| line.split_whitespace().last())
+            .map(str::to_string)
+            .ok_or_else(|| "MachineGuid not found in registry".to_string());
+    }
+
+    if cfg!(target_os = "macos") {
+        let output = Command::new("ioreg")
+            .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+            .output()
+            .map_err(|e| format!("failed to run ioreg: {e}"))?;
+        return String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.contains("IOPlatformUUID"))
+            .and_then(|line| line.split('"').nth(3))
+            .map(str::to_string)
+            .ok_or_else(|| "IOPlatformUUID not reported by ioreg".to_string());
+    }
+
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .map(|id| id.trim().to_string())
+        .find(|id| !id.is_empty())
+        .ok_or_else(|| "no machine id found in /etc/machine-id".to_string())
+}
+
+/// Tightens a key file that was copied in or loosened by hand back to
+/// owner-only access before it is used.
+#[cfg(unix)]
+fn restrict_to_owner(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = std::fs::metadata(path)
+        .map_err(|e| format!("failed to stat {}: {e}", path.display()))?
+        .permissions()
+        .mode();
+    if mode & 0o077 == 0 {
+        return Ok(());
+    }
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .map_err(|e| format!("failed to restrict permissions on {}: {e}", path.display()))
+}
+
+#[cfg(not(unix))]
+fn restrict_to_owner(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_keys_round_trip_and_detect_tampering() {
+        let plaintext = r#"{"OPENAI_API_KEY":"sk-test"}"#;
+        let envelope = seal(plaintext, b"machine-a", KEY_SOURCE_MACHINE, 10).expect("seal");
+
+        assert!(!envelope.ciphertext.contains("sk-test"));
+        assert_eq!(open(&envelope, b"machine-a", 10).expect("open"), plaintext);
+        assert!(open(&envelope, b"machine-b", 10).is_err());
+
+        let mut tampered = envelope.clone();
+        let flipped = if tampered.ciphertext.starts_with('0') {
+            "1"
+        } else {
+            "0"
+        };
+        tampered.ciphertext.replace_range(0..1, flipped);
+        assert!(open(&tampered, b"machine-a", 10).is_err());
+    }
+
+    #[test]
+    fn envelopes_with_other_iteration_counts_are_rejected() {
+        let mut envelope = seal("{}", b"phrase", KEY_SOURCE_PASSPHRASE, 10).expect("seal");
+        assert!(open(&envelope, b"phrase", 11).is_err());
+
+        envelope.iterations = u32::MAX;
+        let err = open_with_passphrase(&envelope, "phrase").expect_err("refused before deriving");
+        assert!(err.contains("4294967295 iterations"), "{err}");
+    }
+
+    #[test]
+    fn passphrase_file_is_private_and_rejects_other_keys() {
+        let path =
+            std::env::temp_dir().join(format!("openclawini-keys-{}.enc", std::process::id()));
+        let mut store = EncryptedFile::with_passphrase(path.clone(), "correct horse".to_string());
+        store.iterations = 10;
+        store
+            .write(r#"{"OPENAI_API_KEY":"sk-test"}"#)
+            .expect("write");
+
+        assert_eq!(
+            EncryptedFile::stored_key_source(&path).expect("key source"),
+            Some(KEY_SOURCE_PASSPHRASE.to_string())
+        );
+        assert!(store.read().expect("read").is_some());
+
+        let wrong = EncryptedFile::with_passphrase(path.clone(), "battery staple".to_string());
+        assert!(wrong.read().is_err());
+        assert!(EncryptedFile::machine_bound(path.clone()).read().is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &Path| std::fs::metadata(path).expect("stat").permissions().mode();
+            assert_eq!(mode(&path) & 0o777, 0o600);
+
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644))
+                .expect("loosen permissions");
+            store.read().expect("read");
+            assert_eq!(mode(&path) & 0o777, 0o600);
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn every_write_uses_a_fresh_nonce() {
+        let first = seal("{}", b"machine-a", KEY_SOURCE_MACHINE, 10).expect("seal");
+        let second = seal("{}", b"machine-a", KEY_SOURCE_MACHINE, 10).expect("seal");
+        assert_ne!(first.nonce, second.nonce);
+        assert_ne!(first.ciphertext, second.ciphertext);
+    }
+}
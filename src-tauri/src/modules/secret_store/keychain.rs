@@ -0,0 +1,311 @@
+use std::process::Command;
+use std::time::Duration;
+
+use super::SecretBackend;
+use crate::modules::common;
+
+pub const NAME: &str = "keychain";
+
+const SERVICE: &str = "openclawini";
+const ACCOUNT: &str = "api-keys";
+const KEYCHAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The platform credential store: Secret Service (via `secret-tool`) on
+/// Linux, the login Keychain (via `security`) on macOS and Credential
+/// Manager (via the WinRT password vault) on Windows.
+///
+/// The key ring JSON is stored as it is, so the entry reads as JSON in the
+/// platform's own tools. It is passed on stdin or through the child
+/// environment, never on the command line.
+#[derive(Debug, Default)]
+pub struct Keychain;
+
+impl Keychain {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SecretBackend for Keychain {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn is_available(&self) -> bool {
+        platform::is_available()
+    }
+
+    fn read(&self) -> Result<Option<String>, String> {
+        platform::read()?.as_deref().map(decode_stored).transpose()
+    }
+
+    fn write(&self, secret: &str) -> Result<(), String> {
+        platform::write(secret)
+    }
+
+    fn delete(&self) -> Result<(), String> {
+        platform::delete()
+    }
+}
+
+/// The stored key ring JSON. Earlier versions stored it hex-encoded, and
+/// `security` prints values with non-ASCII bytes as hex, so anything that
+/// is not JSON is decoded from hex.
+fn decode_stored(stored: &str) -> Result<String, String> {
+    let stored = stored.trim();
+    if stored.starts_with('{') {
+        return Ok(stored.to_string());
+    }
+    let bytes = hex::decode(stored)
+        .map_err(|e| format!("keychain entry {SERVICE}/{ACCOUNT} is corrupt: {e}"))?;
+    String::from_utf8(bytes)
+        .map_err(|e| format!("keychain entry {SERVICE}/{ACCOUNT} is not utf-8: {e}"))
+}
+
+/// Runs a keychain tool, feeding it `stdin` if given. A tool stuck on a
+/// locked keyring's unlock prompt is killed after `KEYCHAIN_TIMEOUT`.
+fn run(mut command: Command, stdin: Option<&str>) -> Result<std::process::Output, String> {
+    let program = command.get_program().to_string_lossy().to_string();
+    match stdin {
+        Some(input) => {
+            common::output_with_input_and_timeout(&mut command, input.as_bytes(), KEYCHAIN_TIMEOUT)
+        }
+        None => common::output_with_timeout(&mut command, KEYCHAIN_TIMEOUT),
+    }
+    .map_err(|e| format!("{program}: {e}"))
+}
+
+fn stderr_of(output: &std::process::Output) -> String {
+    String::from_utf8_lossy(&output.stderr).trim().to_string()
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::*;
+
+    const LABEL: &str = "OpenClawini API keys";
+
+    fn lookup() -> Result<std::process::Output, String> {
+        let mut command = Command::new("secret-tool");
+        command.args(["lookup", "service", SERVICE, "account", ACCOUNT]);
+        run(command, None)
+    }
+
+    pub fn is_available() -> bool {
+        if std::env::var_os("DBUS_SESSION_BUS_ADDRESS").is_none() {
+            return false;
+        }
+        // A missing item exits 1 quietly; a missing or locked-down bus
+        // explains itself on stderr.
+        match lookup() {
+            Ok(output) => output.status.success() || stderr_of(&output).is_empty(),
+            Err(_) => false,
+        }
+    }
+
+    pub fn read() -> Result<Option<String>, String> {
+        let output = lookup()?;
+        if output.status.success() {
+            return Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()));
+        }
+        let stderr = stderr_of(&output);
+        if stderr.is_empty() {
+            Ok(None)
+        } else {
+            Err(format!("secret-tool lookup failed: {stderr}"))
+        }
+    }
+
+    pub fn write(secret: &str) -> Result<(), String> {
+        let mut command = Command::new("secret-tool");
+        command.args([
+            "store", "--label", LABEL, "service", SERVICE, "account", ACCOUNT,
+        ]);
+        let output = run(command, Some(secret))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!("secret-tool store failed: {}", stderr_of(&output)))
+        }
+    }
+
+    pub fn delete() -> Result<(), String> {
+        let mut command = Command::new("secret-tool");
+        command.args(["clear", "service", SERVICE, "account", ACCOUNT]);
+        // Like lookup, a missing item exits 1 with nothing on stderr.
+        let output = run(command, None)?;
+        let stderr = stderr_of(&output);
+        if output.status.success() || stderr.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("secret-tool clear failed: {stderr}"))
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::*;
+
+    const LABEL: &str = "OpenClawini API keys";
+
+    /// `security` exit code for "The specified item could not be found".
+    const ITEM_NOT_FOUND: i32 = 44;
+
+    pub fn is_available() -> bool {
+        let mut command = Command::new("security");
+        command.arg("default-keychain");
+        run(command, None)
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    pub fn read() -> Result<Option<String>, String> {
+        let mut command = Command::new("security");
+        command.args(["find-generic-password", "-s", SERVICE, "-a", ACCOUNT, "-w"]);
+        let output = run(command, None)?;
+        if output.status.success() {
+            Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
+        } else if output.status.code() == Some(ITEM_NOT_FOUND) {
+            Ok(None)
+        } else {
+            Err(format!(
+                "security find-generic-password failed: {}",
+                stderr_of(&output)
+            ))
+        }
+    }
+
+    pub fn write(secret: &str) -> Result<(), String> {
+        // Interactive mode reads the command from stdin, keeping the
+        // secret out of the process list. `-X` takes the password as hex
+        // so the script needs no quoting; the item stores the raw bytes.
+        let mut command = Command::new("security");
+        command.arg("-i");
+        let script = format!(
+            "add-generic-password -U -s {SERVICE} -a {ACCOUNT} -l \"{LABEL}\" -X {}\n",
+            hex::encode(secret.as_bytes())
+        );
+        let output = run(command, Some(&script))?;
+        let stderr = stderr_of(&output);
+        if output.status.success() && stderr.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("security add-generic-password failed: {stderr}"))
+        }
+    }
+
+    pub fn delete() -> Result<(), String> {
+        let mut command = Command::new("security");
+        command.args(["delete-generic-password", "-s", SERVICE, "-a", ACCOUNT]);
+        let output = run(command, None)?;
+        if output.status.success() || output.status.code() == Some(ITEM_NOT_FOUND) {
+            Ok(())
+        } else {
+            Err(format!(
+                "security delete-generic-password failed: {}",
+                stderr_of(&output)
+            ))
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::*;
+
+    const SECRET_ENV: &str = "OPENCLAWINI_SECRET";
+    const LOAD_VAULT: &str = "$null = [Windows.Security.Credentials.PasswordVault,Windows.Security.Credentials,ContentType=WindowsRuntime]; $vault = New-Object Windows.Security.Credentials.PasswordVault";
+
+    fn powershell(script: &str, secret: Option<&str>) -> Result<std::process::Output, String> {
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-NonInteractive", "-Command", script]);
+        if let Some(secret) = secret {
+            command.env(SECRET_ENV, secret);
+        }
+        run(command, None)
+    }
+
+    pub fn is_available() -> bool {
+        powershell(&format!("{LOAD_VAULT}; 'ok'"), None)
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    pub fn read() -> Result<Option<String>, String> {
+        let script = format!(
+            "[Console]::OutputEncoding = [Text.Encoding]::UTF8; {LOAD_VAULT}; try {{ $c = $vault.Retrieve('{SERVICE}', '{ACCOUNT}') }} catch {{ exit 3 }}; $c.RetrievePassword(); $c.Password"
+        );
+        let output = powershell(&script, None)?;
+        match output.status.code() {
+            Some(0) => Ok(Some(String::from_utf8_lossy(&output.stdout).to_string())),
+            Some(3) => Ok(None),
+            _ => Err(format!(
+                "credential manager read failed: {}",
+                stderr_of(&output)
+            )),
+        }
+    }
+
+    pub fn write(secret: &str) -> Result<(), String> {
+        let script = format!(
+            "{LOAD_VAULT}; $vault.Add((New-Object Windows.Security.Credentials.PasswordCredential('{SERVICE}', '{ACCOUNT}', $env:{SECRET_ENV})))"
+        );
+        let output = powershell(&script, Some(secret))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "credential manager write failed: {}",
+                stderr_of(&output)
+            ))
+        }
+    }
+
+    pub fn delete() -> Result<(), String> {
+        let script = format!(
+            "{LOAD_VAULT}; try {{ $c = $vault.Retrieve('{SERVICE}', '{ACCOUNT}') }} catch {{ exit 0 }}; $vault.Remove($c)"
+        );
+        let output = powershell(&script, None)?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "credential manager delete failed: {}",
+                stderr_of(&output)
+            ))
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub fn is_available() -> bool {
+        false
+    }
+
+    pub fn read() -> Result<Option<String>, String> {
+        Err("no platform keychain on this OS".to_string())
+    }
+
+    pub fn write(_secret: &str) -> Result<(), String> {
+        Err("no platform keychain on this OS".to_string())
+    }
+
+    pub fn delete() -> Result<(), String> {
+        Err("no platform keychain on this OS".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_read_raw_or_from_legacy_hex() {
+        let json = r#"{"version":2,"slots":{}}"#;
+        assert_eq!(decode_stored(&format!("{json}\n")).expect("raw"), json);
+        assert_eq!(decode_stored(&hex::encode(json)).expect("legacy hex"), json);
+        assert!(decode_stored("not hex").is_err());
+    }
+}
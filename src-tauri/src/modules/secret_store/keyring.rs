@@ -0,0 +1,368 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const DEFAULT_LABEL: &str = "default";
+const FORMAT_VERSION: u32 = 2;
+const MAX_LABEL_LEN: usize = 40;
+
+/// Every stored value, by env var name. Each env var can hold several
+/// labelled values ("personal", "work") of which one is selected and handed
+/// to the gateway.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyRing {
+    version: u32,
+    slots: BTreeMap<String, KeySlot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct KeySlot {
+    selected: String,
+    keys: Vec<StoredKey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct StoredKey {
+    label: String,
+    value: String,
+    /// `None` for values migrated from the unlabelled format.
+    #[serde(default)]
+    added_at: Option<String>,
+    #[serde(default)]
+    rotated_at: Option<String>,
+}
+
+/// What the UI may see of a stored value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeySummary {
+    pub env_var: String,
+    pub label: String,
+    pub preview: String,
+    pub selected: bool,
+    pub added_at: Option<String>,
+    pub rotated_at: Option<String>,
+}
+
+/// Stored secrets before labels existed were a flat env var → value map.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredFormat {
+    Labelled(KeyRing),
+    Flat(HashMap<String, String>),
+}
+
+impl KeyRing {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        match serde_json::from_str::<StoredFormat>(raw).map_err(|e| e.to_string())? {
+            StoredFormat::Labelled(ring) => Ok(ring),
+            StoredFormat::Flat(values) => {
+                let mut ring = Self::default();
+                for (env_var, value) in values {
+                    ring.insert(&env_var, DEFAULT_LABEL, value, None);
+                }
+                Ok(ring)
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        let ring = Self {
+            version: FORMAT_VERSION,
+            slots: self.slots.clone(),
+        };
+        serde_json::to_string(&ring).map_err(|e| format!("failed to serialize keys: {e}"))
+    }
+
+    /// The selected value of every env var.
+    pub fn selected(&self) -> HashMap<String, String> {
+        self.slots
+            .iter()
+            .filter_map(|(env_var, slot)| {
+                slot.keys
+                    .iter()
+                    .find(|key| key.label == slot.selected)
+                    .map(|key| (env_var.clone(), key.value.clone()))
+            })
+            .collect()
+    }
+
+    /// Replaces the selected value of each env var, storing it as
+    /// [`DEFAULT_LABEL`] when the env var has no values yet.
+    pub fn set_selected(&mut self, values: HashMap<String, String>, now: &str) {
+        for (env_var, value) in values {
+            let label = self
+                .slots
+                .get(&env_var)
+                .map(|slot| slot.selected.clone())
+                .unwrap_or_else(|| DEFAULT_LABEL.to_string());
+            self.put(&env_var, &label, value, now);
+        }
+    }
+
+    /// Adds values for env vars that have none, leaving existing ones alone.
+    pub fn fill_missing(&mut self, values: HashMap<String, String>, now: &str) {
+        for (env_var, value) in values {
+            if !self.slots.contains_key(&env_var) {
+                self.put(&env_var, DEFAULT_LABEL, value, now);
+            }
+        }
+    }
+
+    /// Stores `value` under `label`, replacing a value with the same label,
+    /// and selects it.
+    pub fn put(&mut self, env_var: &str, label: &str, value: String, now: &str) {
+        self.insert(env_var, label, value, Some(now));
+    }
+
+    /// Stores `value` like [`put`](Self::put) but selects it only when the
+    /// env var had no values yet.
+    pub fn add(&mut self, env_var: &str, label: &str, value: String, now: &str) {
+        let previous = self.slots.get(env_var).map(|slot| slot.selected.clone());
+        self.put(env_var, label, value, now);
+        if let (Some(previous), Some(slot)) = (previous, self.slots.get_mut(env_var)) {
+            slot.selected = previous;
+        }
+    }
+
+    fn insert(&mut self, env_var: &str, label: &str, value: String, now: Option<&str>) {
+        let slot = self
+            .slots
+            .entry(env_var.to_string())
+            .or_insert_with(|| KeySlot {
+                selected: label.to_string(),
+                keys: Vec::new(),
+            });
+        match slot.keys.iter_mut().find(|key| key.label == label) {
+            Some(key) if key.value == value => {}
+            Some(key) => {
+                key.value = value;
+                key.rotated_at = now.map(str::to_string);
+            }
+            None => slot.keys.push(StoredKey {
+                label: label.to_string(),
+                value,
+                added_at: now.map(str::to_string),
+                rotated_at: None,
+            }),
+        }
+        slot.selected = label.to_string();
+    }
+
+    pub fn select(&mut self, env_var: &str, label: &str) -> Result<(), String> {
+        let slot = self.slot_with(env_var, label)?;
+        slot.selected = label.to_string();
+        Ok(())
+    }
+
+    /// Replaces the value behind an existing label without changing which
+    /// label is selected.
+    pub fn rotate(
+        &mut self,
+        env_var: &str,
+        label: &str,
+        value: String,
+        now: &str,
+    ) -> Result<(), String> {
+        let slot = self.slot_with(env_var, label)?;
+        let key = slot
+            .keys
+            .iter_mut()
+            .find(|key| key.label == label)
+            .expect("label checked above");
+        key.value = value;
+        key.rotated_at = Some(now.to_string());
+        Ok(())
+    }
+
+    /// Deletes a labelled value. Deleting the selected one selects the
+    /// first remaining value; deleting the last one removes the env var.
+    pub fn remove(&mut self, env_var: &str, label: &str) -> Result<(), String> {
+        let slot = self.slot_with(env_var, label)?;
+        slot.keys.retain(|key| key.label != label);
+        match slot.keys.first() {
+            None => {
+                self.slots.remove(env_var);
+            }
+            Some(first) if slot.selected == label => slot.selected = first.label.clone(),
+            Some(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Adds every labelled value of `other` whose label is not taken yet.
+    /// Env vars that already had values keep their selection; new ones take
+    /// the selection from `other`. Returns what was added.
+    pub fn merge_missing(&mut self, other: &KeyRing, now: &str) -> Vec<KeySummary> {
+        let mut added = Vec::new();
+        for (env_var, slot) in &other.slots {
+            let is_new = !self.slots.contains_key(env_var);
+            for key in &slot.keys {
+                if !self.has_label(env_var, &key.label) {
+                    self.add(env_var, &key.label, key.value.clone(), now);
+                    added.push((env_var.clone(), key.label.clone()));
+                }
+            }
+            if is_new {
+                let _ = self.select(env_var, &slot.selected);
+            }
+        }
+        self.summaries()
+            .into_iter()
+            .filter(|summary| added.contains(&(summary.env_var.clone(), summary.label.clone())))
+            .collect()
+    }
+
+    pub fn has_label(&self, env_var: &str, label: &str) -> bool {
+        self.slots
+            .get(env_var)
+            .map(|slot| slot.keys.iter().any(|key| key.label == label))
+            .unwrap_or(false)
+    }
+
+    /// Whether `value` is stored for `env_var` under any label.
+    pub fn contains_value(&self, env_var: &str, value: &str) -> bool {
+        self.slots
+            .get(env_var)
+            .map(|slot| slot.keys.iter().any(|key| key.value == value))
+            .unwrap_or(false)
+    }
+
+    pub fn summaries(&self) -> Vec<KeySummary> {
+        self.slots
+            .iter()
+            .flat_map(|(env_var, slot)| {
+                slot.keys.iter().map(move |key| KeySummary {
+                    env_var: env_var.clone(),
+                    label: key.label.clone(),
+                    preview: mask(&key.value),
+                    selected: key.label == slot.selected,
+                    added_at: key.added_at.clone(),
+                    rotated_at: key.rotated_at.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn slot_with(&mut self, env_var: &str, label: &str) -> Result<&mut KeySlot, String> {
+        self.slots
+            .get_mut(env_var)
+            .filter(|slot| slot.keys.iter().any(|key| key.label == label))
+            .ok_or_else(|| format!("no stored {env_var} labelled {label}"))
+    }
+}
+
+pub fn validate_label(label: &str) -> Result<String, String> {
+    let label = label.trim();
+    if label.is_empty() || label.chars().count() > MAX_LABEL_LEN {
+        return Err(format!("key label must be 1 to {MAX_LABEL_LEN} characters"));
+    }
+    if label.chars().any(char::is_control) {
+        return Err("key label contains control characters".to_string());
+    }
+    Ok(label.to_string())
+}
+
+/// Shows enough of a value to tell keys apart: the first and last four
+/// characters of long values, only the last two of short ones.
+pub fn mask(value: &str) -> String {
+    let chars = value.chars().collect::<Vec<_>>();
+    let tail = |count: usize| chars[chars.len() - count..].iter().collect::<String>();
+    match chars.len() {
+        0 => String::new(),
+        len if len >= 16 => format!("{}…{}", chars[..4].iter().collect::<String>(), tail(4)),
+        len if len >= 6 => format!("…{}", tail(2)),
+        _ => "…".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_maps_become_default_labels() {
+        let ring = KeyRing::parse(r#"{"ANTHROPIC_API_KEY":"sk-ant-1"}"#).expect("legacy map");
+        assert_eq!(ring.selected()["ANTHROPIC_API_KEY"], "sk-ant-1");
+        assert!(ring.has_label("ANTHROPIC_API_KEY", DEFAULT_LABEL));
+
+        let round_trip = KeyRing::parse(&ring.to_json().expect("serialize")).expect("labelled");
+        assert_eq!(round_trip.selected(), ring.selected());
+    }
+
+    #[test]
+    fn labelled_keys_can_be_selected_rotated_and_removed() {
+        let env = "ANTHROPIC_API_KEY";
+        let mut ring = KeyRing::default();
+        ring.put(env, "personal", "sk-ant-personal-0001".to_string(), "t1");
+        ring.put(env, "work", "sk-ant-work-0000002".to_string(), "t2");
+        assert_eq!(ring.selected()[env], "sk-ant-work-0000002");
+
+        ring.select(env, "personal").expect("select");
+        ring.set_selected(
+            HashMap::from([(env.to_string(), "sk-ant-personal-0003".to_string())]),
+            "t3",
+        );
+        ring.rotate(env, "work", "sk-ant-work-0000004".to_string(), "t4")
+            .expect("rotate");
+        assert_eq!(ring.selected()[env], "sk-ant-personal-0003");
+
+        let summaries = ring.summaries();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].preview, "sk-a…0003");
+        assert!(summaries[0].selected);
+        assert_eq!(summaries[1].rotated_at.as_deref(), Some("t4"));
+        assert!(summaries
+            .iter()
+            .all(|summary| !summary.preview.contains("personal")));
+
+        ring.remove(env, "personal").expect("remove selected");
+        assert_eq!(ring.selected()[env], "sk-ant-work-0000004");
+        ring.remove(env, "work").expect("remove last");
+        assert!(ring.selected().is_empty());
+        assert!(ring.select(env, "work").is_err());
+    }
+
+    #[test]
+    fn added_keys_keep_the_current_selection() {
+        let env = "OPENAI_API_KEY";
+        let mut ring = KeyRing::default();
+        ring.add(env, "imported", "sk-first".to_string(), "t1");
+        assert_eq!(ring.selected()[env], "sk-first");
+
+        ring.add(env, "dotenv", "sk-second".to_string(), "t2");
+        assert_eq!(ring.selected()[env], "sk-first");
+        assert!(ring.contains_value(env, "sk-second"));
+        assert!(!ring.contains_value(env, "sk-third"));
+    }
+
+    #[test]
+    fn merging_adds_only_untaken_labels() {
+        let mut local = KeyRing::default();
+        local.put("OPENAI_API_KEY", "work", "sk-local".to_string(), "t0");
+        let mut bundled = KeyRing::default();
+        bundled.put("OPENAI_API_KEY", "work", "sk-other".to_string(), "t0");
+        bundled.put("OPENAI_API_KEY", "team", "sk-team".to_string(), "t0");
+        bundled.put("GROQ_API_KEY", "a", "gsk-a".to_string(), "t0");
+        bundled.put("GROQ_API_KEY", "b", "gsk-b".to_string(), "t0");
+        bundled.select("GROQ_API_KEY", "a").expect("select");
+
+        let added = local.merge_missing(&bundled, "t1");
+
+        let added = added
+            .iter()
+            .map(|summary| format!("{}/{}", summary.env_var, summary.label))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            added,
+            vec!["GROQ_API_KEY/a", "GROQ_API_KEY/b", "OPENAI_API_KEY/team"]
+        );
+        assert_eq!(local.selected()["OPENAI_API_KEY"], "sk-local");
+        assert_eq!(local.selected()["GROQ_API_KEY"], "gsk-a");
+    }
+
+    #[test]
+    fn short_values_reveal_little() {
+        assert_eq!(mask("abc"), "…");
+        assert_eq!(mask("eu-west-1"), "…-1");
+        assert_eq!(mask(""), "");
+    }
+}
@@ -0,0 +1,478 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use tauri::AppHandle;
+
+use crate::modules::common;
+
+mod encrypted_file;
+mod keychain;
+mod keyring;
+
+pub use encrypted_file::{open_with_passphrase, seal_with_passphrase, EncryptedFile, Envelope};
+pub use keychain::Keychain;
+pub use keyring::{mask, validate_label, KeyRing, KeySummary, DEFAULT_LABEL};
+
+const LOCKED_ERROR: &str = "API key store is locked; unlock it with your passphrase";
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretStoreStatus {
+    pub backend: Option<String>,
+    pub key_source: Option<String>,
+    pub locked: bool,
+}
+
+/// Somewhere to keep the [`KeyRing`]. Backends store it as one opaque secret
+/// so a single keychain prompt covers every provider.
+pub trait SecretBackend {
+    fn name(&self) -> &'static str;
+    fn is_available(&self) -> bool;
+    /// Returns `None` when nothing has been stored yet.
+    fn read(&self) -> Result<Option<String>, String>;
+    fn write(&self, secret: &str) -> Result<(), String>;
+    /// Removes the stored secret. Nothing stored is not an error.
+    fn delete(&self) -> Result<(), String>;
+    /// Something that changes whenever the stored secret does and is cheap
+    /// to get, used to tell whether a cached ring is current. `None` when
+    /// the backend has nothing cheaper than `read`; the cached ring is then
+    /// trusted, as only this app writes the entry.
+    fn fingerprint(&self) -> Result<Option<String>, String> {
+        Ok(None)
+    }
+}
+
+/// The ring last read from or written to each backend, so a command that
+/// loads keys several times derives the file key or runs the keychain tool
+/// once. Dropped when the store is locked.
+struct CachedRing {
+    fingerprint: Option<String>,
+    ring: KeyRing,
+}
+
+fn ring_cache() -> &'static Mutex<HashMap<&'static str, CachedRing>> {
+    static RINGS: OnceLock<Mutex<HashMap<&'static str, CachedRing>>> = OnceLock::new();
+    RINGS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn cached_ring(backend: &'static str, fingerprint: &Option<String>) -> Option<KeyRing> {
+    ring_cache()
+        .lock()
+        .ok()?
+        .get(backend)
+        .filter(|cached| &cached.fingerprint == fingerprint)
+        .map(|cached| cached.ring.clone())
+}
+
+fn remember_ring(backend: &'static str, fingerprint: Option<String>, ring: &KeyRing) {
+    if let Ok(mut cache) = ring_cache().lock() {
+        cache.insert(
+            backend,
+            CachedRing {
+                fingerprint,
+                ring: ring.clone(),
+            },
+        );
+    }
+}
+
+fn forget_ring(backend: &str) {
+    if let Ok(mut cache) = ring_cache().lock() {
+        cache.remove(backend);
+    }
+}
+
+fn forget_rings() {
+    if let Ok(mut cache) = ring_cache().lock() {
+        cache.clear();
+    }
+}
+
+/// Passphrase for a passphrase-protected key file, held only in memory
+/// between `unlock_secret_store` and `lock_secret_store`.
+fn session_passphrase() -> &'static Mutex<Option<String>> {
+    static PASSPHRASE: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+    PASSPHRASE.get_or_init(|| Mutex::new(None))
+}
+
+fn current_passphrase() -> Result<Option<String>, String> {
+    session_passphrase()
+        .lock()
+        .map(|guard| guard.clone())
+        .map_err(|_| "secret store passphrase lock poisoned".to_string())
+}
+
+fn set_session_passphrase(passphrase: Option<String>) -> Result<(), String> {
+    let mut guard = session_passphrase()
+        .lock()
+        .map_err(|_| "secret store passphrase lock poisoned".to_string())?;
+    *guard = passphrase;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_secret_store_status(app: AppHandle) -> Result<SecretStoreStatus, String> {
+    status(&app)
+}
+
+/// Checks `passphrase` against the key file and keeps it in memory so keys
+/// can be read and saved until the store is locked again or the app exits.
+#[tauri::command]
+pub fn unlock_secret_store(
+    app: AppHandle,
+    passphrase: String,
+) -> Result<SecretStoreStatus, String> {
+    let path = common::encrypted_keys_path(&common::app_data_dir(&app)?);
+    let key_source = EncryptedFile::stored_key_source(&path)?;
+    if key_source.as_deref() != Some(encrypted_file::KEY_SOURCE_PASSPHRASE) {
+        return Err("API key store is not protected by a passphrase".to_string());
+    }
+
+    EncryptedFile::with_passphrase(path, passphrase.clone()).read()?;
+    set_session_passphrase(Some(passphrase))?;
+    status(&app)
+}
+
+#[tauri::command]
+pub fn lock_secret_store(app: AppHandle) -> Result<SecretStoreStatus, String> {
+    set_session_passphrase(None)?;
+    forget_rings();
+    status(&app)
+}
+
+/// Re-encrypts the stored keys into `<app_data>/keys.enc` under a new
+/// passphrase, or under the machine-bound key when `passphrase` is `None`,
+/// and makes the encrypted file the active backend. The copy held by the
+/// previous backend is removed once the new one is recorded. The store must
+/// be unlocked first if it is currently passphrase-protected.
+#[tauri::command]
+pub fn set_secret_store_passphrase(
+    app: AppHandle,
+    passphrase: Option<String>,
+) -> Result<SecretStoreStatus, String> {
+    if let Some(value) = passphrase.as_deref() {
+        if value.chars().count() < MIN_PASSPHRASE_LEN {
+            return Err(format!(
+                "passphrase must be at least {MIN_PASSPHRASE_LEN} characters"
+            ));
+        }
+    }
+
+    let ring = load_keyring(&app)?;
+    let previous = backend(&app)?;
+    let path = common::encrypted_keys_path(&common::app_data_dir(&app)?);
+    let target = match passphrase.clone() {
+        Some(value) => EncryptedFile::with_passphrase(path, value),
+        None => EncryptedFile::machine_bound(path),
+    };
+    move_ring(&ring, previous.as_ref(), &target, || {
+        common::set_secret_backend(&app, encrypted_file::NAME)
+    })?;
+    set_session_passphrase(passphrase)?;
+    status(&app)
+}
+
+fn status(app: &AppHandle) -> Result<SecretStoreStatus, String> {
+    let backend = common::get_secret_backend(app)?;
+    if backend.as_deref() != Some(encrypted_file::NAME) {
+        return Ok(SecretStoreStatus {
+            backend,
+            key_source: None,
+            locked: false,
+        });
+    }
+
+    let path = common::encrypted_keys_path(&common::app_data_dir(app)?);
+    let key_source = EncryptedFile::stored_key_source(&path)?;
+    let locked = key_source.as_deref() == Some(encrypted_file::KEY_SOURCE_PASSPHRASE)
+        && current_passphrase()?.is_none();
+    Ok(SecretStoreStatus {
+        backend,
+        key_source,
+        locked,
+    })
+}
+
+pub fn is_locked(app: &AppHandle) -> Result<bool, String> {
+    status(app).map(|status| status.locked)
+}
+
+/// Loads the selected value of every stored key (env var name → value),
+/// migrating a legacy plaintext `keys.json` into the secret store first.
+pub fn load_secrets(app: &AppHandle) -> Result<HashMap<String, String>, String> {
+    load_keyring(app).map(|ring| ring.selected())
+}
+
+/// Replaces the selected value of each given env var; other labelled values
+/// are kept.
+pub fn save_secrets(app: &AppHandle, secrets: &HashMap<String, String>) -> Result<(), String> {
+    let mut ring = load_keyring(app)?;
+    ring.set_selected(secrets.clone(), &common::iso_utc_now());
+    save_keyring(app, &ring)
+}
+
+pub fn load_keyring(app: &AppHandle) -> Result<KeyRing, String> {
+    let backend = backend(app)?;
+    let app_data_dir = common::app_data_dir(app)?;
+    migrate_plaintext_keys(&common::keys_file_path(&app_data_dir), backend.as_ref())?;
+    read_ring(backend.as_ref())
+}
+
+pub fn save_keyring(app: &AppHandle, ring: &KeyRing) -> Result<(), String> {
+    let backend = backend(app)?;
+    let app_data_dir = common::app_data_dir(app)?;
+    migrate_plaintext_keys(&common::keys_file_path(&app_data_dir), backend.as_ref())?;
+    write_ring(backend.as_ref(), ring)
+}
+
+/// Resolves the backend recorded in settings. On first use the platform
+/// keychain is preferred and the encrypted file is the fallback; the choice
+/// is then kept so keys are never silently split across two stores.
+fn backend(app: &AppHandle) -> Result<Box<dyn SecretBackend>, String> {
+    let app_data_dir = common::app_data_dir(app)?;
+    if let Some(name) = common::get_secret_backend(app)? {
+        return backend_by_name(&name, &app_data_dir);
+    }
+
+    let keychain = Keychain::new();
+    let chosen: Box<dyn SecretBackend> = if keychain.is_available() {
+        Box::new(keychain)
+    } else {
+        Box::new(EncryptedFile::machine_bound(common::encrypted_keys_path(
+            &app_data_dir,
+        )))
+    };
+    common::set_secret_backend(app, chosen.name())?;
+    Ok(chosen)
+}
+
+fn backend_by_name(name: &str, app_data_dir: &Path) -> Result<Box<dyn SecretBackend>, String> {
+    match name {
+        keychain::NAME => Ok(Box::new(Keychain::new())),
+        encrypted_file::NAME => Ok(Box::new(encrypted_backend(app_data_dir)?)),
+        other => Err(format!("unknown secret storage backend: {other}")),
+    }
+}
+
+/// Opens `keys.enc` with the key it was written with. A passphrase-protected
+/// file can only be used while the store is unlocked.
+fn encrypted_backend(app_data_dir: &Path) -> Result<EncryptedFile, String> {
+    let path = common::encrypted_keys_path(app_data_dir);
+    match EncryptedFile::stored_key_source(&path)?.as_deref() {
+        Some(encrypted_file::KEY_SOURCE_PASSPHRASE) => current_passphrase()?
+            .map(|passphrase| EncryptedFile::with_passphrase(path, passphrase))
+            .ok_or_else(|| LOCKED_ERROR.to_string()),
+        _ => Ok(EncryptedFile::machine_bound(path)),
+    }
+}
+
+fn read_ring(backend: &dyn SecretBackend) -> Result<KeyRing, String> {
+    let fingerprint = backend.fingerprint()?;
+    if let Some(ring) = cached_ring(backend.name(), &fingerprint) {
+        return Ok(ring);
+    }
+    let ring = match backend.read()? {
+        Some(raw) => KeyRing::parse(&raw)
+            .map_err(|e| format!("invalid key map in {} storage: {e}", backend.name()))?,
+        None => KeyRing::default(),
+    };
+    remember_ring(backend.name(), fingerprint, &ring);
+    Ok(ring)
+}
+
+fn write_ring(backend: &dyn SecretBackend, ring: &KeyRing) -> Result<(), String> {
+    backend.write(&ring.to_json()?)?;
+    remember_ring(backend.name(), backend.fingerprint()?, ring);
+    Ok(())
+}
+
+/// Writes `ring` to `to`, runs `record` to make `to` the active backend and
+/// only then deletes the copy in `from`, so a failure at any step leaves the
+/// keys readable from the backend settings point at.
+fn move_ring(
+    ring: &KeyRing,
+    from: &dyn SecretBackend,
+    to: &dyn SecretBackend,
+    record: impl FnOnce() -> Result<(), String>,
+) -> Result<(), String> {
+    write_ring(to, ring)?;
+    record()?;
+    if from.name() == to.name() {
+        return Ok(());
+    }
+    forget_ring(from.name());
+    from.delete().map_err(|e| {
+        format!(
+            "keys moved to {} storage but the old {} copy could not be removed: {e}",
+            to.name(),
+            from.name()
+        )
+    })
+}
+
+/// Moves entries from a plaintext `keys.json` into `backend` and deletes the
+/// file. Env vars that already have a value in the store keep it.
+fn migrate_plaintext_keys(keys_path: &Path, backend: &dyn SecretBackend) -> Result<(), String> {
+    if !keys_path.exists() {
+        return Ok(());
+    }
+
+    let raw = std::fs::read_to_string(keys_path)
+        .map_err(|e| format!("failed to read {}: {e}", keys_path.display()))?;
+    let plaintext: HashMap<String, String> = serde_json::from_str(&raw)
+        .map_err(|e| format!("invalid keys json in {}: {e}", keys_path.display()))?;
+    let mut ring = read_ring(backend)?;
+    ring.fill_missing(plaintext, &common::iso_utc_now());
+    write_ring(backend, &ring)?;
+
+    std::fs::remove_file(keys_path).map_err(|e| {
+        format!(
+            "migrated keys but failed to remove {}: {e}",
+            keys_path.display()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    #[derive(Default)]
+    struct MemoryBackend {
+        name: &'static str,
+        secret: RefCell<Option<String>>,
+        reads: Cell<usize>,
+    }
+
+    impl SecretBackend for MemoryBackend {
+        fn name(&self) -> &'static str {
+            if self.name.is_empty() {
+                "memory"
+            } else {
+                self.name
+            }
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn read(&self) -> Result<Option<String>, String> {
+            self.reads.set(self.reads.get() + 1);
+            Ok(self.secret.borrow().clone())
+        }
+
+        fn write(&self, secret: &str) -> Result<(), String> {
+            *self.secret.borrow_mut() = Some(secret.to_string());
+            Ok(())
+        }
+
+        fn delete(&self) -> Result<(), String> {
+            *self.secret.borrow_mut() = None;
+            Ok(())
+        }
+
+        fn fingerprint(&self) -> Result<Option<String>, String> {
+            Ok(self.secret.borrow().clone())
+        }
+    }
+
+    #[test]
+    fn rings_are_read_once_until_the_store_changes() {
+        let backend = MemoryBackend {
+            name: "cached",
+            ..Default::default()
+        };
+        let mut ring = KeyRing::default();
+        ring.set_selected(
+            HashMap::from([("OPENAI_API_KEY".to_string(), "sk-one".to_string())]),
+            "2026-01-01T00:00:00Z",
+        );
+        write_ring(&backend, &ring).expect("write ring");
+
+        read_ring(&backend).expect("first read");
+        read_ring(&backend).expect("second read");
+        assert_eq!(backend.reads.get(), 0, "a written ring is cached");
+
+        ring.set_selected(
+            HashMap::from([("OPENAI_API_KEY".to_string(), "sk-two".to_string())]),
+            "2026-01-02T00:00:00Z",
+        );
+        backend
+            .write(&ring.to_json().expect("serialize"))
+            .expect("change behind the cache");
+        assert_eq!(
+            read_ring(&backend).expect("reread").selected()["OPENAI_API_KEY"],
+            "sk-two"
+        );
+        read_ring(&backend).expect("cached again");
+        assert_eq!(backend.reads.get(), 1);
+    }
+
+    #[test]
+    fn plaintext_keys_are_migrated_once_and_removed() {
+        let keys_path =
+            std::env::temp_dir().join(format!("openclawini-keys-{}.json", std::process::id()));
+        std::fs::write(
+            &keys_path,
+            r#"{"ANTHROPIC_API_KEY":"sk-old","OPENAI_API_KEY":"sk-openai"}"#,
+        )
+        .expect("write plaintext keys");
+
+        let backend = MemoryBackend::default();
+        backend
+            .write(r#"{"ANTHROPIC_API_KEY":"sk-new"}"#)
+            .expect("seed store");
+
+        migrate_plaintext_keys(&keys_path, &backend).expect("migration succeeds");
+
+        assert!(!keys_path.exists());
+        let keys = read_ring(&backend).expect("read migrated keys").selected();
+        assert_eq!(keys["ANTHROPIC_API_KEY"], "sk-new");
+        assert_eq!(keys["OPENAI_API_KEY"], "sk-openai");
+
+        migrate_plaintext_keys(&keys_path, &backend).expect("second run is a no-op");
+        assert_eq!(read_ring(&backend).expect("read keys").selected().len(), 2);
+    }
+
+    #[test]
+    fn moving_the_ring_removes_the_previous_copy() {
+        let mut ring = KeyRing::default();
+        ring.set_selected(
+            HashMap::from([("OPENAI_API_KEY".to_string(), "sk-openai".to_string())]),
+            "2026-01-01T00:00:00Z",
+        );
+        let keychain = MemoryBackend {
+            name: "keychain",
+            ..Default::default()
+        };
+        write_ring(&keychain, &ring).expect("seed keychain");
+        let file = MemoryBackend {
+            name: "encrypted_file",
+            ..Default::default()
+        };
+
+        let err = move_ring(&ring, &keychain, &file, || Err("settings".to_string()))
+            .expect_err("recording fails");
+        assert_eq!(err, "settings");
+        assert!(
+            keychain.read().expect("read").is_some(),
+            "kept until the switch is recorded"
+        );
+
+        move_ring(&ring, &keychain, &file, || Ok(())).expect("move succeeds");
+        assert_eq!(keychain.read().expect("read"), None);
+        assert_eq!(
+            read_ring(&file).expect("read moved keys").selected()["OPENAI_API_KEY"],
+            "sk-openai"
+        );
+
+        move_ring(&ring, &file, &file, || Ok(())).expect("re-encrypt in place");
+        assert!(
+            file.read().expect("read").is_some(),
+            "same backend is not deleted"
+        );
+    }
+}
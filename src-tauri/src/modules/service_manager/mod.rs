@@ -8,7 +8,7 @@ use std::thread;
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
-use crate::modules::common;
+use crate::modules::{common, llm_config, secret_store};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayStatus {
@@ -33,6 +33,15 @@ struct GatewayRuntime {
     error: Option<String>,
     started_at: Option<Instant>,
     child: Option<Child>,
+    /// Arguments of the last `start_gateway`, reused by `restart_gateway`.
+    launch: Option<GatewayLaunch>,
+}
+
+#[derive(Clone)]
+struct GatewayLaunch {
+    openclaw_path: String,
+    port: u16,
+    env_vars: HashMap<String, String>,
 }
 
 impl GatewayRuntime {
@@ -44,6 +53,7 @@ impl GatewayRuntime {
             error: None,
             started_at: None,
             child: None,
+            launch: None,
         }
     }
 
@@ -95,6 +105,20 @@ pub fn start_gateway(
     port: u16,
     env_vars: HashMap<String, String>,
 ) -> Result<(), String> {
+    // Stored keys are added here rather than passed in, so they never reach
+    // the frontend. A locked or unreadable store only stops providers that
+    // need a key; keyless setups such as Ollama start without it.
+    let secrets = match secret_store::load_secrets(&app) {
+        Ok(secrets) => secrets,
+        Err(err) if llm_config::configured_provider_needs_key(&app) => {
+            return Err(format!("cannot read the stored API keys: {err}"));
+        }
+        Err(err) => {
+            log::warn!("starting gateway without stored keys: {err}");
+            HashMap::new()
+        }
+    };
+
     {
         let state_lock = gateway_state();
         let mut runtime = state_lock
@@ -107,6 +131,11 @@ pub fn start_gateway(
             return Err(message);
         }
         runtime.set_state("starting", None, port, None, false);
+        runtime.launch = Some(GatewayLaunch {
+            openclaw_path: openclaw_path.clone(),
+            port,
+            env_vars: env_vars.clone(),
+        });
         emit_gateway_status(&app, runtime.snapshot())?;
     }
 
@@ -123,6 +152,7 @@ pub fn start_gateway(
         .arg(port.to_string())
         .arg("--verbose")
         .envs(env_vars)
+        .envs(secrets)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
@@ -245,6 +275,27 @@ pub fn stop_gateway(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Stops the gateway and starts it again with the executable, port and env
+/// vars of the last `start_gateway`. Stored keys are re-read by
+/// `start_gateway`, so key and config changes take effect.
+#[tauri::command]
+pub fn restart_gateway(app: AppHandle) -> Result<(), String> {
+    let launch = gateway_state()
+        .lock()
+        .map_err(|_| "gateway state lock poisoned".to_string())?
+        .launch
+        .clone()
+        .ok_or_else(|| "gateway has not been started yet".to_string())?;
+
+    stop_gateway(app.clone())?;
+    start_gateway(app, launch.openclaw_path, launch.port, launch.env_vars)
+}
+
+/// Whether a gateway started by this app is starting or running.
+pub fn is_gateway_running() -> bool {
+    matches!(get_gateway_status().state.as_str(), "starting" | "running")
+}
+
 #[tauri::command]
 pub fn get_gateway_status() -> GatewayStatus {
     let state_lock = gateway_state();
@@ -426,7 +477,7 @@ where
     }
 }
 
-fn health_check_inner(port: u16) -> bool {
+pub(crate) fn health_check_inner(port: u16) -> bool {
     let mut stream = match TcpStream::connect(("127.0.0.1", port)) {
         Ok(stream) => stream,
         Err(_) => return false,
@@ -0,0 +1,727 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use crate::modules::common::{self, CustomProvider, PortableSettings};
+use crate::modules::openclaw_config::{self, ConfigChange, FieldError, SecretValue};
+use crate::modules::secret_store::{self, Envelope, KeyRing, KeySummary};
+use crate::modules::{node_runtime, openclaw_installer, plugin_manager};
+
+const BUNDLE_FORMAT: &str = "openclawini-setup";
+const BUNDLE_VERSION: u32 = 1;
+const IMPORT_REASON: &str = "imported setup bundle";
+
+/// Everything needed to reproduce a setup on another machine, as written by
+/// `export_setup`. Secrets are only present sealed under a passphrase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SetupBundle {
+    format: String,
+    version: u32,
+    created_at: String,
+    app_version: String,
+    settings: PortableSettings,
+    /// `openclaw.json` with its secret strings removed.
+    openclaw_config: Option<String>,
+    /// Where secret strings were removed from `openclaw_config`.
+    #[serde(default)]
+    stripped_secrets: Vec<String>,
+    versions: SetupVersions,
+    #[serde(default)]
+    plugins: Vec<BundledPlugin>,
+    #[serde(default)]
+    secrets: Option<Envelope>,
+}
+
+/// The sealed part of a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundleSecrets {
+    keys: KeyRing,
+    config_secrets: Vec<SecretValue>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SetupVersions {
+    pub node: Option<String>,
+    pub openclaw: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundledPlugin {
+    pub name: String,
+    /// What was asked for at install time, e.g. `@openclaw/voice@^1`.
+    pub spec: String,
+    pub version: Option<String>,
+}
+
+/// What a bundle holds, without its secrets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetupBundleInfo {
+    pub path: String,
+    pub created_at: String,
+    pub app_version: String,
+    pub includes_secrets: bool,
+    pub has_openclaw_config: bool,
+    pub profiles: Vec<String>,
+    pub versions: SetupVersions,
+    pub plugins: Vec<BundledPlugin>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetupImportPreview {
+    pub bundle: SetupBundleInfo,
+    pub settings_changes: Vec<ConfigChange>,
+    pub config_changes: Vec<ConfigChange>,
+    /// Provider endpoints the import would change, in settings or in
+    /// `openclaw.json`. Keys are sent to these URLs, so the import is only
+    /// applied once they are confirmed.
+    pub base_url_changes: Vec<ConfigChange>,
+    /// A different model catalog URL. The catalog decides which providers
+    /// and endpoints are offered, so it needs confirming too.
+    pub catalog_url_change: Option<ConfigChange>,
+    /// Schema errors the imported config would introduce. Any error blocks
+    /// the import.
+    pub errors: Vec<FieldError>,
+    pub keys_to_add: Vec<KeySummary>,
+    /// npm packages the import would install. They run code, so they are
+    /// only installed once confirmed.
+    pub plugins_to_install: Vec<BundledPlugin>,
+    /// What the import leaves alone, e.g. a different OpenClaw version.
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetupImportResult {
+    pub applied: bool,
+    pub preview: SetupImportPreview,
+    /// Plugins that could not be installed; everything else was applied.
+    pub warnings: Vec<String>,
+}
+
+/// What an import would write, worked out before anything is.
+struct ImportPlan {
+    settings: Option<PortableSettings>,
+    config_text: Option<String>,
+    keys: Option<KeyRing>,
+}
+
+/// Writes the current setup to `path`. Secrets (stored API keys and secret
+/// strings in `openclaw.json`) are left out unless a passphrase is given,
+/// and then only included sealed under it.
+#[tauri::command]
+pub fn export_setup(
+    app: AppHandle,
+    path: String,
+    passphrase: Option<String>,
+) -> Result<SetupBundleInfo, String> {
+    let path = bundle_path(&path)?;
+    let passphrase = passphrase.filter(|value| !value.is_empty());
+    if let Some(value) = passphrase.as_deref() {
+        if value.chars().count() < secret_store::MIN_PASSPHRASE_LEN {
+            return Err(format!(
+                "passphrase must be at least {} characters",
+                secret_store::MIN_PASSPHRASE_LEN
+            ));
+        }
+    }
+
+    let (openclaw_config, config_secrets) = match openclaw_config::read_config_text()? {
+        Some(text) => {
+            let (stripped, secrets) = openclaw_config::strip_secrets(&text)?;
+            (Some(stripped), secrets)
+        }
+        None => (None, Vec::new()),
+    };
+    let secrets = match passphrase {
+        Some(passphrase) => {
+            let sealed = BundleSecrets {
+                keys: secret_store::load_keyring(&app)?,
+                config_secrets: config_secrets.clone(),
+            };
+            let plaintext = serde_json::to_string(&sealed)
+                .map_err(|e| format!("failed to serialize secrets: {e}"))?;
+            Some(secret_store::seal_with_passphrase(&plaintext, &passphrase)?)
+        }
+        None => None,
+    };
+    let plugins = plugin_manager::list_plugins(app.clone())?
+        .into_iter()
+        .filter(|plugin| plugin.managed)
+        .filter_map(|plugin| {
+            Some(BundledPlugin {
+                spec: plugin.spec?,
+                name: plugin.name,
+                version: plugin.version,
+            })
+        })
+        .collect();
+
+    let bundle = SetupBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        created_at: common::iso_utc_now(),
+        app_version: app.package_info().version.to_string(),
+        settings: common::get_portable_settings(&app)?,
+        openclaw_config,
+        stripped_secrets: config_secrets
+            .into_iter()
+            .map(|secret| secret.path)
+            .collect(),
+        versions: installed_versions(&app),
+        plugins,
+        secrets,
+    };
+    let serialized = serde_json::to_vec_pretty(&bundle)
+        .map_err(|e| format!("failed to serialize setup bundle: {e}"))?;
+    if bundle.secrets.is_some() {
+        common::write_private_file_atomic(&path, &serialized)?;
+    } else {
+        common::write_file_atomic(&path, &serialized)?;
+    }
+    Ok(info(&path, &bundle))
+}
+
+/// Reads a bundle written by `export_setup` and previews what importing it
+/// changes. Unless `dry_run` is set, the preview has errors, or it changes
+/// provider endpoints, the catalog URL or installs plugins without the
+/// matching `confirm_*` flag, the changes are then applied: settings and
+/// profiles merged by id, `openclaw.json` (recorded in the config
+/// history), stored API keys whose labels are free, and missing plugins.
+#[tauri::command]
+pub fn import_setup(
+    app: AppHandle,
+    path: String,
+    passphrase: Option<String>,
+    dry_run: Option<bool>,
+    confirm_base_urls: Option<bool>,
+    confirm_catalog_url: Option<bool>,
+    confirm_plugins: Option<bool>,
+) -> Result<SetupImportResult, String> {
+    let path = bundle_path(&path)?;
+    let raw = std::fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let bundle = parse_bundle(&raw)?;
+    let secrets = match (
+        &bundle.secrets,
+        passphrase.filter(|value| !value.is_empty()),
+    ) {
+        (Some(sealed), Some(passphrase)) => Some(unseal(sealed, &passphrase)?),
+        _ => None,
+    };
+
+    let (preview, plan) = plan_import(&app, &path, &bundle, secrets.as_ref())?;
+    let unconfirmed = (!preview.base_url_changes.is_empty() && !confirm_base_urls.unwrap_or(false))
+        || (preview.catalog_url_change.is_some() && !confirm_catalog_url.unwrap_or(false))
+        || (!preview.plugins_to_install.is_empty() && !confirm_plugins.unwrap_or(false));
+    if dry_run.unwrap_or(false) || !preview.errors.is_empty() || unconfirmed {
+        return Ok(SetupImportResult {
+            applied: false,
+            preview,
+            warnings: Vec::new(),
+        });
+    }
+
+    if let Some(settings) = &plan.settings {
+        common::set_portable_settings(&app, settings)?;
+    }
+    if let Some(text) = &plan.config_text {
+        openclaw_config::replace_config(&app, text, IMPORT_REASON)?;
+    }
+    if let Some(keys) = &plan.keys {
+        secret_store::save_keyring(&app, keys)?;
+    }
+    let warnings = preview
+        .plugins_to_install
+        .iter()
+        .filter_map(|plugin| {
+            plugin_manager::install_plugin(app.clone(), plugin.spec.clone())
+                .err()
+                .map(|err| format!("plugin {} was not installed: {err}", plugin.spec))
+        })
+        .collect();
+
+    Ok(SetupImportResult {
+        applied: true,
+        preview,
+        warnings,
+    })
+}
+
+fn plan_import(
+    app: &AppHandle,
+    path: &std::path::Path,
+    bundle: &SetupBundle,
+    secrets: Option<&BundleSecrets>,
+) -> Result<(SetupImportPreview, ImportPlan), String> {
+    let current_settings = common::get_portable_settings(app)?;
+    let merged_settings = merge_settings(&current_settings, &bundle.settings);
+    let settings_changes =
+        openclaw_config::diff(&to_value(&current_settings)?, &to_value(&merged_settings)?);
+    let mut base_url_changes = settings_base_url_changes(&current_settings, &merged_settings);
+    let catalog_url_change = catalog_url_change(&current_settings, &merged_settings);
+
+    let mut config_changes = Vec::new();
+    let mut errors = Vec::new();
+    let mut config_text = None;
+    if let Some(text) = &bundle.openclaw_config {
+        let local_text = openclaw_config::read_config_text()?;
+        let restored = match secrets {
+            Some(secrets) => secrets.config_secrets.clone(),
+            None => carried_secrets(local_text.as_deref(), &bundle.stripped_secrets)?,
+        };
+        let merged = openclaw_config::restore_secrets(text, &restored)?;
+        let current =
+            openclaw_config::read_config()?.unwrap_or_else(|| Value::Object(Default::default()));
+        let next = openclaw_config::json5::parse(&merged)
+            .map_err(|e| format!("the bundled OpenClaw config is not valid json5: {e}"))?;
+        config_changes = openclaw_config::diff(&current, &next);
+        base_url_changes.extend(
+            config_changes
+                .iter()
+                .filter(|change| changes_base_url(change))
+                .cloned(),
+        );
+        errors = openclaw_config::introduced_errors(&current, &next);
+        if !config_changes.is_empty() {
+            config_text = Some(merged);
+        }
+    }
+
+    let mut keys_to_add = Vec::new();
+    let mut keys = None;
+    if let Some(secrets) = secrets {
+        let mut ring = secret_store::load_keyring(app)?;
+        keys_to_add = ring.merge_missing(&secrets.keys, &common::iso_utc_now());
+        if !keys_to_add.is_empty() {
+            keys = Some(ring);
+        }
+    }
+
+    let installed = plugin_manager::list_plugins(app.clone())?
+        .into_iter()
+        .map(|plugin| plugin.name)
+        .collect::<Vec<_>>();
+    let plugins_to_install = bundle
+        .plugins
+        .iter()
+        .filter(|plugin| !installed.contains(&plugin.name))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut notes = version_notes(&bundle.versions, &installed_versions(app));
+    if !base_url_changes.is_empty() {
+        notes.push(
+            "The bundle points providers at other endpoints; confirm them to import it."
+                .to_string(),
+        );
+    }
+    if catalog_url_change.is_some() {
+        notes.push(
+            "The bundle uses another model catalog; confirm it to import the bundle.".to_string(),
+        );
+    }
+    if !plugins_to_install.is_empty() {
+        notes.push(
+            "The bundle installs npm plugins, which run code on this machine; confirm them to import the bundle."
+                .to_string(),
+        );
+    }
+    if bundle.secrets.is_some() && secrets.is_none() {
+        notes.push(
+            "The bundle's API keys and config secrets are protected; enter its passphrase to import them."
+                .to_string(),
+        );
+    } else if bundle.secrets.is_none() && !bundle.stripped_secrets.is_empty() {
+        notes.push(
+            "The bundle has no secrets; API keys and tokens already set here are kept.".to_string(),
+        );
+    }
+
+    let preview = SetupImportPreview {
+        bundle: info(path, bundle),
+        settings_changes: settings_changes.clone(),
+        config_changes,
+        base_url_changes,
+        catalog_url_change,
+        errors,
+        keys_to_add,
+        plugins_to_install,
+        notes,
+    };
+    let plan = ImportPlan {
+        settings: (!settings_changes.is_empty()).then_some(merged_settings),
+        config_text,
+        keys,
+    };
+    Ok((preview, plan))
+}
+
+/// The bundle's settings laid over the current ones: custom providers and
+/// profiles are replaced by id (profiles by name) and added when new, and
+/// provider endpoints by provider. Entries only set here are kept.
+fn merge_settings(current: &PortableSettings, bundled: &PortableSettings) -> PortableSettings {
+    let mut merged = current.clone();
+    if bundled.catalog_url.is_some() {
+        merged.catalog_url = bundled.catalog_url.clone();
+    }
+    for provider in &bundled.custom_providers {
+        match merged
+            .custom_providers
+            .iter_mut()
+            .find(|existing| existing.id == provider.id)
+        {
+            Some(existing) => *existing = provider.clone(),
+            None => merged.custom_providers.push(provider.clone()),
+        }
+    }
+    for profile in &bundled.llm_profiles {
+        match merged
+            .llm_profiles
+            .iter_mut()
+            .find(|existing| existing.name == profile.name)
+        {
+            Some(existing) => *existing = profile.clone(),
+            None => merged.llm_profiles.push(profile.clone()),
+        }
+    }
+    merged.provider_base_urls.extend(
+        bundled
+            .provider_base_urls
+            .iter()
+            .map(|(provider, url)| (provider.clone(), url.clone())),
+    );
+    merged.restart_gateway_on_config_change = bundled.restart_gateway_on_config_change;
+    merged
+}
+
+/// Endpoint overrides and custom provider URLs that differ between
+/// `current` and `merged`.
+fn settings_base_url_changes(
+    current: &PortableSettings,
+    merged: &PortableSettings,
+) -> Vec<ConfigChange> {
+    let change = |path: String, before: Option<&String>, after: &String| ConfigChange {
+        path,
+        before: before.map(|url| Value::String(url.clone())),
+        after: Some(Value::String(after.clone())),
+    };
+    let base_url = |providers: &[CustomProvider], id: &str| {
+        providers
+            .iter()
+            .find(|provider| provider.id == id)
+            .map(|provider| provider.base_url.clone())
+    };
+
+    let mut changes = Vec::new();
+    for (provider, url) in &merged.provider_base_urls {
+        let before = current.provider_base_urls.get(provider);
+        if before != Some(url) {
+            changes.push(change(
+                format!("/provider_base_urls/{provider}"),
+                before,
+                url,
+            ));
+        }
+    }
+    for provider in &merged.custom_providers {
+        let before = base_url(&current.custom_providers, &provider.id);
+        if before.as_ref() != Some(&provider.base_url) {
+            changes.push(change(
+                format!("/custom_providers/{}/base_url", provider.id),
+                before.as_ref(),
+                &provider.base_url,
+            ));
+        }
+    }
+    changes
+}
+
+/// The catalog URL change `merged` makes, if any.
+fn catalog_url_change(
+    current: &PortableSettings,
+    merged: &PortableSettings,
+) -> Option<ConfigChange> {
+    (merged.catalog_url != current.catalog_url).then(|| ConfigChange {
+        path: "/catalog_url".to_string(),
+        before: current.catalog_url.clone().map(Value::String),
+        after: merged.catalog_url.clone().map(Value::String),
+    })
+}
+
+/// Whether a change to `openclaw.json` sets or moves a provider's
+/// `baseUrl`, directly or by replacing an object that holds one.
+fn changes_base_url(change: &ConfigChange) -> bool {
+    fn holds_base_url(value: &Value) -> bool {
+        match value {
+            Value::Object(map) => map
+                .iter()
+                .any(|(key, value)| key == "baseUrl" || holds_base_url(value)),
+            Value::Array(items) => items.iter().any(holds_base_url),
+            _ => false,
+        }
+    }
+    change.after.is_some()
+        && (change.path.ends_with("/baseUrl") || change.after.as_ref().is_some_and(holds_base_url))
+}
+
+fn parse_bundle(raw: &str) -> Result<SetupBundle, String> {
+    let value =
+        serde_json::from_str::<Value>(raw).map_err(|e| format!("not a setup bundle: {e}"))?;
+    if value.get("format").and_then(Value::as_str) != Some(BUNDLE_FORMAT) {
+        return Err("not a setup bundle exported by OpenClawini".to_string());
+    }
+    let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version != u64::from(BUNDLE_VERSION) {
+        return Err(format!(
+            "setup bundle version {version} is not supported; update OpenClawini to import it"
+        ));
+    }
+    let bundle = serde_json::from_value::<SetupBundle>(value)
+        .map_err(|e| format!("invalid setup bundle: {e}"))?;
+    if let Some(text) = &bundle.openclaw_config {
+        if !openclaw_config::json5::parse(text)?.is_object() {
+            return Err("the bundled OpenClaw config must be an object".to_string());
+        }
+    }
+    Ok(bundle)
+}
+
+fn unseal(sealed: &Envelope, passphrase: &str) -> Result<BundleSecrets, String> {
+    let plaintext = secret_store::open_with_passphrase(sealed, passphrase)
+        .map_err(|_| "the passphrase does not open this bundle's secrets".to_string())?;
+    serde_json::from_str(&plaintext).map_err(|e| format!("invalid bundle secrets: {e}"))
+}
+
+/// The local config's secrets at the paths the bundle had to leave empty,
+/// so importing a bundle without secrets does not drop tokens set here.
+fn carried_secrets(local: Option<&str>, stripped: &[String]) -> Result<Vec<SecretValue>, String> {
+    let Some(local) = local else {
+        return Ok(Vec::new());
+    };
+    let (_, secrets) = openclaw_config::strip_secrets(local)?;
+    Ok(secrets
+        .into_iter()
+        .filter(|secret| stripped.contains(&secret.path))
+        .collect())
+}
+
+fn version_notes(bundled: &SetupVersions, installed: &SetupVersions) -> Vec<String> {
+    [
+        ("Node.js", &bundled.node, &installed.node),
+        ("OpenClaw", &bundled.openclaw, &installed.openclaw),
+    ]
+    .into_iter()
+    .filter_map(|(name, bundled, installed)| {
+        let bundled = bundled.as_deref()?;
+        match installed.as_deref() {
+            Some(installed) if installed == bundled => None,
+            Some(installed) => Some(format!(
+                "The bundle was made with {name} {bundled}; {installed} is installed here."
+            )),
+            None => Some(format!(
+                "The bundle was made with {name} {bundled}, which is not installed here."
+            )),
+        }
+    })
+    .collect()
+}
+
+fn installed_versions(app: &AppHandle) -> SetupVersions {
+    SetupVersions {
+        node: node_runtime::get_node_status(app.clone())
+            .ok()
+            .and_then(|status| status.version),
+        openclaw: openclaw_installer::get_openclaw_status(app.clone())
+            .ok()
+            .and_then(|status| status.version),
+    }
+}
+
+fn info(path: &std::path::Path, bundle: &SetupBundle) -> SetupBundleInfo {
+    SetupBundleInfo {
+        path: path.display().to_string(),
+        created_at: bundle.created_at.clone(),
+        app_version: bundle.app_version.clone(),
+        includes_secrets: bundle.secrets.is_some(),
+        has_openclaw_config: bundle.openclaw_config.is_some(),
+        profiles: bundle
+            .settings
+            .llm_profiles
+            .iter()
+            .map(|profile| profile.name.clone())
+            .collect(),
+        versions: bundle.versions.clone(),
+        plugins: bundle.plugins.clone(),
+    }
+}
+
+fn bundle_path(path: &str) -> Result<PathBuf, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("a file path for the setup bundle is required".to_string());
+    }
+    Ok(PathBuf::from(path))
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| format!("failed to serialize settings: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle_json(format: &str, version: u32) -> String {
+        json!({
+            "format": format,
+            "version": version,
+            "created_at": "2026-10-18T00:00:00Z",
+            "app_version": "0.1.0",
+            "settings": { "catalog_url": null },
+            "openclaw_config": "{ gateway: { port: 18789 } }",
+            "versions": { "node": "22.16.0", "openclaw": null }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn bundles_are_checked_before_use() {
+        let bundle = parse_bundle(&bundle_json(BUNDLE_FORMAT, BUNDLE_VERSION)).expect("bundle");
+        assert!(bundle.plugins.is_empty());
+        assert!(bundle.secrets.is_none());
+
+        assert!(parse_bundle("{}").is_err());
+        assert!(parse_bundle(&bundle_json("something-else", 1)).is_err());
+        assert!(parse_bundle(&bundle_json(BUNDLE_FORMAT, 2))
+            .expect_err("newer bundle")
+            .contains("not supported"));
+        let broken = bundle_json(BUNDLE_FORMAT, 1).replace("{ gateway", "[ gateway");
+        assert!(parse_bundle(&broken).is_err());
+    }
+
+    #[test]
+    fn local_secrets_fill_only_stripped_paths() {
+        let local = "{ env: { OPENAI_API_KEY: 'sk-local', GROQ_API_KEY: 'gsk' }, gateway: { auth: { token: 'tok' } } }";
+        let stripped = vec![
+            "/env/OPENAI_API_KEY".to_string(),
+            "/gateway/auth/token".to_string(),
+        ];
+        let carried = carried_secrets(Some(local), &stripped).expect("carried");
+        assert_eq!(
+            carried
+                .iter()
+                .map(|s| (s.path.as_str(), s.value.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/env/OPENAI_API_KEY", "sk-local"),
+                ("/gateway/auth/token", "tok")
+            ]
+        );
+        assert!(carried_secrets(None, &stripped).expect("none").is_empty());
+    }
+
+    fn custom(id: &str, base_url: &str) -> CustomProvider {
+        CustomProvider {
+            id: id.to_string(),
+            display_name: id.to_string(),
+            base_url: base_url.to_string(),
+            env_var: None,
+            models: vec!["model".to_string()],
+        }
+    }
+
+    #[test]
+    fn bundled_settings_merge_by_id() {
+        let current = PortableSettings {
+            catalog_url: Some("https://catalog.example".to_string()),
+            custom_providers: vec![
+                custom("corp", "https://llm.corp"),
+                custom("lab", "http://lab"),
+            ],
+            provider_base_urls: [("ollama".to_string(), "http://gpu:11434".to_string())].into(),
+            ..Default::default()
+        };
+        let bundled = PortableSettings {
+            custom_providers: vec![custom("lab", "http://lab"), custom("edge", "https://edge")],
+            provider_base_urls: [("openai".to_string(), "https://proxy".to_string())].into(),
+            ..Default::default()
+        };
+
+        let merged = merge_settings(&current, &bundled);
+        assert_eq!(
+            merged.catalog_url.as_deref(),
+            Some("https://catalog.example")
+        );
+        assert_eq!(
+            merged
+                .custom_providers
+                .iter()
+                .map(|p| p.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["corp", "lab", "edge"]
+        );
+        assert_eq!(merged.provider_base_urls.len(), 2);
+
+        let paths = settings_base_url_changes(&current, &merged)
+            .into_iter()
+            .map(|change| change.path)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "/provider_base_urls/openai",
+                "/custom_providers/edge/base_url"
+            ]
+        );
+        assert!(settings_base_url_changes(&current, &current).is_empty());
+        assert!(catalog_url_change(&current, &merged).is_none());
+
+        let moved = merge_settings(
+            &current,
+            &PortableSettings {
+                catalog_url: Some("https://elsewhere.example".to_string()),
+                ..Default::default()
+            },
+        );
+        let change = catalog_url_change(&current, &moved).expect("catalog change");
+        assert_eq!(change.path, "/catalog_url");
+        assert_eq!(change.after, Some(json!("https://elsewhere.example")));
+    }
+
+    #[test]
+    fn config_endpoint_changes_need_confirmation() {
+        let current =
+            json!({ "models": { "providers": { "ollama": { "baseUrl": "http://a/v1" } } } });
+        let next = json!({ "models": { "providers": {
+            "ollama": { "baseUrl": "http://b/v1" },
+            "corp": { "baseUrl": "https://corp/v1", "models": [] }
+        } }, "gateway": { "port": 1 } });
+        let flagged = openclaw_config::diff(&current, &next)
+            .into_iter()
+            .filter(changes_base_url)
+            .map(|change| change.path)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flagged,
+            vec!["/models/providers/ollama/baseUrl", "/models/providers/corp"]
+        );
+    }
+
+    #[test]
+    fn version_differences_are_noted() {
+        let bundled = SetupVersions {
+            node: Some("22.16.0".to_string()),
+            openclaw: Some("2026.3.1".to_string()),
+        };
+        let installed = SetupVersions {
+            node: Some("22.16.0".to_string()),
+            openclaw: None,
+        };
+        assert_eq!(
+            version_notes(&bundled, &installed),
+            vec!["The bundle was made with OpenClaw 2026.3.1, which is not installed here."]
+        );
+        assert!(version_notes(&SetupVersions::default(), &installed).is_empty());
+    }
+}
@@ -0,0 +1,318 @@
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::modules::common::InstallLock;
+
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(2);
+const ELECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecondInstancePayload {
+    pub args: Vec<String>,
+    pub cwd: String,
+}
+
+/// Makes this process the primary instance, or forwards its arguments to the
+/// already running one. Returns `Ok(false)` when another instance accepted the
+/// arguments and this process should exit.
+pub fn init(app: &AppHandle) -> Result<bool, String> {
+    let endpoint = endpoint_path(app)?;
+    let payload = SecondInstancePayload {
+        args: std::env::args().collect(),
+        cwd: std::env::current_dir()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+
+    if forward_to_primary(&endpoint, &payload).is_ok() {
+        return Ok(false);
+    }
+
+    // Instances starting together take turns; whoever comes second finds
+    // the first one listening.
+    let _election = elect(&endpoint)?;
+    if forward_to_primary(&endpoint, &payload).is_ok() {
+        return Ok(false);
+    }
+
+    let app_handle = app.clone();
+    listen(&endpoint, move |payload| {
+        focus_main_window(&app_handle);
+        let _ = app_handle.emit("app:second-instance", payload);
+    })?;
+    Ok(true)
+}
+
+/// Holds `instance.lock` next to the endpoint while this process decides
+/// whether it becomes the primary, waiting for another instance doing the
+/// same.
+fn elect(endpoint: &Path) -> Result<InstallLock, String> {
+    let path = endpoint.with_extension("lock");
+    let start = Instant::now();
+    loop {
+        match InstallLock::acquire_file(path.clone(), "single_instance") {
+            Ok(lock) => return Ok(lock),
+            Err(err) if start.elapsed() >= ELECTION_TIMEOUT => {
+                return Err(format!("failed to elect the primary instance: {err}"))
+            }
+            Err(_) => thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
+
+fn endpoint_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let config_dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("failed to resolve app_config_dir: {e}"))?;
+    std::fs::create_dir_all(&config_dir).map_err(|e| {
+        format!(
+            "failed to create app_config_dir {}: {e}",
+            config_dir.display()
+        )
+    })?;
+
+    if cfg!(unix) {
+        Ok(config_dir.join("instance.sock"))
+    } else {
+        Ok(config_dir.join("instance.port"))
+    }
+}
+
+fn focus_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn forward_to_primary(endpoint: &Path, payload: &SecondInstancePayload) -> Result<(), String> {
+    let mut stream = ipc::connect(endpoint)
+        .map_err(|e| format!("no running instance at {}: {e}", endpoint.display()))?;
+
+    let mut message = serde_json::to_string(payload)
+        .map_err(|e| format!("failed to serialize instance payload: {e}"))?;
+    message.push('\n');
+    stream
+        .write_all(message.as_bytes())
+        .map_err(|e| format!("failed to forward arguments: {e}"))?;
+
+    let mut ack = String::new();
+    BufReader::new(stream)
+        .read_line(&mut ack)
+        .map_err(|e| format!("running instance did not acknowledge: {e}"))?;
+    if ack.trim() == "ok" {
+        Ok(())
+    } else {
+        Err("running instance rejected forwarded arguments".to_string())
+    }
+}
+
+/// Serves `endpoint` as the primary instance. Call with the election lock
+/// held, since a dead primary's endpoint is taken over.
+fn listen<F>(endpoint: &Path, on_message: F) -> Result<(), String>
+where
+    F: Fn(SecondInstancePayload) + Send + 'static,
+{
+    let listener = ipc::bind(endpoint)
+        .map_err(|e| format!("failed to listen on {}: {e}", endpoint.display()))?;
+
+    thread::spawn(move || {
+        for stream in ipc::incoming(&listener) {
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            if reader.read_line(&mut line).is_err() {
+                continue;
+            }
+            let Ok(payload) = serde_json::from_str::<SecondInstancePayload>(&line) else {
+                let _ = reader.get_mut().write_all(b"error\n");
+                continue;
+            };
+            let _ = reader.get_mut().write_all(b"ok\n");
+            on_message(payload);
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(unix)]
+mod ipc {
+    use std::io;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::Path;
+
+    use super::FORWARD_TIMEOUT;
+
+    pub fn connect(endpoint: &Path) -> io::Result<UnixStream> {
+        let stream = UnixStream::connect(endpoint)?;
+        stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+        stream.set_write_timeout(Some(FORWARD_TIMEOUT))?;
+        Ok(stream)
+    }
+
+    pub fn bind(endpoint: &Path) -> io::Result<UnixListener> {
+        match UnixListener::bind(endpoint) {
+            Err(err) if err.kind() == io::ErrorKind::AddrInUse => {
+                if UnixStream::connect(endpoint).is_ok() {
+                    return Err(err);
+                }
+                // A socket file nobody answers on is left over from a
+                // crashed instance.
+                std::fs::remove_file(endpoint)?;
+                UnixListener::bind(endpoint)
+            }
+            result => result,
+        }
+    }
+
+    pub fn incoming(listener: &UnixListener) -> impl Iterator<Item = UnixStream> + '_ {
+        listener
+            .incoming()
+            .filter_map(Result::ok)
+            .inspect(|stream| {
+                // A client that never sends a line must not stall the listener.
+                let _ = stream.set_read_timeout(Some(FORWARD_TIMEOUT));
+                let _ = stream.set_write_timeout(Some(FORWARD_TIMEOUT));
+            })
+    }
+}
+
+#[cfg(not(unix))]
+mod ipc {
+    use std::io;
+    use std::net::{SocketAddr, TcpListener, TcpStream};
+    use std::path::Path;
+
+    use super::FORWARD_TIMEOUT;
+
+    pub fn connect(endpoint: &Path) -> io::Result<TcpStream> {
+        let port = std::fs::read_to_string(endpoint)?
+            .trim()
+            .parse::<u16>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let address = SocketAddr::from(([127, 0, 0, 1], port));
+        let stream = TcpStream::connect_timeout(&address, FORWARD_TIMEOUT)?;
+        stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+        stream.set_write_timeout(Some(FORWARD_TIMEOUT))?;
+        Ok(stream)
+    }
+
+    pub fn bind(endpoint: &Path) -> io::Result<TcpListener> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        std::fs::write(endpoint, listener.local_addr()?.port().to_string())?;
+        Ok(listener)
+    }
+
+    pub fn incoming(listener: &TcpListener) -> impl Iterator<Item = TcpStream> + '_ {
+        listener
+            .incoming()
+            .filter_map(Result::ok)
+            .inspect(|stream| {
+                // A client that never sends a line must not stall the listener.
+                let _ = stream.set_read_timeout(Some(FORWARD_TIMEOUT));
+                let _ = stream.set_write_timeout(Some(FORWARD_TIMEOUT));
+            })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn test_endpoint(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("openclawini-{name}-{}.sock", std::process::id()))
+    }
+
+    #[test]
+    fn forwarded_arguments_reach_primary_instance() {
+        let endpoint = test_endpoint("forward");
+        let (tx, rx) = mpsc::channel();
+        listen(&endpoint, move |payload| {
+            let _ = tx.send(payload);
+        })
+        .expect("primary listens");
+
+        let payload = SecondInstancePayload {
+            args: vec![
+                "openclawini".to_string(),
+                "openclawini://open?x=1".to_string(),
+            ],
+            cwd: "/tmp".to_string(),
+        };
+        forward_to_primary(&endpoint, &payload).expect("forward succeeds");
+
+        let received = rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("payload received");
+        assert_eq!(received, payload);
+        let _ = std::fs::remove_file(&endpoint);
+    }
+
+    #[test]
+    fn stale_socket_is_replaced_by_new_primary() {
+        let endpoint = test_endpoint("stale");
+        drop(std::os::unix::net::UnixListener::bind(&endpoint).expect("bind stale socket"));
+        assert!(endpoint.exists());
+
+        let payload = SecondInstancePayload {
+            args: Vec::new(),
+            cwd: String::new(),
+        };
+        assert!(forward_to_primary(&endpoint, &payload).is_err());
+        listen(&endpoint, |_| {}).expect("new primary takes over stale socket");
+        assert!(forward_to_primary(&endpoint, &payload).is_ok());
+        let _ = std::fs::remove_file(&endpoint);
+    }
+
+    #[test]
+    fn live_socket_is_not_taken_over() {
+        let endpoint = test_endpoint("live");
+        listen(&endpoint, |_| {}).expect("primary listens");
+        assert!(ipc::bind(&endpoint).is_err());
+
+        let payload = SecondInstancePayload {
+            args: Vec::new(),
+            cwd: String::new(),
+        };
+        assert!(forward_to_primary(&endpoint, &payload).is_ok());
+        let _ = std::fs::remove_file(&endpoint);
+    }
+
+    #[test]
+    fn silent_client_does_not_block_the_primary() {
+        let endpoint = test_endpoint("silent");
+        listen(&endpoint, |_| {}).expect("primary listens");
+        let _silent = ipc::connect(&endpoint).expect("connect");
+        // The primary gives up on the silent client after its read timeout.
+        thread::sleep(FORWARD_TIMEOUT + Duration::from_millis(200));
+
+        let payload = SecondInstancePayload {
+            args: Vec::new(),
+            cwd: String::new(),
+        };
+        assert!(forward_to_primary(&endpoint, &payload).is_ok());
+        let _ = std::fs::remove_file(&endpoint);
+    }
+
+    #[test]
+    fn election_waits_for_the_other_instance() {
+        let endpoint = test_endpoint("elect");
+        let first = elect(&endpoint).expect("first election");
+        let waiter = thread::spawn({
+            let endpoint = endpoint.clone();
+            move || elect(&endpoint).map(drop)
+        });
+        thread::sleep(Duration::from_millis(200));
+        assert!(!waiter.is_finished());
+        drop(first);
+        waiter.join().expect("join").expect("second election");
+        assert!(!endpoint.with_extension("lock").exists());
+    }
+}
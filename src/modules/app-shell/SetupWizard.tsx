@@ -47,7 +47,9 @@ export function SetupWizard({ onFinish }: SetupWizardProps) {
       {stepIndex === 0 ? <PlatformStep onContinue={() => setStepIndex(1)} /> : null}
       {stepIndex === 1 ? <NodeRuntimeStep onContinue={() => setStepIndex(2)} /> : null}
       {stepIndex === 2 ? <OpenClawInstallerStep onContinue={() => setStepIndex(3)} /> : null}
-      {stepIndex === 3 ? <LlmConfigStep onContinue={() => setStepIndex(4)} /> : null}
+      {stepIndex === 3 ? (
+        <LlmConfigStep onContinue={() => setStepIndex(4)} onSkip={() => setStepIndex(4)} />
+      ) : null}
       {stepIndex === 4 ? <ServiceManagerStep onContinue={() => setStepIndex(5)} /> : null}
       {stepIndex === 5 ? <BrowserLauncherStep onFinish={onFinish} /> : null}
     </section>
@@ -1,26 +1,46 @@
+import { useState } from "react";
+import { ModelParametersFields } from "./ModelParametersFields";
+import { OllamaPanel } from "./OllamaPanel";
 import { useLlmStatus } from "./useLlmStatus";
 
 interface LlmConfigStepProps {
   onContinue: () => void;
+  /** Shown as a "Skip" button when given, for users with OpenClaw already configured. */
+  onSkip?: () => void;
 }
 
-export function LlmConfigStep({ onContinue }: LlmConfigStepProps) {
+export function LlmConfigStep({ onContinue, onSkip }: LlmConfigStepProps) {
   const {
     providers,
     selectedProvider,
     selectedProviderId,
+    acceptedParameters,
+    parameters,
+    baseUrl,
+    endpointCheck,
+    checkingEndpoint,
     selectedModelId,
     apiKey,
     loading,
     saving,
     error,
     saveMessage,
+    configError,
     canSave,
+    detected,
+    importing,
     selectProvider,
     selectModel,
     setApiKey,
+    setParameters,
+    setBaseUrl,
+    checkEndpoint,
     save,
+    importDetected,
+    reloadProviders,
   } = useLlmStatus();
+  const [chosenIds, setChosenIds] = useState<string[]>([]);
+  const importable = detected.filter((item) => !item.already_stored);
 
   return (
     <section className="space-y-5">
@@ -35,6 +55,44 @@ export function LlmConfigStep({ onContinue }: LlmConfigStepProps) {
         ) : null}
       </header>
 
+      {importable.length > 0 ? (
+        <div className="space-y-3 rounded-2xl border border-slate-200 bg-white p-4 shadow-sm">
+          <p className="text-sm font-medium text-slate-700">Existing API keys found</p>
+          <ul className="space-y-2">
+            {importable.map((item) => (
+              <li key={item.id}>
+                <label className="flex items-center gap-2 text-sm text-slate-700">
+                  <input
+                    type="checkbox"
+                    checked={chosenIds.includes(item.id)}
+                    onChange={(event) => {
+                      setChosenIds((ids) =>
+                        event.target.checked
+                          ? [...ids, item.id]
+                          : ids.filter((id) => id !== item.id),
+                      );
+                    }}
+                  />
+                  <span className="font-mono">{item.env_var}</span>
+                  <span className="font-mono text-slate-500">{item.preview}</span>
+                  <span className="truncate text-xs text-slate-500">{item.location}</span>
+                </label>
+              </li>
+            ))}
+          </ul>
+          <button
+            type="button"
+            onClick={() => {
+              void importDetected(chosenIds).then(() => setChosenIds([]));
+            }}
+            disabled={chosenIds.length === 0 || importing}
+            className="rounded-lg border border-slate-300 px-4 py-2 text-sm font-medium text-slate-700 disabled:cursor-not-allowed disabled:opacity-40"
+          >
+            {importing ? "Importing..." : "Import selected"}
+          </button>
+        </div>
+      ) : null}
+
       <div className="grid gap-3 sm:grid-cols-3">
         {providers.map((provider) => {
           const active = selectedProviderId === provider.id;
@@ -82,6 +140,53 @@ export function LlmConfigStep({ onContinue }: LlmConfigStepProps) {
             </select>
           </div>
 
+          {acceptedParameters.length > 0 ? (
+            <ModelParametersFields
+              accepted={acceptedParameters}
+              values={parameters}
+              onChange={setParameters}
+            />
+          ) : null}
+
+          {selectedProvider.base_url && !selectedProvider.custom ? (
+            <div className="space-y-2">
+              <label htmlFor="base-url" className="block text-sm font-medium text-slate-700">
+                Endpoint
+              </label>
+              <div className="flex gap-2">
+                <input
+                  id="base-url"
+                  type="url"
+                  value={baseUrl}
+                  onChange={(event) => {
+                    setBaseUrl(event.target.value);
+                  }}
+                  className="w-full rounded-lg border border-slate-300 px-3 py-2 text-sm"
+                  placeholder={selectedProvider.base_url}
+                />
+                <button
+                  type="button"
+                  onClick={() => {
+                    void checkEndpoint();
+                  }}
+                  disabled={checkingEndpoint}
+                  className="rounded-lg border border-slate-300 px-4 py-2 text-sm font-medium text-slate-700 disabled:cursor-not-allowed disabled:opacity-40"
+                >
+                  {checkingEndpoint ? "Checking..." : "Check"}
+                </button>
+              </div>
+              {endpointCheck ? (
+                <p
+                  className={`text-xs ${endpointCheck.reachable ? "text-emerald-700" : "text-rose-700"}`}
+                >
+                  {endpointCheck.detail}
+                </p>
+              ) : (
+                <p className="text-xs text-slate-500">Leave empty to use the default endpoint.</p>
+              )}
+            </div>
+          ) : null}
+
           {selectedProvider.requires_api_key ? (
             <div className="space-y-2">
               <label htmlFor="api-key" className="block text-sm font-medium text-slate-700">
@@ -101,9 +206,24 @@ export function LlmConfigStep({ onContinue }: LlmConfigStepProps) {
           ) : (
             <p className="text-sm text-emerald-700">This provider works without an API key.</p>
           )}
+
+          {selectedProvider.id === "ollama" ? (
+            <OllamaPanel
+              key={selectedProvider.base_url_override ?? "default"}
+              selectedModelId={selectedModelId}
+              onModelsChanged={() => {
+                void reloadProviders();
+              }}
+            />
+          ) : null}
         </div>
       ) : null}
 
+      {configError ? (
+        <div className="rounded-xl border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-800">
+          A config file was edited and no longer parses: {configError}
+        </div>
+      ) : null}
       {error ? (
         <div className="rounded-xl border border-rose-200 bg-rose-50 px-3 py-2 text-sm text-rose-700">
           {error}
@@ -140,6 +260,15 @@ export function LlmConfigStep({ onContinue }: LlmConfigStepProps) {
         >
           Continue
         </button>
+        {onSkip ? (
+          <button
+            type="button"
+            onClick={onSkip}
+            className="rounded-lg px-4 py-2 text-sm font-medium text-slate-500 hover:text-slate-700"
+          >
+            Skip
+          </button>
+        ) : null}
       </div>
     </section>
   );
@@ -0,0 +1,89 @@
+import type { ModelParameter, ModelParameters } from "./types";
+
+const THINKING_LEVELS = ["off", "minimal", "low", "medium", "high"];
+
+const LABELS: Record<ModelParameter["name"], string> = {
+  thinking: "Thinking level",
+  temperature: "Temperature",
+  max_tokens: "Max output tokens",
+  context_window: "Context window",
+};
+
+interface ModelParametersFieldsProps {
+  accepted: ModelParameter[];
+  values: ModelParameters;
+  onChange: (values: ModelParameters) => void;
+}
+
+function rangeHint(parameter: ModelParameter): string {
+  if (parameter.min !== null && parameter.max !== null) {
+    return `${parameter.min}–${parameter.max}`;
+  }
+  if (parameter.max !== null) {
+    return `up to ${parameter.max}`;
+  }
+  return parameter.min !== null ? `at least ${parameter.min}` : "";
+}
+
+export function ModelParametersFields({ accepted, values, onChange }: ModelParametersFieldsProps) {
+  return (
+    <details className="rounded-xl border border-slate-200 p-3">
+      <summary className="cursor-pointer text-sm font-medium text-slate-700">
+        Model parameters
+      </summary>
+      <p className="mt-2 text-xs text-slate-500">Leave a field empty to use OpenClaw's default.</p>
+      <div className="mt-3 grid gap-3 sm:grid-cols-2">
+        {accepted.map((parameter) => {
+          const id = `model-parameter-${parameter.name}`;
+          if (parameter.name === "thinking") {
+            return (
+              <div key={parameter.name}>
+                <label htmlFor={id} className="mb-1 block text-sm text-slate-700">
+                  {LABELS.thinking}
+                </label>
+                <select
+                  id={id}
+                  className="w-full rounded-lg border border-slate-300 px-3 py-2 text-sm"
+                  value={values.thinking ?? ""}
+                  onChange={(event) => {
+                    onChange({ ...values, thinking: event.target.value || null });
+                  }}
+                >
+                  <option value="">Default</option>
+                  {(parameter.values ?? THINKING_LEVELS).map((level) => (
+                    <option key={level} value={level}>
+                      {level}
+                    </option>
+                  ))}
+                </select>
+              </div>
+            );
+          }
+
+          const value = values[parameter.name];
+          return (
+            <div key={parameter.name}>
+              <label htmlFor={id} className="mb-1 block text-sm text-slate-700">
+                {LABELS[parameter.name]}
+                <span className="ml-1 text-xs text-slate-500">{rangeHint(parameter)}</span>
+              </label>
+              <input
+                id={id}
+                type="number"
+                min={parameter.min ?? undefined}
+                max={parameter.max ?? undefined}
+                step={parameter.name === "temperature" ? 0.1 : 1}
+                value={value ?? ""}
+                onChange={(event) => {
+                  const raw = event.target.value;
+                  onChange({ ...values, [parameter.name]: raw === "" ? null : Number(raw) });
+                }}
+                className="w-full rounded-lg border border-slate-300 px-3 py-2 text-sm"
+              />
+            </div>
+          );
+        })}
+      </div>
+    </details>
+  );
+}
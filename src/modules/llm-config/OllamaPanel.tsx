@@ -0,0 +1,112 @@
+import { useOllama } from "./useOllama";
+
+interface OllamaPanelProps {
+  /** Model id picked in the model selector, e.g. "ollama/llama3.2". */
+  selectedModelId: string | null;
+  onModelsChanged: () => void;
+}
+
+function formatSize(bytes: number): string {
+  if (bytes >= 1_000_000_000) {
+    return `${(bytes / 1_000_000_000).toFixed(1)} GB`;
+  }
+  return `${Math.round(bytes / 1_000_000)} MB`;
+}
+
+export function OllamaPanel({ selectedModelId, onModelsChanged }: OllamaPanelProps) {
+  const { status, models, progress, loading, pulling, error, refresh, pull, remove } =
+    useOllama(onModelsChanged);
+
+  const selectedDownloaded = models.some((model) => model.model_id === selectedModelId);
+
+  if (loading && !status) {
+    return <p className="text-xs text-slate-500">Checking Ollama...</p>;
+  }
+
+  return (
+    <div className="space-y-3 rounded-xl border border-slate-200 p-3">
+      <div className="flex items-center justify-between">
+        <p className="text-sm font-medium text-slate-700">
+          Ollama{status?.version ? ` ${status.version}` : ""}
+        </p>
+        <button
+          type="button"
+          onClick={() => {
+            void refresh();
+          }}
+          className="text-xs font-medium text-slate-500 hover:text-slate-700"
+        >
+          Check again
+        </button>
+      </div>
+
+      {!status?.installed ? (
+        <p className="text-sm text-amber-800">
+          Ollama is not installed and nothing answers at {status?.base_url ?? "its endpoint"}.
+          Install it from ollama.com or set the endpoint of a remote Ollama, then check again.
+        </p>
+      ) : !status.running ? (
+        <p className="text-sm text-amber-800">
+          Ollama is installed but not running at {status.base_url}. Start it, then check again.
+        </p>
+      ) : (
+        <>
+          {models.length > 0 ? (
+            <ul className="space-y-1">
+              {models.map((model) => (
+                <li key={model.name} className="flex items-center justify-between gap-2 text-sm">
+                  <span className="font-mono text-slate-700">{model.name}</span>
+                  <span className="ml-auto text-xs text-slate-500">
+                    {[model.parameter_size, formatSize(model.size)].filter(Boolean).join(" · ")}
+                  </span>
+                  <button
+                    type="button"
+                    onClick={() => {
+                      void remove(model.name);
+                    }}
+                    disabled={pulling !== null}
+                    className="text-xs font-medium text-rose-600 hover:text-rose-700 disabled:opacity-40"
+                  >
+                    Delete
+                  </button>
+                </li>
+              ))}
+            </ul>
+          ) : (
+            <p className="text-sm text-slate-600">No models downloaded yet.</p>
+          )}
+
+          {selectedModelId && !selectedDownloaded ? (
+            <button
+              type="button"
+              onClick={() => {
+                void pull(selectedModelId);
+              }}
+              disabled={pulling !== null}
+              className="rounded-lg border border-slate-300 px-4 py-2 text-sm font-medium text-slate-700 disabled:cursor-not-allowed disabled:opacity-40"
+            >
+              {pulling ? `Downloading ${pulling}...` : "Download selected model"}
+            </button>
+          ) : null}
+
+          {pulling && progress ? (
+            <div className="space-y-1">
+              <div className="flex items-center justify-between text-xs text-slate-600">
+                <span>{progress.status}</span>
+                <span>{progress.percent === null ? "--" : `${Math.round(progress.percent)}%`}</span>
+              </div>
+              <div className="h-2 overflow-hidden rounded-full bg-slate-200">
+                <div
+                  className="h-full bg-brand transition-all"
+                  style={{ width: progress.percent === null ? "35%" : `${progress.percent}%` }}
+                />
+              </div>
+            </div>
+          ) : null}
+        </>
+      )}
+
+      {error ? <p className="text-sm text-rose-700">{error}</p> : null}
+    </div>
+  );
+}
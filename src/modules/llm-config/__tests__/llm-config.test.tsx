@@ -1,23 +1,29 @@
 import { render, screen, waitFor } from "@testing-library/react";
-import { renderHook, fireEvent } from "@testing-library/react";
+import { act, renderHook, fireEvent } from "@testing-library/react";
 import { beforeEach, describe, expect, it, vi } from "vitest";
 import { LlmConfigStep } from "../LlmConfigStep";
 import { useLlmStatus } from "../useLlmStatus";
 
-const { invokeMock } = vi.hoisted(() => ({
+const { invokeMock, listenMock } = vi.hoisted(() => ({
   invokeMock: vi.fn(),
+  listenMock: vi.fn(),
 }));
 
 vi.mock("@tauri-apps/api/core", () => ({
   invoke: invokeMock,
 }));
 
+vi.mock("@tauri-apps/api/event", () => ({
+  listen: listenMock,
+}));
+
 const providers = [
   {
     id: "anthropic",
     display_name: "Anthropic",
     requires_api_key: true,
     env_var: "ANTHROPIC_API_KEY",
+    parameters: [{ name: "temperature", min: 0, max: 1, values: null }],
     models: [{ id: "anthropic/claude-sonnet-4-5-20250929", display_name: "Claude", is_default: true }],
   },
   {
@@ -25,6 +31,7 @@ const providers = [
     display_name: "Ollama",
     requires_api_key: false,
     env_var: null,
+    base_url: "http://127.0.0.1:11434",
     models: [{ id: "ollama/llama3.2", display_name: "Llama 3.2", is_default: true }],
   },
 ];
@@ -32,6 +39,8 @@ const providers = [
 describe("llm-config module", () => {
   beforeEach(() => {
     invokeMock.mockReset();
+    listenMock.mockReset();
+    listenMock.mockResolvedValue(() => {});
     invokeMock.mockImplementation((command: string) => {
       if (command === "list_providers") {
         return Promise.resolve(providers);
@@ -40,9 +49,26 @@ describe("llm-config module", () => {
         return Promise.resolve({
           selected_provider: "anthropic",
           selected_model: "anthropic/claude-sonnet-4-5-20250929",
+          model_chain: ["anthropic/claude-sonnet-4-5-20250929"],
           has_api_key: false,
+          keys_locked: false,
+          warnings: [],
+          active_profile: null,
         });
       }
+      if (command === "detect_existing_credentials") {
+        return Promise.resolve([
+          {
+            id: "environment:process environment:ANTHROPIC_API_KEY",
+            source: "environment",
+            location: "process environment",
+            env_var: "ANTHROPIC_API_KEY",
+            provider: "anthropic",
+            preview: "sk-a…3f9c",
+            already_stored: false,
+          },
+        ]);
+      }
       return Promise.resolve(undefined);
     });
   });
@@ -83,4 +109,164 @@ describe("llm-config module", () => {
 
     expect(screen.getByRole("button", { name: "Continue" })).toBeEnabled();
   });
+
+  it("imports a detected key and can be skipped", async () => {
+    const onSkip = vi.fn();
+    render(<LlmConfigStep onContinue={vi.fn()} onSkip={onSkip} />);
+
+    await waitFor(() => {
+      expect(screen.getByText("sk-a…3f9c")).toBeInTheDocument();
+    });
+
+    fireEvent.click(screen.getByRole("checkbox"));
+    fireEvent.click(screen.getByRole("button", { name: "Import selected" }));
+
+    await waitFor(() => {
+      expect(invokeMock).toHaveBeenCalledWith("import_detected_credentials", {
+        ids: ["environment:process environment:ANTHROPIC_API_KEY"],
+      });
+    });
+
+    fireEvent.click(screen.getByRole("button", { name: "Skip" }));
+    expect(onSkip).toHaveBeenCalled();
+  });
+
+  it("saves model parameters with the model", async () => {
+    render(<LlmConfigStep onContinue={vi.fn()} />);
+
+    const temperature = await screen.findByLabelText(/Temperature/);
+    fireEvent.change(temperature, { target: { value: "0.4" } });
+    fireEvent.click(screen.getByRole("button", { name: "Save" }));
+
+    await waitFor(() => {
+      expect(invokeMock).toHaveBeenCalledWith("save_llm_config", {
+        request: {
+          provider: "anthropic",
+          model: "anthropic/claude-sonnet-4-5-20250929",
+          api_key: undefined,
+          parameters: { thinking: null, temperature: 0.4, max_tokens: null, context_window: null },
+        },
+      });
+    });
+  });
+
+  it("checks and saves a custom Ollama endpoint", async () => {
+    const fallback = invokeMock.getMockImplementation();
+    invokeMock.mockImplementation((command: string, args?: unknown) => {
+      if (command === "check_provider_endpoint") {
+        return Promise.resolve({
+          provider: "ollama",
+          base_url: "http://10.0.0.5:11434",
+          reachable: true,
+          detail: "Ollama 0.5.7 at http://10.0.0.5:11434",
+          http_status: 200,
+        });
+      }
+      return fallback?.(command, args);
+    });
+    render(<LlmConfigStep onContinue={vi.fn()} />);
+
+    await waitFor(() => {
+      expect(screen.getByText("Anthropic")).toBeInTheDocument();
+    });
+    fireEvent.click(screen.getByRole("button", { name: "Ollama No API key required" }));
+
+    const endpoint = await screen.findByLabelText("Endpoint");
+    fireEvent.change(endpoint, { target: { value: "http://10.0.0.5:11434" } });
+    fireEvent.click(screen.getByRole("button", { name: "Check" }));
+    expect(await screen.findByText("Ollama 0.5.7 at http://10.0.0.5:11434")).toBeInTheDocument();
+    expect(invokeMock).toHaveBeenCalledWith("check_provider_endpoint", {
+      provider: "ollama",
+      baseUrl: "http://10.0.0.5:11434",
+    });
+
+    fireEvent.click(screen.getByRole("button", { name: "Save" }));
+    await waitFor(() => {
+      expect(invokeMock).toHaveBeenCalledWith(
+        "save_llm_config",
+        {
+          request: expect.objectContaining({
+            provider: "ollama",
+            base_url: "http://10.0.0.5:11434",
+          }),
+        },
+      );
+    });
+  });
+
+  it("downloads the selected Ollama model", async () => {
+    const pulled = {
+      name: "llama3.2:latest",
+      model_id: "ollama/llama3.2",
+      size: 2_019_393_189,
+      modified_at: null,
+      parameter_size: "3.2B",
+      quantization: "Q4_K_M",
+    };
+    const fallback = invokeMock.getMockImplementation();
+    invokeMock.mockImplementation((command: string, args?: unknown) => {
+      if (command === "get_ollama_status") {
+        return Promise.resolve({
+          installed: true,
+          binary_path: "/usr/local/bin/ollama",
+          running: true,
+          version: "0.5.7",
+          base_url: "http://127.0.0.1:11434",
+        });
+      }
+      if (command === "list_ollama_models") {
+        return Promise.resolve([]);
+      }
+      if (command === "pull_ollama_model") {
+        return Promise.resolve([pulled]);
+      }
+      return fallback?.(command, args);
+    });
+    render(<LlmConfigStep onContinue={vi.fn()} />);
+
+    await waitFor(() => {
+      expect(screen.getByText("Anthropic")).toBeInTheDocument();
+    });
+    fireEvent.click(screen.getByRole("button", { name: "Ollama No API key required" }));
+
+    await waitFor(() => {
+      expect(screen.getByText("No models downloaded yet.")).toBeInTheDocument();
+    });
+    fireEvent.click(screen.getByRole("button", { name: "Download selected model" }));
+
+    await waitFor(() => {
+      expect(screen.getByText("llama3.2:latest")).toBeInTheDocument();
+    });
+    expect(invokeMock).toHaveBeenCalledWith("pull_ollama_model", { model: "ollama/llama3.2" });
+  });
+
+  it("reports an invalid external config edit", async () => {
+    let onChange: ((event: { payload: unknown }) => void) | undefined;
+    listenMock.mockImplementation((_name: string, cb: (event: { payload: unknown }) => void) => {
+      onChange = cb;
+      return Promise.resolve(() => {});
+    });
+    const { result } = renderHook(() => useLlmStatus());
+
+    await waitFor(() => {
+      expect(onChange).toBeDefined();
+    });
+
+    act(() => {
+      onChange?.({
+        payload: {
+          file: "openclaw",
+          path: "/home/me/.openclaw/openclaw.json",
+          valid: false,
+          error: "unexpected end of input",
+          changes: [],
+          gateway_restarted: false,
+        },
+      });
+    });
+
+    expect(result.current.configError).toBe(
+      "/home/me/.openclaw/openclaw.json: unexpected end of input",
+    );
+  });
 });
@@ -1,4 +1,39 @@
 export { LlmConfigStep } from "./LlmConfigStep";
 export { LlmConfigPage } from "./LlmConfigPage";
 export { useLlmStatus } from "./useLlmStatus";
-export type { LlmConfigState, ModelInfo, ProviderInfo } from "./types";
+export { useOllama } from "./useOllama";
+export type {
+  ApiKeyInfo,
+  BundledPlugin,
+  CatalogSource,
+  ConfigChange,
+  ConfigChangedPayload,
+  ConfigNode,
+  ConfigPatch,
+  ConfigPatchResult,
+  ConfigVersion,
+  ConfigWatchStatus,
+  CredentialField,
+  CustomProvider,
+  DetectedCredential,
+  EndpointCheck,
+  FieldError,
+  LlmConfigState,
+  LlmProfile,
+  ModelInfo,
+  ModelParameter,
+  ModelParameters,
+  OllamaModel,
+  OllamaPullProgress,
+  OllamaStatus,
+  OpenClawConfigView,
+  ProfileActivation,
+  ProviderCatalogStatus,
+  ProviderInfo,
+  SaveLlmConfigRequest,
+  SecretStoreStatus,
+  SetupBundleInfo,
+  SetupImportPreview,
+  SetupImportResult,
+  WatchedFileStatus,
+} from "./types";
@@ -2,6 +2,44 @@ export interface ModelInfo {
   id: string;
   display_name: string;
   is_default: boolean;
+  discovered: boolean;
+  parameters: ModelParameter[] | null;
+}
+
+export interface ModelParameter {
+  name: "thinking" | "temperature" | "max_tokens" | "context_window";
+  min: number | null;
+  max: number | null;
+  values: string[] | null;
+}
+
+export interface ModelParameters {
+  thinking: string | null;
+  temperature: number | null;
+  max_tokens: number | null;
+  context_window: number | null;
+}
+
+/** Argument of `save_llm_config`; omitted fields keep what is configured. */
+export interface SaveLlmConfigRequest {
+  provider: string;
+  model: string;
+  api_key?: string;
+  credentials?: Record<string, string>;
+  fallbacks?: string[];
+  key_label?: string;
+  parameters?: ModelParameters;
+  /** Blank goes back to the provider's default endpoint. */
+  base_url?: string;
+}
+
+export interface CredentialField {
+  env_var: string;
+  label: string;
+  kind: "secret" | "text" | "url";
+  required: boolean;
+  group: string | null;
+  default: string | null;
 }
 
 export interface ProviderInfo {
@@ -9,11 +47,224 @@ export interface ProviderInfo {
   display_name: string;
   requires_api_key: boolean;
   env_var: string | null;
+  credentials: CredentialField[];
   models: ModelInfo[];
+  base_url: string | null;
+  base_url_override: string | null;
+  custom: boolean;
+  parameters: ModelParameter[];
+}
+
+export interface EndpointCheck {
+  provider: string;
+  base_url: string;
+  reachable: boolean;
+  detail: string;
+  http_status: number | null;
+}
+
+export interface CustomProvider {
+  id: string;
+  display_name: string;
+  base_url: string;
+  env_var: string | null;
+  models: string[];
 }
 
 export interface LlmConfigState {
   selected_provider: string | null;
   selected_model: string | null;
+  model_chain: string[];
   has_api_key: boolean;
+  keys_locked: boolean;
+  warnings: string[];
+  active_profile: string | null;
+  model_parameters: Record<string, ModelParameters>;
+}
+
+export interface LlmProfile {
+  name: string;
+  provider: string;
+  model: string;
+  fallbacks: string[];
+  key_label: string | null;
+}
+
+export interface ApiKeyInfo {
+  provider: string | null;
+  env_var: string;
+  label: string;
+  preview: string;
+  selected: boolean;
+  added_at: string | null;
+  rotated_at: string | null;
+}
+
+export interface DetectedCredential {
+  id: string;
+  source: "environment" | "dotenv" | "openclaw";
+  location: string;
+  env_var: string;
+  provider: string | null;
+  preview: string;
+  already_stored: boolean;
+}
+
+export interface ProfileActivation {
+  profile: LlmProfile;
+  state: LlmConfigState;
+  gateway_running: boolean;
+}
+
+export interface SecretStoreStatus {
+  backend: string | null;
+  key_source: string | null;
+  locked: boolean;
+}
+
+export interface CatalogSource {
+  name: "bundled" | "remote" | "user";
+  location: string;
+  catalog_version: string | null;
+  loaded: boolean;
+  error: string | null;
+}
+
+export interface ProviderCatalogStatus {
+  remote_url: string | null;
+  sources: CatalogSource[];
+}
+
+export interface ConfigChange {
+  path: string;
+  before: unknown;
+  after: unknown;
+}
+
+export interface ConfigVersion {
+  id: string;
+  created_at: string;
+  reason: string;
+  restored_from: string | null;
+}
+
+export interface ConfigChangedPayload {
+  file: "openclaw" | "settings";
+  path: string;
+  valid: boolean;
+  error: string | null;
+  changes: ConfigChange[];
+  gateway_restarted: boolean;
+}
+
+export interface WatchedFileStatus {
+  file: "openclaw" | "settings";
+  path: string;
+  exists: boolean;
+  valid: boolean;
+  error: string | null;
+}
+
+export interface ConfigWatchStatus {
+  files: WatchedFileStatus[];
+  restart_gateway_on_change: boolean;
+}
+
+export interface FieldError {
+  path: string;
+  message: string;
+}
+
+export interface ConfigNode {
+  path: string;
+  key: string;
+  kind: "object" | "array" | "string" | "number" | "boolean" | "null";
+  value: unknown;
+  secret: boolean;
+  description: string | null;
+  children: ConfigNode[];
+}
+
+export interface OpenClawConfigView {
+  path: string;
+  exists: boolean;
+  tree: ConfigNode;
+  schema: { name: "bundled"; location: string };
+  errors: FieldError[];
+}
+
+export interface ConfigPatch {
+  op: "add" | "replace" | "remove";
+  path: string;
+  value?: unknown;
+}
+
+export interface ConfigPatchResult {
+  applied: boolean;
+  errors: FieldError[];
+  config: OpenClawConfigView;
+}
+
+export interface BundledPlugin {
+  name: string;
+  spec: string;
+  version: string | null;
+}
+
+export interface SetupBundleInfo {
+  path: string;
+  created_at: string;
+  app_version: string;
+  includes_secrets: boolean;
+  has_openclaw_config: boolean;
+  profiles: string[];
+  versions: { node: string | null; openclaw: string | null };
+  plugins: BundledPlugin[];
+}
+
+export interface SetupImportPreview {
+  bundle: SetupBundleInfo;
+  settings_changes: ConfigChange[];
+  config_changes: ConfigChange[];
+  /** Provider endpoints the import changes; applied only with `confirmBaseUrls`. */
+  base_url_changes: ConfigChange[];
+  /** A different catalog URL; applied only with `confirmCatalogUrl`. */
+  catalog_url_change: ConfigChange | null;
+  errors: FieldError[];
+  keys_to_add: Omit<ApiKeyInfo, "provider">[];
+  /** npm plugins the import installs; applied only with `confirmPlugins`. */
+  plugins_to_install: BundledPlugin[];
+  notes: string[];
+}
+
+export interface SetupImportResult {
+  applied: boolean;
+  preview: SetupImportPreview;
+  warnings: string[];
+}
+
+export interface OllamaStatus {
+  installed: boolean;
+  binary_path: string | null;
+  running: boolean;
+  version: string | null;
+  base_url: string;
+}
+
+export interface OllamaModel {
+  name: string;
+  model_id: string;
+  size: number;
+  modified_at: string | null;
+  parameter_size: string | null;
+  quantization: string | null;
+}
+
+export interface OllamaPullProgress {
+  model: string;
+  status: string;
+  digest: string | null;
+  completed: number | null;
+  total: number | null;
+  percent: number | null;
 }
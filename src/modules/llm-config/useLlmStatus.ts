@@ -1,6 +1,23 @@
 import { useCallback, useEffect, useMemo, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { LlmConfigState, ProviderInfo } from "./types";
+import { listen } from "@tauri-apps/api/event";
+import type {
+  ConfigChangedPayload,
+  DetectedCredential,
+  EndpointCheck,
+  LlmConfigState,
+  ModelParameter,
+  ModelParameters,
+  ProviderInfo,
+  SaveLlmConfigRequest,
+} from "./types";
+
+const NO_PARAMETERS: ModelParameters = {
+  thinking: null,
+  temperature: null,
+  max_tokens: null,
+  context_window: null,
+};
 
 export function useLlmStatus(): {
   providers: ProviderInfo[];
@@ -11,13 +28,26 @@ export function useLlmStatus(): {
   saving: boolean;
   error: string | null;
   saveMessage: string | null;
+  configError: string | null;
   selectedProvider: ProviderInfo | null;
+  acceptedParameters: ModelParameter[];
+  parameters: ModelParameters;
+  baseUrl: string;
+  endpointCheck: EndpointCheck | null;
+  checkingEndpoint: boolean;
   canSave: boolean;
+  detected: DetectedCredential[];
+  importing: boolean;
   selectProvider: (providerId: string) => void;
   selectModel: (modelId: string) => void;
   setApiKey: (value: string) => void;
+  setParameters: (values: ModelParameters) => void;
+  setBaseUrl: (value: string) => void;
+  checkEndpoint: () => Promise<void>;
   save: () => Promise<void>;
+  importDetected: (ids: string[]) => Promise<void>;
   refresh: () => Promise<void>;
+  reloadProviders: () => Promise<void>;
 } {
   const [providers, setProviders] = useState<ProviderInfo[]>([]);
   const [selectedProviderId, setSelectedProviderId] = useState<string | null>(null);
@@ -27,12 +57,37 @@ export function useLlmStatus(): {
   const [saving, setSaving] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [saveMessage, setSaveMessage] = useState<string | null>(null);
+  const [configError, setConfigError] = useState<string | null>(null);
+  const [detected, setDetected] = useState<DetectedCredential[]>([]);
+  const [importing, setImporting] = useState(false);
+  const [configuredParameters, setConfiguredParameters] = useState<
+    Record<string, ModelParameters>
+  >({});
+  const [parameters, setParameters] = useState<ModelParameters>(NO_PARAMETERS);
+  const [baseUrl, setBaseUrl] = useState("");
+  const [endpointCheck, setEndpointCheck] = useState<EndpointCheck | null>(null);
+  const [checkingEndpoint, setCheckingEndpoint] = useState(false);
 
   const selectedProvider = useMemo(
     () => providers.find((provider) => provider.id === selectedProviderId) ?? null,
     [providers, selectedProviderId],
   );
 
+  const acceptedParameters = useMemo(() => {
+    const model = selectedProvider?.models.find((item) => item.id === selectedModelId);
+    return model?.parameters ?? selectedProvider?.parameters ?? [];
+  }, [selectedModelId, selectedProvider]);
+
+  useEffect(() => {
+    setParameters((selectedModelId && configuredParameters[selectedModelId]) || NO_PARAMETERS);
+  }, [configuredParameters, selectedModelId]);
+
+  const savedBaseUrl = selectedProvider?.base_url_override ?? "";
+  useEffect(() => {
+    setBaseUrl(savedBaseUrl);
+    setEndpointCheck(null);
+  }, [savedBaseUrl, selectedProviderId]);
+
   const refresh = useCallback(async () => {
     setLoading(true);
     setError(null);
@@ -45,6 +100,7 @@ export function useLlmStatus(): {
       ]);
 
       setProviders(providerList);
+      setConfiguredParameters(state.model_parameters ?? {});
 
       if (state.selected_provider) {
         setSelectedProviderId(state.selected_provider);
@@ -63,12 +119,57 @@ export function useLlmStatus(): {
     } finally {
       setLoading(false);
     }
+
+    // Detection is a convenience; a locked key store must not block setup.
+    try {
+      setDetected(await invoke<DetectedCredential[]>("detect_existing_credentials"));
+    } catch {
+      setDetected([]);
+    }
+  }, []);
+
+  /** Re-reads the provider list only, keeping the current selection. */
+  const reloadProviders = useCallback(async () => {
+    try {
+      setProviders(await invoke<ProviderInfo[]>("list_providers"));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to load providers");
+    }
   }, []);
 
   useEffect(() => {
     void refresh();
   }, [refresh]);
 
+  useEffect(() => {
+    let disposed = false;
+    let unlistenChanges: (() => void) | null = null;
+
+    void listen<ConfigChangedPayload>("config:changed", (event) => {
+      if (disposed) {
+        return;
+      }
+      if (event.payload.valid) {
+        setConfigError(null);
+        void refresh();
+      } else {
+        setConfigError(`${event.payload.path}: ${event.payload.error ?? "invalid config"}`);
+      }
+    }).then((unlisten) => {
+      unlistenChanges = unlisten;
+      if (disposed) {
+        unlisten();
+      }
+    });
+
+    return () => {
+      disposed = true;
+      if (unlistenChanges) {
+        unlistenChanges();
+      }
+    };
+  }, [refresh]);
+
   const selectProvider = useCallback(
     (providerId: string) => {
       setSelectedProviderId(providerId);
@@ -87,6 +188,27 @@ export function useLlmStatus(): {
     setSaveMessage(null);
   }, []);
 
+  const checkEndpoint = useCallback(async () => {
+    if (!selectedProvider) {
+      return;
+    }
+
+    setCheckingEndpoint(true);
+    setError(null);
+    try {
+      setEndpointCheck(
+        await invoke<EndpointCheck>("check_provider_endpoint", {
+          provider: selectedProvider.id,
+          baseUrl: baseUrl.trim() || undefined,
+        }),
+      );
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to check endpoint");
+    } finally {
+      setCheckingEndpoint(false);
+    }
+  }, [baseUrl, selectedProvider]);
+
   const canSave = useMemo(
     () => Boolean(selectedProvider && selectedModelId),
     [selectedModelId, selectedProvider],
@@ -103,19 +225,54 @@ export function useLlmStatus(): {
     setSaveMessage(null);
 
     try {
-      await invoke("save_llm_config", {
+      const request: SaveLlmConfigRequest = {
         provider: selectedProvider.id,
         model: selectedModelId,
-        apiKey:
+        api_key:
           selectedProvider.requires_api_key && apiKey.trim().length > 0 ? apiKey : undefined,
-      });
+        parameters: acceptedParameters.length > 0 ? parameters : undefined,
+        base_url: baseUrl.trim() !== savedBaseUrl ? baseUrl.trim() : undefined,
+      };
+      await invoke("save_llm_config", { request });
+      setConfiguredParameters((current) => ({ ...current, [selectedModelId]: parameters }));
+      if (baseUrl.trim() !== savedBaseUrl) {
+        setProviders(await invoke<ProviderInfo[]>("list_providers"));
+      }
       setSaveMessage("Configuration saved");
     } catch (err) {
       setError(err instanceof Error ? err.message : "Failed to save configuration");
     } finally {
       setSaving(false);
     }
-  }, [apiKey, selectedModelId, selectedProvider]);
+  }, [
+    acceptedParameters,
+    apiKey,
+    baseUrl,
+    parameters,
+    savedBaseUrl,
+    selectedModelId,
+    selectedProvider,
+  ]);
+
+  const importDetected = useCallback(async (ids: string[]) => {
+    if (ids.length === 0) {
+      return;
+    }
+
+    setImporting(true);
+    setError(null);
+    setSaveMessage(null);
+
+    try {
+      await invoke("import_detected_credentials", { ids });
+      setDetected(await invoke<DetectedCredential[]>("detect_existing_credentials"));
+      setSaveMessage(`Imported ${ids.length} existing key${ids.length === 1 ? "" : "s"}`);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to import keys");
+    } finally {
+      setImporting(false);
+    }
+  }, []);
 
   return {
     providers,
@@ -126,12 +283,25 @@ export function useLlmStatus(): {
     saving,
     error,
     saveMessage,
+    configError,
     selectedProvider,
+    acceptedParameters,
+    parameters,
+    baseUrl,
+    endpointCheck,
+    checkingEndpoint,
     canSave,
+    detected,
+    importing,
     selectProvider,
     selectModel,
     setApiKey,
+    setParameters,
+    setBaseUrl,
+    checkEndpoint,
     save,
+    importDetected,
     refresh,
+    reloadProviders,
   };
 }
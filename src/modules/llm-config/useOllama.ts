@@ -0,0 +1,107 @@
+import { useCallback, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { OllamaModel, OllamaPullProgress, OllamaStatus } from "./types";
+
+export function useOllama(onModelsChanged?: () => void): {
+  status: OllamaStatus | null;
+  models: OllamaModel[];
+  progress: OllamaPullProgress | null;
+  loading: boolean;
+  pulling: string | null;
+  error: string | null;
+  refresh: () => Promise<void>;
+  pull: (model: string) => Promise<void>;
+  remove: (model: string) => Promise<void>;
+} {
+  const [status, setStatus] = useState<OllamaStatus | null>(null);
+  const [models, setModels] = useState<OllamaModel[]>([]);
+  const [progress, setProgress] = useState<OllamaPullProgress | null>(null);
+  const [loading, setLoading] = useState(true);
+  const [pulling, setPulling] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    setLoading(true);
+    setError(null);
+    try {
+      const nextStatus = (await invoke<OllamaStatus>("get_ollama_status")) ?? null;
+      setStatus(nextStatus);
+      setModels(nextStatus?.running ? ((await invoke<OllamaModel[]>("list_ollama_models")) ?? []) : []);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Failed to check Ollama");
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  useEffect(() => {
+    let disposed = false;
+    let unlistenProgress: (() => void) | null = null;
+
+    void listen<OllamaPullProgress>("ollama:pull-progress", (event) => {
+      if (!disposed) {
+        setProgress(event.payload);
+      }
+    }).then((unlisten) => {
+      unlistenProgress = unlisten;
+      if (disposed) {
+        unlisten();
+      }
+    });
+
+    return () => {
+      disposed = true;
+      if (unlistenProgress) {
+        unlistenProgress();
+      }
+    };
+  }, []);
+
+  const pull = useCallback(
+    async (model: string) => {
+      setPulling(model);
+      setProgress(null);
+      setError(null);
+      try {
+        setModels((await invoke<OllamaModel[]>("pull_ollama_model", { model })) ?? []);
+        onModelsChanged?.();
+      } catch (err) {
+        setError(err instanceof Error ? err.message : `Failed to download ${model}`);
+      } finally {
+        setPulling(null);
+        setProgress(null);
+      }
+    },
+    [onModelsChanged],
+  );
+
+  const remove = useCallback(
+    async (model: string) => {
+      setError(null);
+      try {
+        setModels((await invoke<OllamaModel[]>("delete_ollama_model", { model })) ?? []);
+        onModelsChanged?.();
+      } catch (err) {
+        setError(err instanceof Error ? err.message : `Failed to delete ${model}`);
+      }
+    },
+    [onModelsChanged],
+  );
+
+  return {
+    status,
+    models,
+    progress,
+    loading,
+    pulling,
+    error,
+    refresh,
+    pull,
+    remove,
+  };
+}
@@ -1,4 +1,4 @@
 export { OpenClawInstallerStep } from "./OpenClawInstallerStep";
 export { OpenClawInstallerPage } from "./OpenClawInstallerPage";
 export { useOpenClawStatus } from "./useOpenClawStatus";
-export type { InstallProgress, OpenClawStatus, UpdateInfo } from "./types";
+export type { InstallProgress, OpenClawStatus, ReleaseNotes, UpdateInfo } from "./types";
@@ -8,6 +8,13 @@ export interface UpdateInfo {
   installed_version: string;
   latest_version: string;
   update_available: boolean;
+  release_notes: ReleaseNotes[];
+}
+
+export interface ReleaseNotes {
+  version: string;
+  date: string | null;
+  notes: string;
 }
 
 export interface InstallProgress {
@@ -159,7 +159,7 @@ describe("service-manager module", () => {
           binary_path: "/usr/local/bin/openclaw",
         });
       }
-      if (command === "get_node_env" || command === "load_api_keys") {
+      if (command === "get_node_env") {
         return Promise.resolve({});
       }
       if (command === "start_gateway") {
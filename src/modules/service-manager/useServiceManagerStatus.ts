@@ -115,10 +115,9 @@ export function useServiceManagerStatus(): {
     setError(null);
 
     try {
-      const [openClaw, nodeEnv, keys] = await Promise.all([
+      const [openClaw, nodeEnv] = await Promise.all([
         invoke<OpenClawStatus>("get_openclaw_status"),
         invoke<Record<string, string>>("get_node_env"),
-        invoke<Record<string, string>>("load_api_keys"),
       ]);
 
       if (!openClaw.binary_path) {
@@ -128,10 +127,7 @@ export function useServiceManagerStatus(): {
       await invoke("start_gateway", {
         openclawPath: openClaw.binary_path,
         port: status.port || DEFAULT_PORT,
-        envVars: {
-          ...nodeEnv,
-          ...keys,
-        },
+        envVars: nodeEnv,
       });
     } catch (err) {
       const message = errorMessage(err, "Failed to start gateway");